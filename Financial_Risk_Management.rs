@@ -1,165 +0,0 @@
-use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::{
-    account_info::{next_account_info, AccountInfo},
-    entrypoint,
-    entrypoint::ProgramResult,
-    msg,
-    program_error::ProgramError,
-    pubkey::Pubkey,
-    sysvar::{clock::Clock, Sysvar},
-};
-
-// Define structures for risk assessment
-#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
-pub struct RiskProfile {
-    pub risk_score: u8, // Simplified risk score, could be based on health data, claim history, etc.
-    pub coverage_limit: u64, // Maximum claim amount based on risk, in lamports
-}
-
-// Define structures for financial management
-#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
-pub struct Treasury {
-    pub balance: u64, // Current balance of the treasury in lamports
-    pub reserve_ratio: f32, // Percentage of funds to keep in reserve for liquidity and solvency
-}
-
-// Extend the DAO structure to include financial and risk management components
-#[derive(BorshSerialize, BorshDeserialize, Debug)]
-pub struct HealthInsuranceDAO {
-    pub admin: Pubkey, // Public key of the DAO's administrative account
-    pub members: Vec<Member>, // List of all DAO members
-    pub claims: Vec<Claim>, // List of all submitted claims
-    pub treasury: Treasury, // Financial management component
-    pub risk_profiles: Vec<RiskProfile>, // Risk assessment for each member based on their risk score
-}
-
-// Entrypoint for the program, handling different instructions
-entrypoint!(process_instruction);
-
-fn process_instruction(
-    program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    instruction_data: &[u8],
-) -> ProgramResult {
-    let accounts_iter = &mut accounts.iter();
-    let account = next_account_info(accounts_iter)?;
-
-    // Verify that this program owns the account we're about to modify
-    if account.owner != program_id {
-        return Err(ProgramError::IncorrectProgramId);
-    }
-
-    let mut dao_data = HealthInsuranceDAO::try_from_slice(&account.data.borrow())?;
-
-    match instruction_data[0] {
-        // ... existing instructions ...
-
-        3 => {
-            // Premium Payment - This instruction handles the payment of insurance premiums by members
-            let payer = next_account_info(accounts_iter)?; // Account of the member paying the premium
-            let amount = u64::from_le_bytes(instruction_data[1..9].try_into().unwrap()); // Amount paid, here assumed in lamports
-
-            // Add the premium payment to the treasury balance, ensuring no arithmetic overflow
-            dao_data.treasury.balance = dao_data.treasury.balance.checked_add(amount).ok_or(ProgramError::ArithmeticOverflow)?;
-            msg!("Premium payment of {} lamports received", amount);
-        }
-
-        4 => {
-            // Claim Payout - This instruction processes claim payouts based on risk assessment
-            let member = next_account_info(accounts_iter)?; // The member requesting the payout
-            let claim_index = u64::from_le_bytes(instruction_data[1..9].try_into().unwrap()); // Index of the claim in the claims vector
-            
-            if let Some(claim) = dao_data.claims.get(claim_index as usize) {
-                // Check if the claim amount is within the member's risk profile coverage
-                if let Some(risk_profile) = dao_data.risk_profiles.iter().find(|rp| rp.risk_score == calculate_risk_score(&claim.member)) {
-                    if claim.amount > risk_profile.coverage_limit {
-                        return Err(ProgramError::InvalidArgument); // Claim exceeds coverage limit
-                    }
-
-                    // Ensure there's enough balance in the treasury after accounting for the reserve ratio
-                    let required_reserve = (dao_data.treasury.balance as f32 * dao_data.treasury.reserve_ratio) as u64;
-                    if dao_data.treasury.balance - required_reserve < claim.amount {
-                        return Err(ProgramError::InsufficientFunds); // Not enough funds after reserve
-                    }
-
-                    // Deduct claim amount from treasury balance, simulating the payout
-                    dao_data.treasury.balance = dao_data.treasury.balance.checked_sub(claim.amount).ok_or(ProgramError::ArithmeticOverflow)?;
-                    msg!("Claim payout of {} lamports processed", claim.amount);
-                } else {
-                    return Err(ProgramError::InvalidAccountData); // No risk profile found for this member
-                }
-            } else {
-                return Err(ProgramError::InvalidAccountData); // Claim with this index does not exist
-            }
-        }
-
-        5 => {
-            // Update Risk Profile - This instruction updates or adds a member's risk profile
-            let member = next_account_info(accounts_iter)?; // Account of the member whose risk profile is being updated
-            let new_risk_score = instruction_data[1]; // New risk score for the member
-            let new_coverage_limit = u64::from_le_bytes(instruction_data[2..10].try_into().unwrap()); // New coverage limit in lamports
-
-            // Check if the member already has a risk profile
-            if let Some(risk_profile) = dao_data.risk_profiles.iter_mut().find(|rp| calculate_risk_score(&member.key) == rp.risk_score) {
-                risk_profile.risk_score = new_risk_score;
-                risk_profile.coverage_limit = new_coverage_limit;
-                msg!("Updated risk profile for member {}", member.key);
-            } else {
-                // If no existing profile, add a new one
-                dao_data.risk_profiles.push(RiskProfile {
-                    risk_score: new_risk_score,
-                    coverage_limit: new_coverage_limit,
-                });
-                msg!("New risk profile added for member {}", member.key);
-            }
-        }
-
-        6 => {
-            // Adjust Treasury Reserve Ratio - This allows the admin to adjust the reserve policy
-            let admin = next_account_info(accounts_iter)?;
-            if *admin.key != dao_data.admin {
-                return Err(ProgramError::IncorrectProgramId); // Only the admin should adjust this
-            }
-
-            let new_reserve_ratio = f32::from_le_bytes(instruction_data[1..5].try_into().unwrap()); // New reserve ratio
-            dao_data.treasury.reserve_ratio = new_reserve_ratio;
-            msg!("Treasury reserve ratio updated to {}", new_reserve_ratio);
-        }
-
-        _ => return Err(ProgramError::InvalidInstructionData), // Unrecognized instruction
-    }
-
-    // Save the updated DAO state back into the account's data
-    dao_data.serialize(&mut &mut account.data.borrow_mut()[..])?;
-    Ok(())
-}
-
-// Placeholder for risk score calculation - This would be much more complex in practice
-fn calculate_risk_score(member: &Pubkey) -> u8 {
-    // Example: Member's risk score based on their key. In reality, this would involve health data, claim history, etc.
-    (member.as_ref()[0] % 100) as u8 // Simplified for example, generates a score between 0 and 99
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use solana_program::instruction::{AccountMeta, Instruction};
-    use solana_program_test::*;
-    use solana_sdk::{
-        account::Account,
-        signature::{Keypair, Signer},
-        transaction::Transaction,
-    };
-
-    #[tokio::test]
-    async fn test_premium_payment() {
-        // Test setup and premium payment logic goes here
-    }
-
-    #[tokio::test]
-    async fn test_claim_payout() {
-        // Test setup and claim payout logic goes here
-    }
-
-    // More tests for risk management and treasury operations
-}
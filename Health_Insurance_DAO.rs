@@ -42,6 +42,15 @@ fn process_instruction(
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> ProgramResult {
+    // Fail early with a clear message when the client supplies too few accounts for the instruction
+    let instruction_type = *instruction_data.first().ok_or(ProgramError::InvalidInstructionData)?;
+    if let Some(expected) = expected_account_count(instruction_type) {
+        if accounts.len() < expected {
+            msg!("Instruction {} expects at least {} accounts but received {}", instruction_type, expected, accounts.len());
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+    }
+
     let accounts_iter = &mut accounts.iter();
     let account = next_account_info(accounts_iter)?;
 
@@ -101,6 +110,16 @@ fn process_instruction(
     Ok(())
 }
 
+// Minimum number of accounts each instruction needs, including the DAO account
+fn expected_account_count(instruction_type: u8) -> Option<usize> {
+    match instruction_type {
+        0 => Some(2), // DAO, member
+        1 => Some(3), // DAO, member, treasury
+        2 => Some(2), // DAO, oracle
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
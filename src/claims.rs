@@ -0,0 +1,3932 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    hash::{hash, hashv},
+    msg,
+    program::{invoke, invoke_signed, set_return_data},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_instruction, system_program,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use crate::{require_role, require_signed_role, Claim, ClaimHistory, ClaimStatus, Emergency, HealthInsuranceDAO, Member, PreAuth, ProviderClaims, Role, ServiceType, VerifierPairing};
+use crate::dispute;
+use crate::finance;
+use crate::error::DaoError;
+use crate::instruction::ClaimsInstruction;
+
+// Risk score at or above which new claims are paused while the treasury is in emergency mode
+const EMERGENCY_HIGH_RISK_SCORE: u8 = 70;
+
+// Verifications needed to move a claim to Verified when the DAO hasn't configured a threshold
+const DEFAULT_VERIFICATION_THRESHOLD: u8 = 2;
+
+// Verifier cap applied when the DAO hasn't configured one
+const DEFAULT_MAX_VERIFIERS_PER_CLAIM: u8 = 5;
+
+// Version tag written at the start of every claims export page
+const CLAIMS_EXPORT_VERSION: u8 = 1;
+
+// Claim records per export page, chosen so a page fits within the return data limit
+const CLAIMS_EXPORT_PAGE_SIZE: usize = 10;
+
+// Stale pending claims expired per call, so a large backlog is worked off without exceeding the compute budget
+const EXPIRY_BATCH_SIZE: usize = 20;
+
+// Seed prefix for claim PDAs, derived as ["claim", dao_key, claim_id]
+const CLAIM_SEED: &[u8] = b"claim";
+
+// Seed prefix for the PDAs marking external references as used, derived as ["claim_ref", dao_key, external_ref]
+const CLAIM_REF_SEED: &[u8] = b"claim_ref";
+
+// Seed prefix for the PDAs recording the latest claim for a service, derived as ["claim_service", dao_key, service_key]
+const CLAIM_SERVICE_SEED: &[u8] = b"claim_service";
+
+// Spare bytes allocated with each claim account so reviewers and payouts recorded after submission still fit
+pub(crate) const CLAIM_ACCOUNT_HEADROOM: usize = 512;
+
+// Size of a marker account, which holds the id of the claim it points to
+const CLAIM_MARKER_SPACE: usize = 8;
+
+// Claims one BatchVerify instruction may list, keeping the instruction within the compute budget
+const MAX_BATCH_VERIFY: usize = 10;
+
+// Rolling window over which the annual per-member claim cap is counted
+const CLAIM_YEAR_SECONDS: i64 = 365 * 24 * 60 * 60;
+
+// Consequence of a member's latest fraud strike
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FraudPenalty {
+    Warning,    // Below every configured threshold
+    Suspension, // Suspension threshold reached; the member can no longer submit claims
+    Removal,    // Removal threshold reached; the member is dropped from the DAO
+}
+
+// Fixed-width audit record for a single claim
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct ClaimRecord {
+    pub claim_id: u64,           // Unique identifier for the claim
+    pub member: Pubkey,          // The member who submitted the claim
+    pub provider: Pubkey,        // The provider's public key
+    pub amount: u64,             // The amount of the claim in lamports
+    pub service_date: i64,       // Date of the medical service or event
+    pub status: ClaimStatus,     // Current status of the claim
+}
+
+// One page of the claims export returned to auditors
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct ClaimsExport {
+    pub version: u8,             // Export format version
+    pub total_claims: u64,       // Claims ever submitted, so callers know when to stop paging
+    pub offset: u64,             // Id of the first claim covered by this page
+    pub records: Vec<ClaimRecord>, // Up to CLAIMS_EXPORT_PAGE_SIZE records for ids from `offset`, leaving out archived claims
+}
+
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction: ClaimsInstruction,
+) -> ProgramResult {
+    // Fail early with a clear message when the client supplies too few accounts for the instruction
+    let expected = expected_account_count(&instruction);
+    if accounts.len() < expected {
+        msg!("Instruction {:?} expects at least {} accounts but received {}", instruction, expected, accounts.len());
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let accounts_iter = &mut accounts.iter();
+    let account = next_account_info(accounts_iter)?;
+
+    if account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let mut dao_data = HealthInsuranceDAO::load(account)?;
+
+    // Only read-only instructions run while the DAO is paused
+    if !allowed_while_paused(&instruction) {
+        dao_data.ensure_active()?;
+    }
+
+    match instruction {
+        ClaimsInstruction::SubmitClaim { amount, service_date, external_ref, preauth_id, condition, supersedes, service_type, nonce } => {
+            // Instruction for submitting a new claim, written to its own PDA. The accounts after the system program
+            // are the prior claim when resubmitting, then the standing claim for the same service if one was filed.
+            let member = next_account_info(accounts_iter)?;
+            let provider = next_account_info(accounts_iter)?;
+            let claim_account = next_account_info(accounts_iter)?; // PDA for the new claim, funded by the member
+            let ref_marker = next_account_info(accounts_iter)?; // PDA marking the external reference as used
+            let service_marker = next_account_info(accounts_iter)?; // PDA recording the latest claim for this service
+            let system_program_account = next_account_info(accounts_iter)?;
+            if !member.is_signer {
+                return Err(ProgramError::MissingRequiredSignature); // Only the member can spend their own nonce
+            }
+            check_claimant(&dao_data, member.key, Clock::get()?.unix_timestamp)?;
+            dao_data.consume_nonce(member.key, nonce)?;
+
+            // The provider attests the service occurred by co-signing the submission
+            if dao_data.require_provider_signature && !provider.is_signer {
+                msg!("Provider {} must co-sign the claim", provider.key);
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+
+            // A resubmission must link to the member's own rejected or withdrawn claim, and only once
+            let mut prior = None;
+            if let Some(prior_id) = supersedes {
+                let prior_account = next_account_info(accounts_iter)?;
+                let prior_claim = load_claim(program_id, account.key, prior_account, prior_id)?;
+                if prior_claim.member != *member.key {
+                    msg!("Claim {} belongs to another member and can't be superseded", prior_id);
+                    return Err(ProgramError::InvalidArgument);
+                }
+                if prior_claim.status != ClaimStatus::Rejected && prior_claim.status != ClaimStatus::Withdrawn {
+                    return Err(ProgramError::InvalidArgument); // Only rejected or withdrawn claims can be resubmitted
+                }
+                if prior_claim.resubmitted {
+                    return Err(ProgramError::InvalidArgument); // Prior claim has already been resubmitted
+                }
+                prior = Some((prior_account, prior_claim));
+            }
+
+            // External references map one-to-one onto on-chain claims
+            let (ref_key, ref_bump) = ref_marker_address(program_id, account.key, &external_ref);
+            if *ref_marker.key != ref_key {
+                return Err(ProgramError::InvalidSeeds); // Marker must be this DAO's PDA for the reference
+            }
+            if ref_marker.owner == program_id {
+                msg!("External reference {:?} is already used by another claim", external_ref);
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            // The same service can't be claimed twice while an earlier claim for it is still standing
+            let service = service_key(member.key, provider.key, service_date, amount);
+            let (service_marker_key, service_bump) = service_marker_address(program_id, account.key, &service);
+            if *service_marker.key != service_marker_key {
+                return Err(ProgramError::InvalidSeeds); // Marker must be this DAO's PDA for the service
+            }
+            let service_marked = service_marker.owner == program_id;
+            if service_marked {
+                let standing_id = u64::try_from_slice(&service_marker.data.borrow())?;
+                // An archived claim has been settled, so it no longer stands
+                let standing = load_live_claim(program_id, account.key, next_account_info(accounts_iter)?, standing_id)?;
+                if matches!(standing, Some(ref claim) if is_standing(claim)) {
+                    msg!("Member {} already has a claim for {} lamports from {} on {}", member.key, amount, provider.key, service_date);
+                    return Err(DaoError::DuplicateClaim.into());
+                }
+            }
+
+            let now = Clock::get()?.unix_timestamp;
+            admit_claim(&mut dao_data, &[*member.key], service_date, now)?;
+            let history = dao_data.members.iter().find(|m| m.member_address == *member.key).map(|m| m.claim_history.clone()).unwrap_or_default();
+
+            // High-cost planned procedures must reference a valid pre-authorization
+            let requires_preauth = dao_data.preauth_amount_threshold > 0
+                && amount >= dao_data.preauth_amount_threshold
+                && dao_data.preauth_service_types.contains(&service_type);
+            if requires_preauth {
+                let preauth_index = preauth_id.ok_or(DaoError::PreAuthRequired)?;
+                let preauth = dao_data.preauths.get_mut(preauth_index as usize).ok_or(DaoError::PreAuthInvalid)?;
+                if preauth.member != *member.key
+                    || preauth.provider != *provider.key
+                    || preauth.service_type != service_type
+                    || !preauth.approved
+                    || preauth.used
+                    || now > preauth.expires_at
+                    || amount > preauth.max_amount
+                {
+                    return Err(DaoError::PreAuthInvalid.into()); // Pre-authorization doesn't cover this claim
+                }
+                preauth.used = true;
+            }
+
+            let provider_claims = dao_data.provider_claims.iter().find(|p| p.provider == *provider.key);
+            let anomaly_score = anomaly_score(&history, provider_claims, amount, service_type);
+            // Tiny claims from members with a clean history cost more to verify than they're worth
+            let micro_claim = amount < dao_data.micro_claim_threshold
+                && in_good_standing(&history, dao_data.micro_claim_min_paid_claims);
+            let mut claim = Claim {
+                claim_id: assign_claim_id(&mut dao_data.claims_count)?,
+                member: *member.key,
+                amount,
+                service_date,
+                service_type,
+                provider: *provider.key,
+                status: ClaimStatus::Pending,
+                verifiers: Vec::new(),
+                preauth_id: if requires_preauth { preauth_id } else { None },
+                assigned_verifiers: Vec::new(),
+                condition,
+                condition_met: false,
+                external_ref,
+                submitted_at: now,
+                provider_attested: provider.is_signer,
+                supersedes,
+                holdback: 0,
+                holdback_release_at: 0,
+                anomaly_score,
+                rejections: Vec::new(),
+                ack_requested_at: 0,
+                acknowledged: false,
+                shares: Vec::new(),
+                zkp_proof: Vec::new(),
+                rejection_reason: None,
+                appealed: false,
+                paid_amount: 0,
+                resubmitted: false,
+            };
+            if micro_claim {
+                claim.status = ClaimStatus::Verified;
+                msg!("Micro-claim {} auto-approved below the {} lamport threshold", claim.claim_id, dao_data.micro_claim_threshold);
+            }
+
+            create_claim(program_id, account.key, member, claim_account, system_program_account, &claim)?;
+            let claim_id_bytes = claim.claim_id.to_le_bytes();
+            create_pda(program_id, member, ref_marker, system_program_account, CLAIM_MARKER_SPACE, &[CLAIM_REF_SEED, account.key.as_ref(), &external_ref, &[ref_bump]])?;
+            ref_marker.data.borrow_mut().copy_from_slice(&claim_id_bytes);
+            if !service_marked {
+                create_pda(program_id, member, service_marker, system_program_account, CLAIM_MARKER_SPACE, &[CLAIM_SERVICE_SEED, account.key.as_ref(), &service, &[service_bump]])?;
+            }
+            service_marker.data.borrow_mut().copy_from_slice(&claim_id_bytes);
+            if let Some((prior_account, mut prior_claim)) = prior {
+                prior_claim.resubmitted = true;
+                save_claim(prior_account, &prior_claim)?;
+            }
+
+            track_claim(&mut dao_data, &claim);
+            msg!("Claim {} submitted for {} lamports (external ref {:?}, anomaly score {})", claim.claim_id, amount, external_ref, anomaly_score);
+
+            // Contingent claims still wait for their condition. An auto-paid claim goes through the regular payout, so
+            // the submission accounts are followed by the treasury payout accounts.
+            if micro_claim && dao_data.micro_claim_auto_pay && claim.condition.is_none() {
+                finance::disburse_claim(program_id, account, &mut dao_data, &mut claim, None, accounts_iter)?;
+                save_claim(claim_account, &claim)?;
+            }
+        }
+        ClaimsInstruction::VerifyClaim { claim_id, approve } => {
+            // Instruction for verifying a claim - Verifiers only
+            let verifier = next_account_info(accounts_iter)?;
+            let claim_account = next_account_info(accounts_iter)?;
+            if !verifier.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            require_role(&dao_data, verifier.key, Role::Verifier)?;
+
+            let mut claim = load_claim(program_id, account.key, claim_account, claim_id)?;
+            review_claim(&mut dao_data, &mut claim, verifier.key, approve, Clock::get()?.unix_timestamp)?;
+            save_claim(claim_account, &claim)?;
+        }
+        ClaimsInstruction::BatchVerify { claim_ids } => {
+            // Instruction for approving several claims at once - Verifiers only. The verifier is followed by one claim
+            // account per listed id, in the same order.
+            let verifier = next_account_info(accounts_iter)?;
+            if !verifier.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            require_role(&dao_data, verifier.key, Role::Verifier)?;
+            if claim_ids.len() > MAX_BATCH_VERIFY {
+                msg!("Batch of {} claims exceeds the limit of {}", claim_ids.len(), MAX_BATCH_VERIFY);
+                return Err(DaoError::BatchTooLarge.into());
+            }
+
+            // Claims this verifier can't approve are skipped rather than failing the whole batch
+            let now = Clock::get()?.unix_timestamp;
+            let mut advanced = 0;
+            for &claim_id in &claim_ids {
+                let claim_account = next_account_info(accounts_iter)?;
+                let mut claim = match load_live_claim(program_id, account.key, claim_account, claim_id)? {
+                    Some(claim) => claim,
+                    None => {
+                        msg!("Skipped claim {}: no such claim", claim_id);
+                        continue;
+                    }
+                };
+                match review_claim(&mut dao_data, &mut claim, verifier.key, true, now) {
+                    Ok(()) => {
+                        save_claim(claim_account, &claim)?;
+                        advanced += 1;
+                    }
+                    Err(error) => msg!("Skipped claim {}: {:?}", claim_id, error),
+                }
+            }
+            msg!("Batch verification by {} advanced {} of {} claims", verifier.key, advanced, claim_ids.len());
+        }
+        ClaimsInstruction::SetAffiliations { affiliations } => {
+            // Instruction for recording a member's affiliations - Admin only
+            let admin = next_account_info(accounts_iter)?;
+            let member = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+
+            if let Some(record) = dao_data.members.iter_mut().find(|m| m.member_address == *member.key) {
+                msg!("Recorded {} affiliations for member {}", affiliations.len(), member.key);
+                record.affiliations = affiliations;
+            } else {
+                return Err(DaoError::NotAMember.into());
+            }
+        }
+        ClaimsInstruction::RequestPreAuth { max_amount, service_type } => {
+            // Instruction for requesting pre-authorization of a planned procedure
+            let member = next_account_info(accounts_iter)?;
+            let provider = next_account_info(accounts_iter)?;
+            if !member.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+
+            dao_data.preauths.push(PreAuth {
+                preauth_id: dao_data.preauths.len() as u64,
+                member: *member.key,
+                provider: *provider.key,
+                service_type,
+                max_amount,
+                approved: false, // Requests start unapproved
+                expires_at: 0,
+                used: false,
+            });
+            msg!("Pre-authorization {} requested for {} lamports", dao_data.preauths.len() - 1, max_amount);
+        }
+        ClaimsInstruction::ApprovePreAuth { preauth_index, valid_for_secs } => {
+            // Instruction for approving a pre-authorization request - Admin only
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+            if valid_for_secs <= 0 {
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            if let Some(preauth) = dao_data.preauths.get_mut(preauth_index as usize) {
+                preauth.approved = true;
+                preauth.expires_at = Clock::get()?.unix_timestamp.checked_add(valid_for_secs).ok_or(ProgramError::ArithmeticOverflow)?;
+                msg!("Pre-authorization {} approved until {}", preauth.preauth_id, preauth.expires_at);
+            } else {
+                return Err(ProgramError::InvalidAccountData); // Pre-authorization not found
+            }
+        }
+        ClaimsInstruction::ConfigurePreAuth { amount_threshold, service_types } => {
+            // Instruction for configuring which claims require pre-authorization - Admin only
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+
+            dao_data.preauth_amount_threshold = amount_threshold;
+            dao_data.preauth_service_types = service_types;
+            msg!("Pre-authorization required for {} service types at or above {} lamports", dao_data.preauth_service_types.len(), amount_threshold);
+        }
+        ClaimsInstruction::SetTreasury => {
+            // Instruction for setting the treasury account - Admin only
+            let admin = next_account_info(accounts_iter)?;
+            let treasury = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+
+            // Validate the new treasury against the same rules the transfer paths enforce
+            let treasury_kind = finance::classify_treasury(account, treasury, treasury.key, program_id)?;
+            dao_data.treasury_account = *treasury.key;
+            msg!("Treasury set to {} ({:?})", treasury.key, treasury_kind);
+        }
+        ClaimsInstruction::DemoteInactiveVerifiers => {
+            // Maintenance instruction demoting verifiers who have been idle past the inactivity threshold
+            if dao_data.verifier_inactivity_secs <= 0 {
+                return Err(ProgramError::InvalidAccountData); // Auto-downgrade is not configured
+            }
+            let cutoff = Clock::get()?.unix_timestamp.saturating_sub(dao_data.verifier_inactivity_secs);
+
+            let mut demoted = 0;
+            for record in dao_data.members.iter_mut().filter(|m| m.role == Role::Verifier) {
+                // Verifiers who never verified are measured from when they joined
+                if record.last_verification_at.max(record.joined_timestamp) < cutoff {
+                    // Logging each demotion is costly on large member lists, so only the total is reported
+                    record.role = Role::Member;
+                    demoted += 1;
+                }
+            }
+            msg!("{} idle verifiers demoted", demoted);
+        }
+        ClaimsInstruction::SetVerifierInactivity { inactivity_secs } => {
+            // Instruction for configuring the verifier inactivity threshold - Admin only
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+            if inactivity_secs < 0 {
+                return Err(ProgramError::InvalidArgument);
+            }
+            dao_data.verifier_inactivity_secs = inactivity_secs;
+            msg!("Verifier inactivity threshold set to {} seconds", inactivity_secs);
+        }
+        ClaimsInstruction::SetMaxVerifiers { max_verifiers } => {
+            // Instruction for configuring the maximum verifiers per claim - Admin only
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+            if (max_verifiers as usize) < base_verification_threshold(dao_data.verification_threshold) {
+                return Err(ProgramError::InvalidArgument); // Must leave room for the verifications a claim needs
+            }
+            if dao_data.category_verification_thresholds.iter().any(|(_, threshold)| *threshold > max_verifiers) {
+                return Err(ProgramError::InvalidArgument); // Every category must still be able to reach its threshold
+            }
+            dao_data.max_verifiers_per_claim = max_verifiers;
+            msg!("Maximum verifiers per claim set to {}", max_verifiers);
+        }
+        ClaimsInstruction::AppealClaim { claim_id } => {
+            // Instruction for appealing a rejected claim - Opens a dispute over the rejection and, if the appeal is
+            // upheld, routes the re-review to verifiers who didn't see it the first time
+            let member = next_account_info(accounts_iter)?;
+            let claim_account = next_account_info(accounts_iter)?;
+            if !member.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if dispute::open_dispute_limit_reached(&dao_data.disputes, dao_data.max_open_disputes) {
+                msg!("The DAO already has the maximum of {} open disputes", dao_data.max_open_disputes);
+                return Err(DaoError::OpenDisputeLimitReached.into());
+            }
+
+            let mut claim = load_claim(program_id, account.key, claim_account, claim_id)?;
+            if claim.member != *member.key {
+                return Err(DaoError::Unauthorized.into()); // Only the claimant can appeal
+            }
+            if claim.appealed {
+                return Err(DaoError::ClaimAlreadyAppealed.into());
+            }
+            if claim.status != ClaimStatus::Rejected {
+                return Err(ProgramError::InvalidAccountData); // Only rejected claims can be appealed
+            }
+
+            let provider_affiliations = dao_data.members.iter()
+                .find(|m| m.member_address == claim.provider)
+                .map(|m| m.affiliations.as_slice())
+                .unwrap_or(&[]);
+            let mut fresh_verifiers = appeal_verifiers(
+                &dao_data.members,
+                &claim,
+                provider_affiliations,
+                &dao_data.verifier_pairings,
+                dao_data.max_verifier_provider_pairings,
+            );
+            if fresh_verifiers.len() < base_verification_threshold(dao_data.verification_threshold) {
+                msg!("Only {} fresh verifiers available for appeal of claim {}", fresh_verifiers.len(), claim.claim_id);
+                return Err(ProgramError::InvalidArgument); // Not enough independent reviewers for the appeal
+            }
+            // The panel never needs more members than may verify the claim, so large verifier pools don't bloat it
+            let max_verifiers = match dao_data.max_verifiers_per_claim {
+                0 => DEFAULT_MAX_VERIFIERS_PER_CLAIM,
+                max => max,
+            };
+            fresh_verifiers.truncate(max_verifiers as usize);
+
+            untrack_claim(&mut dao_data, &claim);
+            claim.assigned_verifiers = fresh_verifiers;
+            claim.verifiers.clear();
+            claim.rejections.clear();
+            claim.status = ClaimStatus::Appealed;
+            claim.appealed = true;
+            track_claim(&mut dao_data, &claim);
+            save_claim(claim_account, &claim)?;
+            msg!("Claim {} appealed with {} fresh verifiers assigned", claim_id, claim.assigned_verifiers.len());
+
+            // The DAO itself answers the appeal; members vote on whether the rejection stands
+            let description = format!("Appeal of rejected claim {}", claim_id);
+            let dispute_id = dispute::file_dispute(&mut dao_data, *member.key, *account.key, Some(claim_id), description, 0)?;
+            msg!("Appeal of claim {} opened as dispute {}", claim_id, dispute_id);
+        }
+        ClaimsInstruction::ConfigurePeriodCap { period_claim_cap, rollover_enabled } => {
+            // Instruction for configuring the per-period claim cap - Admin only
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+            dao_data.period_claim_cap = period_claim_cap;
+            dao_data.period_rollover_enabled = rollover_enabled;
+            msg!("Period claim cap set to {} (rollover: {})", period_claim_cap, rollover_enabled);
+        }
+        ClaimsInstruction::RolloverPeriod => {
+            // Instruction for rolling over to a new claim period - Admin only
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+
+            // Unused slots from the base cap carry over when enabled; carryover never stacks beyond one period
+            dao_data.period_carryover = if dao_data.period_rollover_enabled {
+                dao_data.period_claim_cap.saturating_sub(dao_data.period_claims_used)
+            } else {
+                0
+            };
+            dao_data.period_claims_used = 0;
+            msg!("Claim period rolled over with {} carried-over slots", dao_data.period_carryover);
+        }
+        ClaimsInstruction::ExportClaims { offset } => {
+            // Read instruction exporting a page of claim records for auditors via return data. The DAO account is
+            // followed by the claim accounts for consecutive ids starting at `offset`; archived claims are left out.
+            let mut claims = Vec::new();
+            for (claim_id, claim_account) in (offset..).zip(accounts_iter.take(CLAIMS_EXPORT_PAGE_SIZE)) {
+                if let Some(claim) = load_live_claim(program_id, account.key, claim_account, claim_id)? {
+                    claims.push(claim);
+                }
+            }
+            let page = export_claims_page(&claims, dao_data.claims_count, offset);
+            set_return_data(&page.try_to_vec()?);
+            msg!("Exported {} claims starting at {}", page.records.len(), offset);
+        }
+        ClaimsInstruction::SetConditionOracle => {
+            // Instruction for setting the condition oracle - Admin only
+            let admin = next_account_info(accounts_iter)?;
+            let oracle = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+            dao_data.condition_oracle = *oracle.key;
+            msg!("Condition oracle set to {}", oracle.key);
+        }
+        ClaimsInstruction::FulfillCondition { claim_id, attestation } => {
+            // Instruction for fulfilling a claim condition using the condition oracle's attestation
+            let oracle = next_account_info(accounts_iter)?;
+            let claim_account = next_account_info(accounts_iter)?;
+            if !oracle.is_signer || *oracle.key != dao_data.condition_oracle {
+                return Err(ProgramError::MissingRequiredSignature); // Only the configured oracle can attest
+            }
+
+            let mut claim = load_claim(program_id, account.key, claim_account, claim_id)?;
+            match claim.condition {
+                Some(commitment) if hash(&attestation).to_bytes() == commitment => {
+                    claim.condition_met = true;
+                    save_claim(claim_account, &claim)?;
+                    msg!("Condition fulfilled for claim {}", claim.claim_id);
+                }
+                Some(_) => return Err(ProgramError::InvalidArgument), // Attestation doesn't match the commitment
+                None => return Err(ProgramError::InvalidAccountData), // Claim has no condition to fulfill
+            }
+        }
+        ClaimsInstruction::DeclareEmergency { threshold, window_start, expires_at } => {
+            // Instruction for declaring an emergency with a reduced verification threshold - Admin only
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+
+            // The emergency threshold must actually lower the bar, and the window must still be open
+            if threshold == 0 || threshold as usize >= base_verification_threshold(dao_data.verification_threshold) {
+                return Err(ProgramError::InvalidArgument);
+            }
+            if window_start > expires_at || expires_at <= Clock::get()?.unix_timestamp {
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            dao_data.emergency = Some(Emergency {
+                verification_threshold: threshold,
+                window_start,
+                expires_at,
+            });
+            msg!("Emergency declared: {} verifications for services between {} and {}", threshold, window_start, expires_at);
+        }
+        ClaimsInstruction::DepositStake { amount } => {
+            // Instruction for depositing verifier stake into the DAO account
+            let verifier = next_account_info(accounts_iter)?;
+            let system_program_account = next_account_info(accounts_iter)?;
+            if !verifier.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if *system_program_account.key != system_program::id() {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+
+            let record = dao_data.members.iter_mut().find(|m| m.member_address == *verifier.key).ok_or(DaoError::NotAMember)?;
+            invoke(
+                &system_instruction::transfer(verifier.key, account.key, amount),
+                &[verifier.clone(), account.clone(), system_program_account.clone()],
+            )?;
+            record.stake = record.stake.checked_add(amount).ok_or(ProgramError::ArithmeticOverflow)?;
+            msg!("Verifier {} staked {} lamports (total {})", verifier.key, amount, record.stake);
+        }
+        ClaimsInstruction::ConfirmFraud { claim_id } => {
+            // Instruction for confirming a claim as fraudulent - Admin only. Rejects the claim and slashes its verifiers
+            let admin = next_account_info(accounts_iter)?;
+            let claim_account = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+
+            let mut claim = load_claim(program_id, account.key, claim_account, claim_id)?;
+            if claim.status == ClaimStatus::Paid || claim.status == ClaimStatus::Rejected || claim.status == ClaimStatus::Withdrawn {
+                return Err(ProgramError::InvalidAccountData); // Only open claims can be confirmed fraudulent
+            }
+            untrack_claim(&mut dao_data, &claim);
+            claim.status = ClaimStatus::Rejected;
+            track_claim(&mut dao_data, &claim);
+            save_claim(claim_account, &claim)?;
+
+            // Every verifier who approved the claim loses up to the required stake
+            for verifier_key in claim.verifiers.iter() {
+                if let Some(record) = dao_data.members.iter_mut().find(|m| m.member_address == *verifier_key) {
+                    let slashed = record.stake.min(dao_data.verifier_stake_requirement);
+                    record.stake -= slashed;
+                    dao_data.slashed_stakes = dao_data.slashed_stakes.checked_add(slashed).ok_or(ProgramError::ArithmeticOverflow)?;
+                    msg!("Slashed {} lamports from verifier {}", slashed, verifier_key);
+                }
+            }
+            msg!("Claim {} confirmed fraudulent", claim.claim_id);
+
+            // Escalate against the claimant: a warning, then suspension, then removal as strikes accumulate
+            let claimant = claim.member;
+            if let Some(index) = dao_data.members.iter().position(|m| m.member_address == claimant) {
+                let record = &mut dao_data.members[index];
+                record.fraud_strikes = record.fraud_strikes.saturating_add(1);
+                match fraud_penalty(record.fraud_strikes, dao_data.fraud_suspension_strikes, dao_data.fraud_removal_strikes) {
+                    FraudPenalty::Warning => msg!("Member {} warned after {} fraud strikes", claimant, record.fraud_strikes),
+                    FraudPenalty::Suspension => {
+                        record.suspended = true;
+                        msg!("Member {} suspended after {} fraud strikes", claimant, record.fraud_strikes);
+                    }
+                    FraudPenalty::Removal => {
+                        // Any stake the member still holds is forfeited along with their membership
+                        let removed = dao_data.members.remove(index);
+                        dao_data.slashed_stakes = dao_data.slashed_stakes.checked_add(removed.stake).ok_or(ProgramError::ArithmeticOverflow)?;
+                        msg!("Member {} removed after {} fraud strikes", claimant, removed.fraud_strikes);
+                    }
+                }
+            }
+        }
+        ClaimsInstruction::SetStakeRequirement { requirement } => {
+            // Instruction for setting the verifier stake requirement - Admin only
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+            dao_data.verifier_stake_requirement = requirement;
+            msg!("Verifier stake requirement set to {} lamports", requirement);
+        }
+        ClaimsInstruction::SetCoolingWindow { cooling_seconds } => {
+            // Instruction for setting the verification cooling window - Admin only
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+            if cooling_seconds < 0 {
+                return Err(ProgramError::InvalidArgument);
+            }
+            dao_data.verify_cooling_seconds = cooling_seconds;
+            msg!("Verification cooling window set to {} seconds", cooling_seconds);
+        }
+        ClaimsInstruction::SetProviderSignatureRequired { required } => {
+            // Instruction for requiring provider co-signatures on claim submissions - Admin only
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+            dao_data.require_provider_signature = required;
+            msg!("Provider signature requirement set to {}", dao_data.require_provider_signature);
+        }
+        ClaimsInstruction::WithdrawClaim { claim_id } => {
+            // Instruction for withdrawing a pending claim - Claimant only
+            let member = next_account_info(accounts_iter)?;
+            let claim_account = next_account_info(accounts_iter)?;
+            if !member.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+
+            let mut claim = load_claim(program_id, account.key, claim_account, claim_id)?;
+            if claim.member != *member.key {
+                return Err(DaoError::Unauthorized.into()); // Only the claimant can withdraw the claim
+            }
+            match claim.status {
+                ClaimStatus::Pending => {}
+                ClaimStatus::Paid | ClaimStatus::PartiallyPaid => return Err(DaoError::ClaimAlreadyPaid.into()),
+                ClaimStatus::Verified | ClaimStatus::PayoutPendingAck => return Err(DaoError::ClaimAlreadyVerified.into()),
+                _ => return Err(ProgramError::InvalidAccountData), // Only pending claims can be withdrawn
+            }
+            untrack_claim(&mut dao_data, &claim);
+            claim.status = ClaimStatus::Withdrawn;
+            track_claim(&mut dao_data, &claim);
+            save_claim(claim_account, &claim)?;
+            msg!("Claim {} withdrawn by {}", claim.claim_id, member.key);
+        }
+        ClaimsInstruction::SetCategoryThresholds { thresholds } => {
+            // Instruction for configuring per-category verification thresholds - Admin only
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+
+            let max_verifiers = match dao_data.max_verifiers_per_claim {
+                0 => DEFAULT_MAX_VERIFIERS_PER_CLAIM,
+                max => max,
+            };
+            if thresholds.iter().any(|(_, threshold)| *threshold == 0 || *threshold > max_verifiers) {
+                return Err(ProgramError::InvalidArgument); // Each threshold must be reachable within the verifier cap
+            }
+            dao_data.category_verification_thresholds = thresholds;
+            msg!("Verification thresholds configured for {} service types", dao_data.category_verification_thresholds.len());
+        }
+        ClaimsInstruction::SetAnnualClaimCap { cap } => {
+            // Instruction for setting the annual per-member claim count cap - Admin only
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+            dao_data.max_claims_per_member_per_year = cap;
+            msg!("Annual claim cap set to {} claims per member", cap);
+        }
+        ClaimsInstruction::SetPairingLimit { limit } => {
+            // Instruction for setting the verifier-provider pairing limit - Admin only
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+            dao_data.max_verifier_provider_pairings = limit;
+            msg!("Verifier-provider pairing limit set to {}", limit);
+        }
+        ClaimsInstruction::ReleaseHoldback { claim_id } => {
+            // Instruction for releasing a payout holdback - Provider confirmation, or anyone once the timeout passes
+            let provider = next_account_info(accounts_iter)?;
+            let claim_account = next_account_info(accounts_iter)?;
+
+            let mut claim = load_claim(program_id, account.key, claim_account, claim_id)?;
+            if !matches!(claim.status, ClaimStatus::Paid | ClaimStatus::PartiallyPaid) || claim.holdback == 0 {
+                return Err(ProgramError::InvalidAccountData); // Nothing is being held back on this claim
+            }
+
+            let confirmed = provider.is_signer && *provider.key == claim.provider;
+            if !confirmed && Clock::get()?.unix_timestamp < claim.holdback_release_at {
+                msg!("Holdback on claim {} awaits provider confirmation until {}", claim.claim_id, claim.holdback_release_at);
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+
+            // The holdback left the treasury balance when the claim was paid, so releasing it only moves the funds
+            let holdback = claim.holdback;
+            finance::pay_from_treasury(program_id, account, &dao_data, &claim.member, holdback, accounts_iter)?;
+            claim.holdback = 0;
+            save_claim(claim_account, &claim)?;
+            msg!("Released {} lamports of held-back payout to {} for claim {}", holdback, claim.member, claim_id);
+        }
+        ClaimsInstruction::ConfigureHoldback { holdback_bps, timeout_secs } => {
+            // Instruction for configuring the payout holdback - Admin only
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+            if holdback_bps > 10_000 || timeout_secs < 0 {
+                return Err(ProgramError::InvalidArgument);
+            }
+            dao_data.holdback_bps = holdback_bps;
+            dao_data.holdback_timeout_secs = timeout_secs;
+            msg!("Payout holdback set to {} bps, released after {} seconds", holdback_bps, timeout_secs);
+        }
+        ClaimsInstruction::ArchiveClaims => {
+            // Instruction for archiving paid and rejected claims past the retention period. The DAO account is followed
+            // by the claim accounts to consider; each archivable one is closed, returning its rent to the DAO account.
+            if dao_data.claim_retention_seconds <= 0 {
+                return Err(ProgramError::InvalidAccountData); // Archival hasn't been configured
+            }
+            let cutoff = Clock::get()?.unix_timestamp.saturating_sub(dao_data.claim_retention_seconds);
+
+            let mut archived = Vec::new();
+            for claim_account in accounts_iter {
+                let claim = load_listed_claim(program_id, account.key, claim_account)?;
+                if is_archivable(&claim, cutoff) {
+                    // The member's and provider's histories still count the claim; only its open exposure goes
+                    release_claim_exposure(&mut dao_data, &claim);
+                    close_claim_account(account, claim_account)?;
+                    archived.push(claim);
+                }
+            }
+            if archived.is_empty() {
+                msg!("No claims old enough to archive");
+            } else {
+                let leaves = archived.iter().map(|c| Ok(hash(&c.try_to_vec()?).to_bytes())).collect::<Result<Vec<_>, ProgramError>>()?;
+                dao_data.archived_claims_root = hashv(&[&dao_data.archived_claims_root, &merkle_root(&leaves)]).to_bytes();
+                dao_data.archived_claim_count = dao_data.archived_claim_count.checked_add(archived.len() as u64).ok_or(ProgramError::ArithmeticOverflow)?;
+                msg!("Archived {} claims; archive root is now {:?}", archived.len(), dao_data.archived_claims_root);
+            }
+        }
+        ClaimsInstruction::SetClaimRetention { retention_seconds } => {
+            // Instruction for setting the claim retention period - Admin only
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+            if retention_seconds < 0 {
+                return Err(ProgramError::InvalidArgument);
+            }
+            dao_data.claim_retention_seconds = retention_seconds;
+            msg!("Claim retention set to {} seconds", retention_seconds);
+        }
+        ClaimsInstruction::SetMinReviewers { min_reviewed } => {
+            // Instruction for setting the minimum number of verifiers who must review a claim - Admin only
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+            let max_verifiers = match dao_data.max_verifiers_per_claim {
+                0 => DEFAULT_MAX_VERIFIERS_PER_CLAIM,
+                max => max,
+            };
+            if min_reviewed > max_verifiers {
+                return Err(ProgramError::InvalidArgument); // The review count must be reachable within the verifier cap
+            }
+            dao_data.min_verifiers_reviewed = min_reviewed;
+            msg!("Claims now need {} distinct reviews before payout", min_reviewed);
+        }
+        ClaimsInstruction::ConfigureMicroClaims { threshold, auto_pay, min_paid_claims } => {
+            // Instruction for configuring micro-claim auto-approval - Admin only
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+            dao_data.micro_claim_threshold = threshold;
+            dao_data.micro_claim_auto_pay = auto_pay;
+            dao_data.micro_claim_min_paid_claims = min_paid_claims;
+            msg!("Micro-claims below {} lamports auto-approved (auto-pay {}) for members with {} paid claims", threshold, auto_pay, min_paid_claims);
+        }
+        ClaimsInstruction::SetFraudThresholds { suspension_strikes, removal_strikes } => {
+            // Instruction for configuring fraud strike thresholds - Admin only
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+            if suspension_strikes > 0 && removal_strikes > 0 && suspension_strikes >= removal_strikes {
+                return Err(ProgramError::InvalidArgument); // Suspension must come before removal
+            }
+            dao_data.fraud_suspension_strikes = suspension_strikes;
+            dao_data.fraud_removal_strikes = removal_strikes;
+            msg!("Members suspended at {} and removed at {} fraud strikes", suspension_strikes, removal_strikes);
+        }
+        ClaimsInstruction::AcknowledgePayout { claim_id } => {
+            // Instruction for acknowledging a pending high-value payout - Claimant only
+            let member = next_account_info(accounts_iter)?;
+            let claim_account = next_account_info(accounts_iter)?;
+            if !member.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+
+            let mut claim = load_claim(program_id, account.key, claim_account, claim_id)?;
+            if claim.member != *member.key {
+                return Err(DaoError::Unauthorized.into()); // Only the claimant can acknowledge the payout
+            }
+            if claim.status != ClaimStatus::PayoutPendingAck {
+                return Err(ProgramError::InvalidAccountData); // No acknowledgment is pending
+            }
+            let deadline = claim.ack_requested_at.saturating_add(dao_data.ack_timeout_secs);
+            if Clock::get()?.unix_timestamp > deadline {
+                msg!("Acknowledgment window for claim {} closed at {}", claim.claim_id, deadline);
+                return Err(ProgramError::InvalidArgument);
+            }
+            untrack_claim(&mut dao_data, &claim);
+            claim.acknowledged = true;
+            claim.status = ClaimStatus::Verified;
+            track_claim(&mut dao_data, &claim);
+            save_claim(claim_account, &claim)?;
+            msg!("Payout for claim {} acknowledged by {}", claim.claim_id, member.key);
+        }
+        ClaimsInstruction::ConfigureAcknowledgment { threshold, timeout_secs } => {
+            // Instruction for configuring payout acknowledgments - Admin only
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+            if threshold > 0 && timeout_secs <= 0 {
+                return Err(ProgramError::InvalidArgument); // Members need a window to acknowledge in
+            }
+            dao_data.ack_required_above = threshold;
+            dao_data.ack_timeout_secs = timeout_secs;
+            msg!("Payouts of {} lamports or more need acknowledgment within {} seconds", threshold, timeout_secs);
+        }
+        ClaimsInstruction::RejectClaim { claim_id, reason } => {
+            // Instruction for rejecting a pending claim - Verifiers only
+            let verifier = next_account_info(accounts_iter)?;
+            let claim_account = next_account_info(accounts_iter)?;
+            if !verifier.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            require_role(&dao_data, verifier.key, Role::Verifier)?;
+
+            let mut claim = load_claim(program_id, account.key, claim_account, claim_id)?;
+            match claim.status {
+                ClaimStatus::Pending => {}
+                ClaimStatus::Paid | ClaimStatus::PartiallyPaid => return Err(DaoError::ClaimAlreadyPaid.into()),
+                ClaimStatus::Verified | ClaimStatus::PayoutPendingAck => return Err(DaoError::ClaimAlreadyVerified.into()),
+                _ => return Err(ProgramError::InvalidAccountData), // Claim is already closed
+            }
+            msg!("Claim {} rejected by {}: {}", claim.claim_id, verifier.key, reason.as_deref().unwrap_or("no reason given"));
+            untrack_claim(&mut dao_data, &claim);
+            claim.status = ClaimStatus::Rejected;
+            claim.rejection_reason = reason;
+            track_claim(&mut dao_data, &claim);
+            save_claim(claim_account, &claim)?;
+        }
+        ClaimsInstruction::SetVerificationThreshold { threshold } => {
+            // Instruction for setting the number of verifications a claim needs - Admin only
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+
+            // The threshold must be reachable by the registered verifiers within the per-claim verifier cap
+            let registered_verifiers = dao_data.members.iter().filter(|m| m.role == Role::Verifier).count();
+            let max_verifiers = match dao_data.max_verifiers_per_claim {
+                0 => DEFAULT_MAX_VERIFIERS_PER_CLAIM,
+                max => max,
+            };
+            if threshold == 0 || threshold as usize > registered_verifiers || threshold > max_verifiers {
+                msg!("Threshold {} is unreachable with {} registered verifiers", threshold, registered_verifiers);
+                return Err(ProgramError::InvalidArgument);
+            }
+            dao_data.verification_threshold = threshold;
+            msg!("Claims now need {} verifications", threshold);
+        }
+        ClaimsInstruction::SetClaimWindow { claim_window_secs } => {
+            // Instruction for setting how long after a service claims may be submitted - Admin only
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+            if claim_window_secs < 0 {
+                return Err(ProgramError::InvalidArgument);
+            }
+            dao_data.claim_window_secs = claim_window_secs;
+            msg!("Claim window set to {} seconds", claim_window_secs);
+        }
+        ClaimsInstruction::ExpireClaims => {
+            // Instruction for rejecting pending claims that have outlived the pending TTL - Anyone may call. The DAO
+            // account is followed by the claim accounts to consider, of which the first EXPIRY_BATCH_SIZE are read.
+            if dao_data.pending_ttl_secs <= 0 {
+                return Err(ProgramError::InvalidAccountData); // Expiry hasn't been configured
+            }
+            let cutoff = Clock::get()?.unix_timestamp.saturating_sub(dao_data.pending_ttl_secs);
+
+            let mut expired = 0;
+            for claim_account in accounts_iter.take(EXPIRY_BATCH_SIZE) {
+                let mut claim = load_listed_claim(program_id, account.key, claim_account)?;
+                if claim.status != ClaimStatus::Pending || claim.submitted_at >= cutoff {
+                    continue;
+                }
+                untrack_claim(&mut dao_data, &claim);
+                claim.status = ClaimStatus::Rejected;
+                claim.rejection_reason = Some("expired".to_string());
+                track_claim(&mut dao_data, &claim);
+                save_claim(claim_account, &claim)?;
+                expired += 1;
+            }
+            msg!("Expired {} stale pending claims", expired);
+        }
+        ClaimsInstruction::SetPendingTtl { ttl_secs } => {
+            // Instruction for setting how long a claim may stay pending before it can be expired - Admin only
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+            if ttl_secs < 0 {
+                return Err(ProgramError::InvalidArgument);
+            }
+            dao_data.pending_ttl_secs = ttl_secs;
+            msg!("Pending claims expire after {} seconds", ttl_secs);
+        }
+    }
+
+    dao_data.save(account)
+}
+
+// Fails unless `member` may file a claim at `now`: a member who isn't suspended, whose coverage hasn't lapsed, and who
+// isn't a high-risk member while the treasury is in emergency mode
+pub(crate) fn check_claimant(dao_data: &HealthInsuranceDAO, member: &Pubkey, now: i64) -> ProgramResult {
+    require_role(dao_data, member, Role::Member)?;
+    let record = dao_data.members.iter().find(|m| m.member_address == *member).ok_or(DaoError::Unauthorized)?;
+
+    // Members suspended for repeated fraud can't file new claims
+    if record.suspended {
+        msg!("Member {} is suspended and cannot submit claims", member);
+        return Err(DaoError::MemberSuspended.into());
+    }
+
+    // Members behind on their premiums can't file new claims until they pay
+    if !dao_data.coverage_active(record, now) {
+        msg!("Coverage for member {} lapsed after their premium paid at {}", member, record.last_premium_ts);
+        return Err(DaoError::CoverageLapsed.into());
+    }
+
+    // An insolvent treasury stops taking on new high-risk exposure until the emergency is cleared
+    if dao_data.emergency_mode && dao_data.risk_profiles.iter().any(|rp| rp.member == *member && rp.risk_score >= EMERGENCY_HIGH_RISK_SCORE) {
+        msg!("Treasury is in emergency mode; claims from high-risk member {} are paused", member);
+        return Err(DaoError::EmergencyMode.into());
+    }
+    Ok(())
+}
+
+// Limits on new claims that hold whoever submits them. The service has to fall within the claim window, each member
+// drawing on the claim has to be under the annual claim cap, and the period cap has to have room, which the claim
+// then takes up.
+pub(crate) fn admit_claim(dao_data: &mut HealthInsuranceDAO, participants: &[Pubkey], service_date: i64, now: i64) -> ProgramResult {
+    // Bound the number of claims accepted per period
+    let period_limit = dao_data.period_claim_cap.saturating_add(dao_data.period_carryover);
+    if dao_data.period_claim_cap > 0 && dao_data.period_claims_used >= period_limit {
+        msg!("Period claim cap of {} reached", period_limit);
+        return Err(DaoError::PeriodCapReached.into());
+    }
+
+    // Services can't be claimed before they happen, or once the claim window after them has closed
+    let window_closed = dao_data.claim_window_secs > 0 && now.saturating_sub(service_date) > dao_data.claim_window_secs;
+    if service_date > now || window_closed {
+        msg!("Service date {} is outside the claim window of {} seconds before {}", service_date, dao_data.claim_window_secs, now);
+        return Err(DaoError::ClaimWindowExpired.into());
+    }
+
+    // Some plans cap how many claims a member can make per year, regardless of amount
+    if dao_data.max_claims_per_member_per_year > 0 {
+        for participant in participants {
+            let history = dao_data.members.iter().find(|m| m.member_address == *participant).map(|m| &m.claim_history);
+            if history.map_or(0, |history| claims_in_year(history, now)) >= dao_data.max_claims_per_member_per_year {
+                msg!("Member {} has reached the annual cap of {} claims", participant, dao_data.max_claims_per_member_per_year);
+                return Err(DaoError::AnnualClaimCapReached.into());
+            }
+        }
+    }
+
+    if dao_data.period_claim_cap > 0 {
+        dao_data.period_claims_used += 1;
+    }
+    // Submissions older than the rolling year no longer count toward the annual cap
+    let window_start = now.saturating_sub(CLAIM_YEAR_SECONDS);
+    for record in dao_data.members.iter_mut().filter(|m| participants.contains(&m.member_address)) {
+        record.claim_history.recent_submissions.retain(|t| *t > window_start);
+    }
+    Ok(())
+}
+
+// Minimum number of accounts each instruction needs, including the DAO account
+fn expected_account_count(instruction: &ClaimsInstruction) -> usize {
+    match instruction {
+        ClaimsInstruction::SubmitClaim { .. } => 7, // DAO, member, provider, claim account, external-ref marker, service marker, system program
+        ClaimsInstruction::VerifyClaim { .. } => 3, // DAO, verifier, claim account
+        ClaimsInstruction::SetAffiliations { .. } => 3, // DAO, admin, member
+        ClaimsInstruction::RequestPreAuth { .. } => 3, // DAO, member, provider
+        ClaimsInstruction::ApprovePreAuth { .. } => 2, // DAO, admin
+        ClaimsInstruction::ConfigurePreAuth { .. } => 2, // DAO, admin
+        ClaimsInstruction::SetTreasury => 3, // DAO, admin, treasury
+        ClaimsInstruction::DemoteInactiveVerifiers => 1, // DAO
+        ClaimsInstruction::SetVerifierInactivity { .. } => 2, // DAO, admin
+        ClaimsInstruction::SetMaxVerifiers { .. } => 2, // DAO, admin
+        ClaimsInstruction::AppealClaim { .. } => 3, // DAO, member, claim account
+        ClaimsInstruction::ConfigurePeriodCap { .. } => 2, // DAO, admin
+        ClaimsInstruction::RolloverPeriod => 2, // DAO, admin
+        ClaimsInstruction::ExportClaims { .. } => 1, // DAO, followed by the claim accounts to export
+        ClaimsInstruction::SetConditionOracle => 3, // DAO, admin, oracle
+        ClaimsInstruction::FulfillCondition { .. } => 3, // DAO, oracle, claim account
+        ClaimsInstruction::DeclareEmergency { .. } => 2, // DAO, admin
+        ClaimsInstruction::DepositStake { .. } => 3, // DAO, verifier, system program
+        ClaimsInstruction::ConfirmFraud { .. } => 3, // DAO, admin, claim account
+        ClaimsInstruction::SetStakeRequirement { .. } => 2, // DAO, admin
+        ClaimsInstruction::SetCoolingWindow { .. } => 2, // DAO, admin
+        ClaimsInstruction::SetProviderSignatureRequired { .. } => 2, // DAO, admin
+        ClaimsInstruction::WithdrawClaim { .. } => 3, // DAO, member, claim account
+        ClaimsInstruction::SetCategoryThresholds { .. } => 2, // DAO, admin
+        ClaimsInstruction::SetAnnualClaimCap { .. } => 2, // DAO, admin
+        ClaimsInstruction::SetPairingLimit { .. } => 2, // DAO, admin
+        ClaimsInstruction::ReleaseHoldback { .. } => 6, // DAO, provider, claim account, treasury, claimant, system program (or four token accounts)
+        ClaimsInstruction::ConfigureHoldback { .. } => 2, // DAO, admin
+        ClaimsInstruction::ArchiveClaims => 1, // DAO, followed by the claim accounts to archive
+        ClaimsInstruction::SetClaimRetention { .. } => 2, // DAO, admin
+        ClaimsInstruction::SetMinReviewers { .. } => 2, // DAO, admin
+        ClaimsInstruction::ConfigureMicroClaims { .. } => 2, // DAO, admin
+        ClaimsInstruction::SetFraudThresholds { .. } => 2, // DAO, admin
+        ClaimsInstruction::AcknowledgePayout { .. } => 3, // DAO, member, claim account
+        ClaimsInstruction::ConfigureAcknowledgment { .. } => 2, // DAO, admin
+        ClaimsInstruction::RejectClaim { .. } => 3, // DAO, verifier, claim account
+        ClaimsInstruction::SetVerificationThreshold { .. } => 2, // DAO, admin
+        ClaimsInstruction::SetClaimWindow { .. } => 2, // DAO, admin
+        ClaimsInstruction::ExpireClaims => 1, // DAO, followed by the claim accounts to expire
+        ClaimsInstruction::SetPendingTtl { .. } => 2, // DAO, admin
+        ClaimsInstruction::BatchVerify { .. } => 2, // DAO, verifier, followed by one claim account per id
+    }
+}
+
+// Instructions that only read state, and so still run while the DAO is paused
+fn allowed_while_paused(instruction: &ClaimsInstruction) -> bool {
+    matches!(instruction, ClaimsInstruction::ExportClaims { .. })
+}
+
+// Address and bump of the PDA holding the claim with the given id
+pub fn claim_address(program_id: &Pubkey, dao_key: &Pubkey, claim_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CLAIM_SEED, dao_key.as_ref(), &claim_id.to_le_bytes()], program_id)
+}
+
+// Address and bump of the PDA marking an external reference as used
+pub(crate) fn ref_marker_address(program_id: &Pubkey, dao_key: &Pubkey, external_ref: &[u8; 16]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CLAIM_REF_SEED, dao_key.as_ref(), external_ref], program_id)
+}
+
+// Address and bump of the PDA recording the latest claim for the service with the given `service_key`
+pub(crate) fn service_marker_address(program_id: &Pubkey, dao_key: &Pubkey, service: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CLAIM_SERVICE_SEED, dao_key.as_ref(), service], program_id)
+}
+
+// Id for the next claim; ids only ever increase, so archiving a claim never frees its id or address for reuse
+pub(crate) fn assign_claim_id(claims_count: &mut u64) -> Result<u64, ProgramError> {
+    let claim_id = *claims_count;
+    *claims_count = claim_id.checked_add(1).ok_or(ProgramError::ArithmeticOverflow)?;
+    Ok(claim_id)
+}
+
+// Load claim `claim_id` from its PDA, or None if the claim was archived or never submitted
+pub(crate) fn load_live_claim(program_id: &Pubkey, dao_key: &Pubkey, claim_account: &AccountInfo, claim_id: u64) -> Result<Option<Claim>, ProgramError> {
+    if *claim_account.key != claim_address(program_id, dao_key, claim_id).0 {
+        return Err(ProgramError::InvalidSeeds); // Claim accounts must be this DAO's PDA for the claim id
+    }
+    if claim_account.owner != program_id || claim_account.data_is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(Claim::load(claim_account)?))
+}
+
+// Load claim `claim_id` from its PDA, which must still exist
+pub(crate) fn load_claim(program_id: &Pubkey, dao_key: &Pubkey, claim_account: &AccountInfo, claim_id: u64) -> Result<Claim, ProgramError> {
+    load_live_claim(program_id, dao_key, claim_account, claim_id)?.ok_or_else(|| {
+        msg!("Claim {} has no account at {}", claim_id, claim_account.key);
+        DaoError::ClaimNotFound.into()
+    })
+}
+
+// Load a claim passed without its id, checking the account is this DAO's PDA for the id stored in it
+pub(crate) fn load_listed_claim(program_id: &Pubkey, dao_key: &Pubkey, claim_account: &AccountInfo) -> Result<Claim, ProgramError> {
+    if claim_account.owner != program_id || claim_account.data_is_empty() {
+        return Err(DaoError::ClaimNotFound.into());
+    }
+    let claim = Claim::load(claim_account)?;
+    if *claim_account.key != claim_address(program_id, dao_key, claim.claim_id).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    Ok(claim)
+}
+
+// Write the claim back to its account, which must still have room for everything recorded since submission
+pub(crate) fn save_claim(claim_account: &AccountInfo, claim: &Claim) -> ProgramResult {
+    let len = claim.try_to_vec()?.len();
+    if claim_account.data_len() < len {
+        msg!("Claim {} needs {} bytes but its account holds {}", claim.claim_id, len, claim_account.data_len());
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+    claim.save(claim_account)
+}
+
+// Create the PDA for a new claim, funded by `payer`, and write the claim into it
+pub(crate) fn create_claim<'a>(
+    program_id: &Pubkey,
+    dao_key: &Pubkey,
+    payer: &AccountInfo<'a>,
+    claim_account: &AccountInfo<'a>,
+    system_program_account: &AccountInfo<'a>,
+    claim: &Claim,
+) -> ProgramResult {
+    let (claim_key, bump) = claim_address(program_id, dao_key, claim.claim_id);
+    if *claim_account.key != claim_key {
+        return Err(ProgramError::InvalidSeeds); // Claim accounts must be this DAO's PDA for the claim id
+    }
+    let space = claim.try_to_vec()?.len() + CLAIM_ACCOUNT_HEADROOM;
+    create_pda(program_id, payer, claim_account, system_program_account, space, &[CLAIM_SEED, dao_key.as_ref(), &claim.claim_id.to_le_bytes(), &[bump]])?;
+    claim.save(claim_account)
+}
+
+// Allocate a program-owned PDA of `space` bytes, funded by `payer`
+pub(crate) fn create_pda<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    pda: &AccountInfo<'a>,
+    system_program_account: &AccountInfo<'a>,
+    space: usize,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    if !payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if *system_program_account.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    invoke_signed(
+        &system_instruction::create_account(payer.key, pda.key, Rent::get()?.minimum_balance(space), space as u64, program_id),
+        &[payer.clone(), pda.clone(), system_program_account.clone()],
+        &[signer_seeds],
+    )
+}
+
+// Close an archived claim's account, returning its rent to the DAO account
+fn close_claim_account(dao_account: &AccountInfo, claim_account: &AccountInfo) -> ProgramResult {
+    **dao_account.try_borrow_mut_lamports()? = dao_account.lamports().checked_add(claim_account.lamports()).ok_or(ProgramError::ArithmeticOverflow)?;
+    **claim_account.try_borrow_mut_lamports()? = 0;
+    claim_account.realloc(0, false)?;
+    claim_account.assign(&system_program::id());
+    Ok(())
+}
+
+// Add the claim's contribution to the totals the DAO keeps over its claim accounts
+pub(crate) fn track_claim(dao_data: &mut HealthInsuranceDAO, claim: &Claim) {
+    tally_claim(dao_data, claim, true);
+}
+
+// Remove the claim's contribution from the DAO's totals, before the claim changes or when its account is closed
+pub(crate) fn untrack_claim(dao_data: &mut HealthInsuranceDAO, claim: &Claim) {
+    tally_claim(dao_data, claim, false);
+}
+
+// Remove the claim's unpaid amount from the DAO-wide exposure totals, leaving the histories that count it untouched
+fn release_claim_exposure(dao_data: &mut HealthInsuranceDAO, claim: &Claim) {
+    tally_exposure(dao_data, claim, false);
+}
+
+// Add (or remove) the claim's unpaid amount to the DAO's outstanding payouts and open exposure
+fn tally_exposure(dao_data: &mut HealthInsuranceDAO, claim: &Claim, add: bool) {
+    let adjust = |total: u64, amount: u64| if add { total.saturating_add(amount) } else { total.saturating_sub(amount) };
+    let unpaid = claim.amount.saturating_sub(claim.paid_amount);
+    if finance::awaiting_payout(claim) {
+        dao_data.outstanding_claims = adjust(dao_data.outstanding_claims, unpaid);
+    }
+    if !finance::claim_settled(claim) {
+        dao_data.open_claim_exposure = adjust(dao_data.open_claim_exposure, unpaid);
+    }
+}
+
+// Add (or remove) one claim's contribution to the DAO-wide, per-provider, and per-member claim totals
+fn tally_claim(dao_data: &mut HealthInsuranceDAO, claim: &Claim, add: bool) {
+    tally_exposure(dao_data, claim, add);
+    let adjust = |total: u64, amount: u64| if add { total.saturating_add(amount) } else { total.saturating_sub(amount) };
+    let count = |total: u32, counted: bool| match (counted, add) {
+        (false, _) => total,
+        (true, true) => total.saturating_add(1),
+        (true, false) => total.saturating_sub(1),
+    };
+    let settled = finance::claim_settled(claim);
+    let rejected = claim.status == ClaimStatus::Rejected;
+    let paid = claim.status == ClaimStatus::Paid;
+
+    match dao_data.provider_claims.iter_mut().find(|p| p.provider == claim.provider) {
+        Some(stats) => {
+            stats.claims = count(stats.claims, true);
+            stats.rejected = count(stats.rejected, rejected);
+        }
+        None if add => dao_data.provider_claims.push(ProviderClaims { provider: claim.provider, claims: 1, rejected: rejected as u32 }),
+        None => {}
+    }
+
+    if let Some(record) = dao_data.members.iter_mut().find(|m| m.member_address == claim.member) {
+        let history = &mut record.claim_history;
+        history.submitted = count(history.submitted, true);
+        history.amount_submitted = adjust(history.amount_submitted, claim.amount);
+        match history.service_types.iter_mut().find(|(service_type, _)| *service_type == claim.service_type) {
+            Some((_, claims)) => *claims = count(*claims, true),
+            None if add => history.service_types.push((claim.service_type, 1)),
+            None => {}
+        }
+        history.open = count(history.open, !settled);
+        history.paid = count(history.paid, paid);
+        history.rejected = count(history.rejected, rejected);
+        history.paid_out = count(history.paid_out, claim.paid_amount > 0);
+        history.paid_out_total = adjust(history.paid_out_total, claim.paid_amount);
+    }
+
+    // Shared claims draw on every participant's coverage, so each participant's record carries the claim too
+    let in_flight = finance::claim_in_flight(claim);
+    for (participant, amount) in finance::claim_participants(claim) {
+        if let Some(record) = dao_data.members.iter_mut().find(|m| m.member_address == participant) {
+            let history = &mut record.claim_history;
+            history.in_flight = count(history.in_flight, in_flight);
+            if claim.status != ClaimStatus::Withdrawn {
+                if add {
+                    history.recent_submissions.push(claim.submitted_at);
+                } else if let Some(i) = history.recent_submissions.iter().position(|t| *t == claim.submitted_at) {
+                    history.recent_submissions.swap_remove(i);
+                }
+            }
+            if paid {
+                history.paid_coverage = adjust(history.paid_coverage, amount);
+            }
+        }
+    }
+}
+
+// Claims drawing on the member's coverage submitted within the rolling year ending at `now`; withdrawn claims don't count
+fn claims_in_year(history: &ClaimHistory, now: i64) -> u32 {
+    let window_start = now.saturating_sub(CLAIM_YEAR_SECONDS);
+    history.recent_submissions.iter().filter(|t| **t > window_start).count() as u32
+}
+
+// Heuristic outlier score for a new claim, from 0 (typical) to 100, built from three capped components:
+// - up to 40 for an amount well above the member's average prior claim
+// - up to 30 for repeated claims of the same service type by the member
+// - up to 30 for the share of the provider's past claims that were rejected
+fn anomaly_score(history: &ClaimHistory, provider_claims: Option<&ProviderClaims>, amount: u64, service_type: ServiceType) -> u8 {
+    let amount_score = if history.submitted == 0 {
+        0
+    } else {
+        let average = history.amount_submitted as u128 / history.submitted as u128;
+        match amount as u128 {
+            a if a > average * 3 => 40,
+            a if a > average * 2 => 25,
+            a if a * 2 > average * 3 => 10,
+            _ => 0,
+        }
+    };
+
+    let repeats = history.service_types.iter().find(|(t, _)| *t == service_type).map_or(0, |(_, claims)| *claims as u64);
+    let frequency_score = (repeats * 10).min(30);
+
+    let provider_score = match provider_claims {
+        Some(stats) if stats.claims > 0 => stats.rejected as u64 * 30 / stats.claims as u64,
+        _ => 0,
+    };
+
+    (amount_score + frequency_score + provider_score) as u8
+}
+
+// Penalty for a member with `strikes` confirmed fraudulent claims; a threshold of 0 is never reached
+fn fraud_penalty(strikes: u8, suspension_strikes: u8, removal_strikes: u8) -> FraudPenalty {
+    if removal_strikes > 0 && strikes >= removal_strikes {
+        FraudPenalty::Removal
+    } else if suspension_strikes > 0 && strikes >= suspension_strikes {
+        FraudPenalty::Suspension
+    } else {
+        FraudPenalty::Warning
+    }
+}
+
+// A member's claim history is their reputation for micro-claims: enough paid claims and none rejected
+fn in_good_standing(history: &ClaimHistory, min_paid_claims: u32) -> bool {
+    history.paid >= min_paid_claims && history.rejected == 0
+}
+
+// Distinct verifiers who have approved or declined the claim
+pub(crate) fn reviewer_count(claim: &Claim) -> usize {
+    claim.verifiers.len() + claim.rejections.len()
+}
+
+// Paid and rejected claims submitted at or before the cutoff are in a terminal state and old enough to archive
+fn is_archivable(claim: &Claim, cutoff: i64) -> bool {
+    matches!(claim.status, ClaimStatus::Paid | ClaimStatus::Rejected) && claim.submitted_at <= cutoff
+}
+
+// Merkle root over the leaf hashes, pairing adjacent nodes and carrying an odd node up by hashing it with itself
+fn merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    if leaves.is_empty() {
+        return [0u8; 32];
+    }
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| hashv(&[&pair[0], pair.get(1).unwrap_or(&pair[0])]).to_bytes())
+            .collect();
+    }
+    level[0]
+}
+
+// Records the verifier's review of one claim, moving it to Verified once enough verifiers approve. Callers check
+// the verifier role; every other check runs before the claim is touched, so a failed review leaves the DAO unchanged.
+fn review_claim(dao_data: &mut HealthInsuranceDAO, claim: &mut Claim, verifier: &Pubkey, approve: bool, now: i64) -> ProgramResult {
+    // Locate the verifier and provider records in one pass over the member list
+    let (verifier_index, provider_index) = member_indices(&dao_data.members, verifier, &claim.provider);
+    if *verifier == claim.member {
+        msg!("Verifier {} cannot verify their own claim {}", verifier, claim.claim_id);
+        return Err(DaoError::SelfVerification.into());
+    }
+
+    // Verifiers must have enough stake at risk to be held accountable for bad verifications
+    let stake = verifier_index.map_or(0, |i| dao_data.members[i].stake);
+    if stake < dao_data.verifier_stake_requirement {
+        msg!("Verifier {} has {} staked but {} is required", verifier, stake, dao_data.verifier_stake_requirement);
+        return Err(DaoError::InsufficientStake.into());
+    }
+
+    // Reject verifiers with a conflict of interest on this claim
+    let verifier_affiliations = verifier_index.map_or(&[][..], |i| dao_data.members[i].affiliations.as_slice());
+    let provider_affiliations = provider_index.map_or(&[][..], |i| dao_data.members[i].affiliations.as_slice());
+    if has_conflict_of_interest(verifier, verifier_affiliations, claim, provider_affiliations) {
+        msg!("Verifier {} has a conflict of interest on claim {}", verifier, claim.claim_id);
+        return Err(DaoError::ConflictOfInterest.into());
+    }
+
+    // Rotate verifiers away from providers they've already reviewed too often, to limit collusion
+    let max_pairings = dao_data.max_verifier_provider_pairings;
+    if max_pairings > 0 && pairing_count(&dao_data.verifier_pairings, verifier, &claim.provider) >= max_pairings {
+        msg!("Verifier {} has reached the limit of {} verifications for provider {}", verifier, max_pairings, claim.provider);
+        return Err(DaoError::VerifierPairingLimit.into());
+    }
+
+    // Appealed claims may only be reviewed by the verifiers assigned to the appeal
+    if !claim.assigned_verifiers.is_empty() && !claim.assigned_verifiers.contains(verifier) {
+        msg!("Verifier {} is not assigned to appealed claim {}", verifier, claim.claim_id);
+        return Err(DaoError::NotAssignedVerifier.into());
+    }
+
+    // Give fraud-detection heuristics time to run before the claim can be verified
+    let eligible_at = claim.submitted_at.saturating_add(dao_data.verify_cooling_seconds);
+    if now < eligible_at {
+        msg!("Claim {} is cooling until {}", claim.claim_id, eligible_at);
+        return Err(DaoError::VerificationCooling.into());
+    }
+
+    // Verified claims stay open to review until enough distinct verifiers have looked at them
+    let awaiting_review = reviewer_count(claim) < dao_data.min_verifiers_reviewed as usize;
+    match claim.status {
+        ClaimStatus::Pending | ClaimStatus::Verified if claim.status == ClaimStatus::Pending || awaiting_review => {
+            if claim.verifiers.contains(verifier) || claim.rejections.contains(verifier) {
+                return Err(DaoError::DuplicateVote.into()); // Each verifier reviews a claim once
+            }
+            // Cap the reviewer lists so they can't grow the account without bound
+            let max_verifiers = match dao_data.max_verifiers_per_claim {
+                0 => DEFAULT_MAX_VERIFIERS_PER_CLAIM,
+                max => max,
+            };
+            if reviewer_count(claim) >= max_verifiers as usize {
+                msg!("Claim {} already has the maximum of {} verifiers", claim.claim_id, max_verifiers);
+                return Err(DaoError::VerifierLimitReached.into());
+            }
+            untrack_claim(dao_data, claim);
+            if approve {
+                claim.verifiers.push(*verifier);
+            } else {
+                claim.rejections.push(*verifier);
+            }
+            if let Some(i) = verifier_index {
+                dao_data.members[i].last_verification_at = now;
+            }
+            if max_pairings > 0 {
+                record_pairing(&mut dao_data.verifier_pairings, verifier, &claim.provider);
+            }
+            let base_threshold = base_verification_threshold(dao_data.verification_threshold);
+            if claim.verifiers.len() >= verification_threshold(base_threshold, dao_data.emergency.as_ref(), &dao_data.category_verification_thresholds, claim, now) {
+                claim.status = ClaimStatus::Verified;
+            }
+            track_claim(dao_data, claim);
+            msg!("Claim {} (external ref {:?}, anomaly score {}) verification in progress. Approvals: {}, reviews: {}", claim.claim_id, claim.external_ref, claim.anomaly_score, claim.verifiers.len(), reviewer_count(claim));
+        },
+        _ => return Err(DaoError::ClaimAlreadyVerified.into()),
+    }
+    Ok(())
+}
+
+// Positions of the verifier and the provider in the member list, found in a single scan
+fn member_indices(members: &[Member], verifier: &Pubkey, provider: &Pubkey) -> (Option<usize>, Option<usize>) {
+    let mut verifier_index = None;
+    let mut provider_index = None;
+    for (i, m) in members.iter().enumerate() {
+        if verifier_index.is_none() && m.member_address == *verifier {
+            verifier_index = Some(i);
+        }
+        if provider_index.is_none() && m.member_address == *provider {
+            provider_index = Some(i);
+        }
+        if verifier_index.is_some() && provider_index.is_some() {
+            break;
+        }
+    }
+    (verifier_index, provider_index)
+}
+
+// Verifications a claim needs absent category or emergency overrides; accounts created before the threshold
+// was configurable store 0 and keep the original default
+fn base_verification_threshold(configured: u8) -> usize {
+    match configured {
+        0 => DEFAULT_VERIFICATION_THRESHOLD as usize,
+        threshold => threshold as usize,
+    }
+}
+
+// Verifications needed for a claim: its service type's threshold (or the base threshold), reduced while an emergency
+// covering its service date is active
+fn verification_threshold(base_threshold: usize, emergency: Option<&Emergency>, category_thresholds: &[(ServiceType, u8)], claim: &Claim, now: i64) -> usize {
+    let threshold = category_thresholds
+        .iter()
+        .find(|(service_type, _)| *service_type == claim.service_type)
+        .map_or(base_threshold, |(_, threshold)| *threshold as usize);
+    match emergency {
+        Some(e) if now <= e.expires_at && claim.service_date >= e.window_start && claim.service_date <= e.expires_at => {
+            threshold.min(e.verification_threshold as usize)
+        }
+        _ => threshold,
+    }
+}
+
+// Build the export page for the claims loaded from ids starting at `offset`
+fn export_claims_page(claims: &[Claim], total_claims: u64, offset: u64) -> ClaimsExport {
+    let records = claims
+        .iter()
+        .take(CLAIMS_EXPORT_PAGE_SIZE)
+        .map(|claim| ClaimRecord {
+            claim_id: claim.claim_id,
+            member: claim.member,
+            provider: claim.provider,
+            amount: claim.amount,
+            service_date: claim.service_date,
+            status: claim.status.clone(),
+        })
+        .collect();
+    ClaimsExport {
+        version: CLAIMS_EXPORT_VERSION,
+        total_claims,
+        offset,
+        records,
+    }
+}
+
+// Verifiers eligible to review an appeal: anyone with the verifier role who didn't review the claim originally,
+// has no conflict of interest on it, and hasn't hit the pairing limit with its provider. Verifiers least familiar
+// with the provider come first so assignments spread across the pool.
+fn appeal_verifiers(
+    members: &[Member],
+    claim: &Claim,
+    provider_affiliations: &[Pubkey],
+    pairings: &[VerifierPairing],
+    max_pairings: u32,
+) -> Vec<Pubkey> {
+    let mut eligible = members
+        .iter()
+        .filter(|m| m.role == Role::Verifier)
+        .filter(|m| !claim.verifiers.contains(&m.member_address))
+        .filter(|m| !has_conflict_of_interest(&m.member_address, &m.affiliations, claim, provider_affiliations))
+        .map(|m| (pairing_count(pairings, &m.member_address, &claim.provider), m.member_address))
+        .filter(|(count, _)| max_pairings == 0 || *count < max_pairings)
+        .collect::<Vec<_>>();
+    eligible.sort_by_key(|(count, _)| *count);
+    eligible.into_iter().map(|(_, verifier)| verifier).collect()
+}
+
+// Verifications the verifier has recorded on the provider's claims
+fn pairing_count(pairings: &[VerifierPairing], verifier: &Pubkey, provider: &Pubkey) -> u32 {
+    pairings
+        .iter()
+        .find(|p| p.verifier == *verifier && p.provider == *provider)
+        .map_or(0, |p| p.count)
+}
+
+// Count one more verification by the verifier on the provider's claims
+fn record_pairing(pairings: &mut Vec<VerifierPairing>, verifier: &Pubkey, provider: &Pubkey) {
+    match pairings.iter_mut().find(|p| p.verifier == *verifier && p.provider == *provider) {
+        Some(pairing) => pairing.count = pairing.count.saturating_add(1),
+        None => pairings.push(VerifierPairing { verifier: *verifier, provider: *provider, count: 1 }),
+    }
+}
+
+// Key identifying a service by member, provider, service date, and amount, used to seed its marker PDA
+pub(crate) fn service_key(member: &Pubkey, provider: &Pubkey, service_date: i64, amount: u64) -> [u8; 32] {
+    hashv(&[member.as_ref(), provider.as_ref(), &service_date.to_le_bytes(), &amount.to_le_bytes()]).to_bytes()
+}
+
+// Whether an earlier claim for a service still blocks a new one. Rejected and withdrawn claims don't, so a corrected
+// claim can still be filed for the same service.
+fn is_standing(claim: &Claim) -> bool {
+    claim.status != ClaimStatus::Rejected && claim.status != ClaimStatus::Withdrawn
+}
+
+// A verifier is conflicted if the claim is their own, if they are affiliated with the provider,
+// or if they share an affiliation with the provider
+fn has_conflict_of_interest(
+    verifier: &Pubkey,
+    verifier_affiliations: &[Pubkey],
+    claim: &Claim,
+    provider_affiliations: &[Pubkey],
+) -> bool {
+    *verifier == claim.member
+        || verifier_affiliations.contains(&claim.provider)
+        || verifier_affiliations.iter().any(|a| provider_affiliations.contains(a))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction::{DaoInstruction, FinanceInstruction};
+    use crate::{process_instruction, Dispute, DisputeStatus};
+    use crate::testing::Ledger;
+    use solana_program::instruction::{AccountMeta, Instruction};
+    use solana_program_test::*;
+    use solana_sdk::{
+        account::Account,
+        instruction::InstructionError,
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    };
+
+    // Instruction data routing `instruction` to the claims module
+    fn claims_data(instruction: ClaimsInstruction) -> Vec<u8> {
+        DaoInstruction::Claims(instruction).try_to_vec().unwrap()
+    }
+
+    // Lamports held above rent in test DAO accounts, which double as the treasury for payouts
+    const TREASURY_FUNDS: u64 = 100_000_000;
+
+    // A pending claim submitted by `member` for a service at `provider`
+    fn pending_claim(claim_id: u64, member: Pubkey, provider: Pubkey) -> Claim {
+        Claim {
+            claim_id,
+            member,
+            amount: 1_000_000,
+            service_date: 0,
+            service_type: ServiceType::Consultation,
+            provider,
+            status: ClaimStatus::Pending,
+            verifiers: Vec::new(),
+            preauth_id: None,
+            assigned_verifiers: Vec::new(),
+            condition: None,
+            condition_met: false,
+            external_ref: (claim_id as u128).to_le_bytes(),
+            submitted_at: 0,
+            provider_attested: false,
+            supersedes: None,
+            holdback: 0,
+            holdback_release_at: 0,
+            anomaly_score: 0,
+            rejections: Vec::new(),
+            ack_requested_at: 0,
+            acknowledged: false,
+            shares: Vec::new(),
+            zkp_proof: Vec::new(),
+            rejection_reason: None,
+            appealed: false,
+            paid_amount: 0,
+            resubmitted: false,
+        }
+    }
+
+    // DAO state with the given members and claims and no optional policies configured
+    fn dao_state(members: Vec<Member>, claims: Vec<Claim>) -> Ledger {
+        let dao = HealthInsuranceDAO {
+            admin: Pubkey::new_unique(),
+            members,
+            treasury_account: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        Ledger::new(dao, claims)
+    }
+
+    // Account for the PDA holding claim `claim_id`
+    fn claim_meta(program_id: Pubkey, dao_key: Pubkey, claim_id: u64) -> AccountMeta {
+        AccountMeta::new(claim_address(&program_id, &dao_key, claim_id).0, false)
+    }
+
+    fn member(member_address: Pubkey, affiliations: Vec<Pubkey>) -> Member {
+        Member {
+            member_address,
+            affiliations,
+            role: Role::Member,
+            ..Default::default()
+        }
+    }
+
+    fn verifier(member_address: Pubkey, last_verification_at: i64) -> Member {
+        Member {
+            role: Role::Verifier,
+            last_verification_at,
+            ..member(member_address, Vec::new())
+        }
+    }
+
+    // System-owned wallet that can pay for new accounts and receive payouts
+    fn wallet() -> Account {
+        Account { lamports: 1_000_000_000, data: Vec::new(), owner: system_program::id(), executable: false, rent_epoch: 0 }
+    }
+
+    // Run a single instruction against a DAO account and claim accounts seeded with `dao`
+    async fn process(
+        program_id: Pubkey,
+        dao_key: Pubkey,
+        dao: &Ledger,
+        instruction: Instruction,
+        signers: &[&Keypair],
+    ) -> Result<(), BanksClientError> {
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, TREASURY_FUNDS);
+        for signer in signers {
+            program_test.add_account(signer.pubkey(), wallet());
+        }
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let mut all_signers = vec![&payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &all_signers,
+            recent_blockhash,
+        );
+        banks_client.process_transaction(transaction).await
+    }
+
+    // Run a single instruction with the cluster clock set to `unix_timestamp`, returning the result and final DAO state
+    async fn process_at(
+        program_id: Pubkey,
+        dao_key: Pubkey,
+        dao: &Ledger,
+        instruction: Instruction,
+        signers: &[&Keypair],
+        unix_timestamp: i64,
+    ) -> (Result<(), BanksClientError>, Ledger) {
+        process_at_funded(program_id, dao_key, dao, &[], instruction, signers, unix_timestamp).await
+    }
+
+    // Like `process_at`, with each of `funded` seeded as a wallet that can receive payouts
+    async fn process_at_funded(
+        program_id: Pubkey,
+        dao_key: Pubkey,
+        dao: &Ledger,
+        funded: &[Pubkey],
+        instruction: Instruction,
+        signers: &[&Keypair],
+        unix_timestamp: i64,
+    ) -> (Result<(), BanksClientError>, Ledger) {
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, TREASURY_FUNDS);
+        // Signers fund the accounts their instructions create
+        let signer_keys: Vec<Pubkey> = signers.iter().map(|s| s.pubkey()).filter(|key| !funded.contains(key)).collect();
+        for key in funded.iter().chain(&signer_keys) {
+            program_test.add_account(*key, wallet());
+        }
+        let mut context = program_test.start_with_context().await;
+        context.set_sysvar(&Clock { unix_timestamp, ..Clock::default() });
+
+        let mut all_signers = vec![&context.payer];
+        all_signers.extend_from_slice(signers);
+        let instructions = [instruction];
+        let transaction = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&context.payer.pubkey()),
+            &all_signers,
+            context.last_blockhash,
+        );
+        let result = context.banks_client.process_transaction(transaction).await;
+
+        let dao = dao.reload(&mut context.banks_client, program_id, dao_key, &instructions).await;
+        (result, dao)
+    }
+
+    fn verify_instruction(program_id: Pubkey, dao_key: Pubkey, verifier: Pubkey, claim_id: u64) -> Instruction {
+        let data = claims_data(ClaimsInstruction::VerifyClaim { claim_id, approve: true });
+        Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),          // DAO account
+                AccountMeta::new_readonly(verifier, true), // Verifier
+                claim_meta(program_id, dao_key, claim_id), // Claim account
+            ],
+            data,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_self_verification_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_account = Keypair::new();
+        let claimant = Keypair::new();
+        let provider = Pubkey::new_unique();
+
+        // The claimant holds the verifier role and files a claim of their own
+        let dao = dao_state(vec![verifier(claimant.pubkey(), 0)], Vec::new());
+        let instruction = submit_instruction(program_id, dao_account.pubkey(), claimant.pubkey(), provider, 1_000, None, ServiceType::Consultation, [7; 16]);
+        let (result, dao) = process_at(program_id, dao_account.pubkey(), &dao, instruction, &[&claimant], 0).await;
+        assert!(result.is_ok());
+
+        // The claimant attempts to verify their own claim
+        let instruction = verify_instruction(program_id, dao_account.pubkey(), claimant.pubkey(), 0);
+        let (result, dao) = process_at(program_id, dao_account.pubkey(), &dao, instruction, &[&claimant], 0).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::SelfVerification as u32)),
+        );
+        assert!(dao.claims[0].verifiers.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_affiliated_provider_verification_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_account = Keypair::new();
+        let claimant = Pubkey::new_unique();
+        let provider = Pubkey::new_unique();
+        let hospital_group = Pubkey::new_unique();
+        let direct_verifier = Keypair::new();
+        let shared_verifier = Keypair::new();
+
+        let dao = dao_state(
+            vec![
+                member(claimant, Vec::new()),
+                member(provider, vec![hospital_group]),
+                Member { affiliations: vec![provider], ..verifier(direct_verifier.pubkey(), 0) }, // Affiliated with the provider itself
+                Member { affiliations: vec![hospital_group], ..verifier(shared_verifier.pubkey(), 0) }, // Shares the provider's affiliation
+            ],
+            vec![pending_claim(0, claimant, provider)],
+        );
+
+        let instruction = verify_instruction(program_id, dao_account.pubkey(), direct_verifier.pubkey(), 0);
+        let result = process(program_id, dao_account.pubkey(), &dao, instruction, &[&direct_verifier]).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::ConflictOfInterest as u32)),
+        );
+
+        let instruction = verify_instruction(program_id, dao_account.pubkey(), shared_verifier.pubkey(), 0);
+        let result = process(program_id, dao_account.pubkey(), &dao, instruction, &[&shared_verifier]).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::ConflictOfInterest as u32)),
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn submit_instruction(
+        program_id: Pubkey,
+        dao_key: Pubkey,
+        member: Pubkey,
+        provider: Pubkey,
+        amount: u64,
+        preauth_id: Option<u64>,
+        service_type: ServiceType,
+        external_ref: [u8; 16],
+    ) -> Instruction {
+        let data = claims_data(ClaimsInstruction::SubmitClaim {
+            amount,
+            service_date: 0,
+            external_ref,
+            preauth_id,
+            condition: None,
+            supersedes: None,
+            service_type,
+            nonce: 0,
+        });
+        Instruction { program_id, accounts: submit_accounts(program_id, dao_key, member, provider, 0, amount, external_ref), data }
+    }
+
+    // Accounts for submitting the given service as claim 0; `as_claim` retargets the instruction at a later claim id
+    fn submit_accounts(program_id: Pubkey, dao_key: Pubkey, member: Pubkey, provider: Pubkey, service_date: i64, amount: u64, external_ref: [u8; 16]) -> Vec<AccountMeta> {
+        let ref_marker = ref_marker_address(&program_id, &dao_key, &external_ref).0;
+        let service_marker = service_marker_address(&program_id, &dao_key, &service_key(&member, &provider, service_date, amount)).0;
+        vec![
+            AccountMeta::new(dao_key, false),                       // DAO account
+            AccountMeta::new(member, true),                         // Member submitting and funding the claim
+            AccountMeta::new_readonly(provider, false),             // Provider of the service
+            claim_meta(program_id, dao_key, 0),                     // Claim account
+            AccountMeta::new(ref_marker, false),                    // External reference marker
+            AccountMeta::new(service_marker, false),                // Service marker
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+        ]
+    }
+
+    // Point a submission built by `submit_instruction` at the account for `claim_id`
+    fn as_claim(mut instruction: Instruction, claim_id: u64) -> Instruction {
+        instruction.accounts[3] = claim_meta(instruction.program_id, instruction.accounts[0].pubkey, claim_id);
+        instruction
+    }
+
+    // DAO state requiring pre-authorization for surgery claims of 5M lamports or more
+    fn dao_requiring_surgery_preauth(claimant: Pubkey, preauths: Vec<PreAuth>) -> Ledger {
+        let mut dao = dao_state(vec![member(claimant, Vec::new())], Vec::new());
+        dao.preauths = preauths;
+        dao.preauth_amount_threshold = 5_000_000;
+        dao.preauth_service_types = vec![ServiceType::Surgery];
+        dao
+    }
+
+    #[tokio::test]
+    async fn test_claim_with_valid_preauth_accepted() {
+        let program_id = Pubkey::new_unique();
+        let dao_account = Keypair::new();
+        let claimant = Keypair::new();
+        let provider = Pubkey::new_unique();
+
+        let dao = dao_requiring_surgery_preauth(
+            claimant.pubkey(),
+            vec![PreAuth {
+                preauth_id: 0,
+                member: claimant.pubkey(),
+                provider,
+                service_type: ServiceType::Surgery,
+                max_amount: 8_000_000,
+                approved: true,
+                expires_at: i64::MAX,
+                used: false,
+            }],
+        );
+
+        let instruction = submit_instruction(program_id, dao_account.pubkey(), claimant.pubkey(), provider, 6_000_000, Some(0), ServiceType::Surgery, [7; 16]);
+        let result = process(program_id, dao_account.pubkey(), &dao, instruction, &[&claimant]).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_claim_beyond_preauth_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_account = Keypair::new();
+        let claimant = Keypair::new();
+        let provider = Pubkey::new_unique();
+
+        // The pre-authorization only covers up to 5.5M lamports
+        let dao = dao_requiring_surgery_preauth(
+            claimant.pubkey(),
+            vec![PreAuth {
+                preauth_id: 0,
+                member: claimant.pubkey(),
+                provider,
+                service_type: ServiceType::Surgery,
+                max_amount: 5_500_000,
+                approved: true,
+                expires_at: i64::MAX,
+                used: false,
+            }],
+        );
+
+        let instruction = submit_instruction(program_id, dao_account.pubkey(), claimant.pubkey(), provider, 6_000_000, Some(0), ServiceType::Surgery, [7; 16]);
+        let result = process(program_id, dao_account.pubkey(), &dao, instruction, &[&claimant]).await;
+        assert_eq!(result.unwrap_err().unwrap(), TransactionError::InstructionError(0, InstructionError::Custom(DaoError::PreAuthInvalid as u32)));
+    }
+
+    #[tokio::test]
+    async fn test_claim_without_preauth_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_account = Keypair::new();
+        let claimant = Keypair::new();
+        let provider = Pubkey::new_unique();
+
+        let dao = dao_requiring_surgery_preauth(claimant.pubkey(), Vec::new());
+
+        let instruction = submit_instruction(program_id, dao_account.pubkey(), claimant.pubkey(), provider, 6_000_000, None, ServiceType::Surgery, [7; 16]);
+        let result = process(program_id, dao_account.pubkey(), &dao, instruction, &[&claimant]).await;
+        assert_eq!(result.unwrap_err().unwrap(), TransactionError::InstructionError(0, InstructionError::Custom(DaoError::PreAuthRequired as u32)));
+    }
+
+    // Attempt to point the DAO at `treasury`, which is created with the given owner unless it is the DAO account itself
+    async fn set_treasury(treasury: Option<(Pubkey, Option<Pubkey>)>) -> Result<(), BanksClientError> {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let admin = Keypair::new();
+
+        let mut dao = dao_state(Vec::new(), Vec::new());
+        dao.admin = admin.pubkey();
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, 0);
+        let treasury_key = match treasury {
+            Some((treasury_key, owner)) => {
+                // No owner stands in for "owned by this program"
+                let owner = owner.unwrap_or(program_id);
+                program_test.add_account(
+                    treasury_key,
+                    Account {
+                        lamports: 10_000_000,
+                        data: Vec::new(),
+                        owner,
+                        executable: false,
+                        rent_epoch: 0,
+                    },
+                );
+                treasury_key
+            }
+            None => dao_key, // Same-account configuration
+        };
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                 // DAO account
+                AccountMeta::new_readonly(admin.pubkey(), true),  // Admin
+                AccountMeta::new_readonly(treasury_key, false),   // Proposed treasury
+            ],
+            data: claims_data(ClaimsInstruction::SetTreasury),
+        };
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[&payer, &admin],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(transaction).await
+    }
+
+    #[tokio::test]
+    async fn test_separate_treasury_accepted() {
+        let result = set_treasury(Some((Pubkey::new_unique(), Some(system_program::id())))).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_same_account_treasury_accepted() {
+        let result = set_treasury(None).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_program_owned_separate_treasury_rejected() {
+        // A separate account owned by the program is neither the DAO account nor a wallet that can sign transfers
+        let result = set_treasury(Some((Pubkey::new_unique(), None))).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_idle_verifier_demoted_active_verifier_kept() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let now = 100 * 86_400;
+        let idle = Pubkey::new_unique();
+        let active = Pubkey::new_unique();
+
+        let mut dao = dao_state(
+            vec![
+                verifier(idle, now - 60 * 86_400),  // Last verified 60 days ago
+                verifier(active, now - 86_400),     // Last verified yesterday
+            ],
+            Vec::new(),
+        );
+        dao.verifier_inactivity_secs = 30 * 86_400;
+
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![AccountMeta::new(dao_key, false)], // DAO account
+            data: claims_data(ClaimsInstruction::DemoteInactiveVerifiers),
+        };
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[], now).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.members[0].role, Role::Member);
+        assert_eq!(dao.members[1].role, Role::Verifier);
+    }
+
+    #[tokio::test]
+    async fn test_verifiers_beyond_cap_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_account = Keypair::new();
+        let late_verifier = Keypair::new();
+
+        // A pending claim that has already collected the maximum number of verifiers
+        let mut claim = pending_claim(0, Pubkey::new_unique(), Pubkey::new_unique());
+        claim.verifiers = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut dao = dao_state(vec![verifier(late_verifier.pubkey(), 0)], vec![claim]);
+        dao.max_verifiers_per_claim = 2;
+
+        let instruction = verify_instruction(program_id, dao_account.pubkey(), late_verifier.pubkey(), 0);
+        let result = process(program_id, dao_account.pubkey(), &dao, instruction, &[&late_verifier]).await;
+        assert_eq!(result.unwrap_err().unwrap(), TransactionError::InstructionError(0, InstructionError::Custom(DaoError::VerifierLimitReached as u32)));
+    }
+
+    fn appeal_instruction(program_id: Pubkey, dao_key: Pubkey, member: Pubkey, claim_id: u64) -> Instruction {
+        let data = claims_data(ClaimsInstruction::AppealClaim { claim_id });
+        Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),          // DAO account
+                AccountMeta::new_readonly(member, true),   // Claimant appealing
+                claim_meta(program_id, dao_key, claim_id), // Claim account
+            ],
+            data,
+        }
+    }
+
+    // A claim rejected after review by `reviewers`
+    fn rejected_claim(member: Pubkey, reviewers: Vec<Pubkey>) -> Claim {
+        let mut claim = pending_claim(0, member, Pubkey::new_unique());
+        claim.status = ClaimStatus::Rejected;
+        claim.verifiers = reviewers;
+        claim
+    }
+
+    #[tokio::test]
+    async fn test_appeal_assigned_to_fresh_verifiers() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Keypair::new();
+        let original = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let fresh = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+        let dao = dao_state(
+            vec![
+                member(claimant.pubkey(), Vec::new()),
+                verifier(original[0], 0),
+                verifier(original[1], 0),
+                verifier(fresh[0], 0),
+                verifier(fresh[1], 0),
+            ],
+            vec![rejected_claim(claimant.pubkey(), original.to_vec())],
+        );
+
+        let instruction = appeal_instruction(program_id, dao_key, claimant.pubkey(), 0);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&claimant], 0).await;
+        assert!(result.is_ok());
+
+        let claim = &dao.claims[0];
+        assert_eq!(claim.status, ClaimStatus::Appealed);
+        assert!(claim.appealed);
+        assert_eq!(claim.assigned_verifiers, fresh.to_vec());
+        assert!(original.iter().all(|v| !claim.assigned_verifiers.contains(v)));
+
+        // The appeal is decided through a dispute linked to the claim
+        let dispute = &dao.disputes[0];
+        assert_eq!(dispute.claim_id, Some(claim.claim_id));
+        assert_eq!(dispute.initiator, claimant.pubkey());
+        assert_eq!(dispute.respondent, dao_key);
+    }
+
+    #[tokio::test]
+    async fn test_claim_appealed_only_once() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Keypair::new();
+        let verifiers: Vec<Member> = (0..2).map(|_| verifier(Pubkey::new_unique(), 0)).collect();
+
+        // The earlier appeal failed, leaving the rejection in place
+        let mut claim = rejected_claim(claimant.pubkey(), Vec::new());
+        claim.appealed = true;
+        let mut members = vec![member(claimant.pubkey(), Vec::new())];
+        members.extend(verifiers);
+        let dao = dao_state(members, vec![claim]);
+
+        let instruction = appeal_instruction(program_id, dao_key, claimant.pubkey(), 0);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&claimant], 0).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::ClaimAlreadyAppealed as u32)),
+        );
+        assert!(dao.disputes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_appeal_rejected_without_enough_fresh_verifiers() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Keypair::new();
+        let original = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+        // Only one verifier who hasn't already reviewed the claim
+        let dao = dao_state(
+            vec![
+                member(claimant.pubkey(), Vec::new()),
+                verifier(original[0], 0),
+                verifier(original[1], 0),
+                verifier(Pubkey::new_unique(), 0),
+            ],
+            vec![rejected_claim(claimant.pubkey(), original.to_vec())],
+        );
+
+        let instruction = appeal_instruction(program_id, dao_key, claimant.pubkey(), 0);
+        let (result, _) = process_at(program_id, dao_key, &dao, instruction, &[&claimant], 0).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_appeal_rejected_at_open_dispute_cap() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Keypair::new();
+        let original = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+        // Fresh verifiers are available, but the DAO already has as many open disputes as it allows
+        let mut dao = dao_state(
+            vec![
+                member(claimant.pubkey(), Vec::new()),
+                verifier(original[0], 0),
+                verifier(original[1], 0),
+                verifier(Pubkey::new_unique(), 0),
+                verifier(Pubkey::new_unique(), 0),
+            ],
+            vec![rejected_claim(claimant.pubkey(), original.to_vec())],
+        );
+        dao.disputes = vec![Dispute { status: DisputeStatus::Open, ..Default::default() }];
+        dao.max_open_disputes = 1;
+
+        let instruction = appeal_instruction(program_id, dao_key, claimant.pubkey(), 0);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&claimant], 0).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::OpenDisputeLimitReached as u32)),
+        );
+        assert_eq!(dao.claims[0].status, ClaimStatus::Rejected);
+        assert_eq!(dao.disputes.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_submission_past_period_cap_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_account = Keypair::new();
+        let claimant = Keypair::new();
+
+        let mut dao = dao_state(vec![member(claimant.pubkey(), Vec::new())], Vec::new());
+        dao.period_claim_cap = 3;
+        dao.period_claims_used = 3;
+
+        let instruction = submit_instruction(program_id, dao_account.pubkey(), claimant.pubkey(), Pubkey::new_unique(), 1_000, None, ServiceType::Consultation, [7; 16]);
+        let result = process(program_id, dao_account.pubkey(), &dao, instruction, &[&claimant]).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::PeriodCapReached as u32)),
+        );
+    }
+
+    fn rollover_instruction(program_id: Pubkey, dao_key: Pubkey, admin: Pubkey) -> Instruction {
+        Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),       // DAO account
+                AccountMeta::new_readonly(admin, true), // Admin
+            ],
+            data: claims_data(ClaimsInstruction::RolloverPeriod),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_period_rollover_carries_unused_slots() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let admin = Keypair::new();
+
+        let mut dao = dao_state(Vec::new(), Vec::new());
+        dao.admin = admin.pubkey();
+        dao.period_claim_cap = 10;
+        dao.period_claims_used = 4;
+        dao.period_rollover_enabled = true;
+
+        let instruction = rollover_instruction(program_id, dao_key, admin.pubkey());
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&admin], 0).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.period_claims_used, 0);
+        assert_eq!(dao.period_carryover, 6);
+    }
+
+    #[tokio::test]
+    async fn test_period_rollover_without_carryover() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let admin = Keypair::new();
+
+        let mut dao = dao_state(Vec::new(), Vec::new());
+        dao.admin = admin.pubkey();
+        dao.period_claim_cap = 10;
+        dao.period_claims_used = 4;
+        dao.period_carryover = 3;
+
+        let instruction = rollover_instruction(program_id, dao_key, admin.pubkey());
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&admin], 0).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.period_claims_used, 0);
+        assert_eq!(dao.period_carryover, 0);
+    }
+
+    #[test]
+    fn test_claims_export_paged_and_reassembled() {
+        let claims: Vec<Claim> = (0..15)
+            .map(|id| pending_claim(id, Pubkey::new_unique(), Pubkey::new_unique()))
+            .collect();
+
+        // Page through the export the way an auditor would, decoding each page from its wire format
+        let mut reassembled = Vec::new();
+        let mut offset = 0;
+        loop {
+            let bytes = export_claims_page(&claims[offset as usize..], claims.len() as u64, offset).try_to_vec().unwrap();
+            assert!(bytes.len() <= solana_program::program::MAX_RETURN_DATA);
+
+            let page = ClaimsExport::try_from_slice(&bytes).unwrap();
+            assert_eq!(page.version, CLAIMS_EXPORT_VERSION);
+            assert_eq!(page.total_claims, 15);
+            assert_eq!(page.offset, offset);
+            if page.records.is_empty() {
+                break;
+            }
+            offset += page.records.len() as u64;
+            reassembled.extend(page.records);
+        }
+
+        assert_eq!(reassembled.len(), claims.len());
+        for (record, claim) in reassembled.iter().zip(claims.iter()) {
+            assert_eq!(record.claim_id, claim.claim_id);
+            assert_eq!(record.member, claim.member);
+            assert_eq!(record.provider, claim.provider);
+            assert_eq!(record.amount, claim.amount);
+            assert_eq!(record.status, claim.status);
+        }
+    }
+
+    // A verified claim contingent on the event attested by `attestation`
+    fn conditional_claim(member: Pubkey, attestation: &[u8]) -> Claim {
+        let mut claim = pending_claim(0, member, Pubkey::new_unique());
+        claim.status = ClaimStatus::Verified;
+        claim.condition = Some(hash(attestation).to_bytes());
+        claim
+    }
+
+    // Initialized treasury holding the DAO account's TREASURY_FUNDS, with unlimited coverage for `claimant`
+    fn fund_treasury(dao: &mut HealthInsuranceDAO, dao_key: Pubkey, claimant: Pubkey) {
+        dao.treasury_account = dao_key; // Treasury held in the DAO account
+        dao.treasury = crate::Treasury { balance: TREASURY_FUNDS, initialized: true, ..Default::default() };
+        dao.risk_profiles.push(crate::RiskProfile { member: claimant, coverage_limit: u64::MAX, ..Default::default() });
+    }
+
+    fn payout_instruction(program_id: Pubkey, dao_key: Pubkey, authority: Pubkey, member: Pubkey, claim_id: u64) -> Instruction {
+        let data = DaoInstruction::Finance(FinanceInstruction::PayoutClaim { claim_id, amount: None }).try_to_vec().unwrap();
+        Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                            // DAO account
+                AccountMeta::new_readonly(authority, true),                  // Admin or verifier releasing the payout
+                claim_meta(program_id, dao_key, claim_id),                   // Claim account
+                AccountMeta::new(dao_key, false),                            // Treasury
+                AccountMeta::new(member, false),                             // Member receiving the payout
+                AccountMeta::new_readonly(system_program::id(), false),      // System program
+            ],
+            data,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fulfilled_condition_unlocks_payout() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let oracle = Keypair::new();
+        let claimant = Pubkey::new_unique();
+        let attestation = b"follow-up confirms diagnosis";
+
+        let mut dao = dao_state(vec![member(claimant, Vec::new())], vec![conditional_claim(claimant, attestation)]);
+        fund_treasury(&mut dao, dao_key, claimant);
+        let admin = Keypair::new();
+        dao.admin = admin.pubkey();
+        dao.condition_oracle = oracle.pubkey();
+
+        let data = claims_data(ClaimsInstruction::FulfillCondition { claim_id: 0, attestation: attestation.to_vec() });
+        let fulfill = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                // DAO account
+                AccountMeta::new_readonly(oracle.pubkey(), true), // Condition oracle
+                claim_meta(program_id, dao_key, 0),              // Claim account
+            ],
+            data,
+        };
+        let (result, dao) = process_at(program_id, dao_key, &dao, fulfill, &[&oracle], 0).await;
+        assert!(result.is_ok());
+        assert!(dao.claims[0].condition_met);
+
+        let payout = payout_instruction(program_id, dao_key, admin.pubkey(), claimant, 0);
+        let (result, dao) = process_at(program_id, dao_key, &dao, payout, &[&admin], 0).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims[0].status, ClaimStatus::Paid);
+    }
+
+    #[tokio::test]
+    async fn test_unfulfilled_condition_blocks_payout() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Pubkey::new_unique();
+
+        let mut dao = dao_state(vec![member(claimant, Vec::new())], vec![conditional_claim(claimant, b"follow-up confirms diagnosis")]);
+        fund_treasury(&mut dao, dao_key, claimant);
+        let admin = Keypair::new();
+        dao.admin = admin.pubkey();
+
+        let payout = payout_instruction(program_id, dao_key, admin.pubkey(), claimant, 0);
+        let (result, dao) = process_at(program_id, dao_key, &dao, payout, &[&admin], 0).await;
+        assert!(result.is_err());
+        assert_eq!(dao.claims[0].status, ClaimStatus::Verified);
+    }
+
+    #[tokio::test]
+    async fn test_payout_transfers_lamports_to_member() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Pubkey::new_unique();
+
+        let mut claim = pending_claim(0, claimant, Pubkey::new_unique());
+        claim.status = ClaimStatus::Verified;
+        let mut dao = dao_state(vec![member(claimant, Vec::new())], vec![claim]);
+        fund_treasury(&mut dao, dao_key, claimant);
+        let admin = Keypair::new();
+        dao.admin = admin.pubkey();
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, TREASURY_FUNDS);
+        program_test.add_account(
+            claimant,
+            Account { lamports: 5_000_000, data: Vec::new(), owner: system_program::id(), executable: false, rent_epoch: 0 },
+        );
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        // Paying to anyone but the claimant is refused
+        let misdirected = payout_instruction(program_id, dao_key, admin.pubkey(), Pubkey::new_unique(), 0);
+        let transaction = Transaction::new_signed_with_payer(&[misdirected], Some(&payer.pubkey()), &[&payer, &admin], recent_blockhash);
+        assert!(banks_client.process_transaction(transaction).await.is_err());
+
+        let payout = [payout_instruction(program_id, dao_key, admin.pubkey(), claimant, 0)];
+        let transaction = Transaction::new_signed_with_payer(&payout, Some(&payer.pubkey()), &[&payer, &admin], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let member_account = banks_client.get_account(claimant).await.unwrap().unwrap();
+        assert_eq!(member_account.lamports, 5_000_000 + 1_000_000);
+        let dao = dao.reload(&mut banks_client, program_id, dao_key, &payout).await;
+        assert_eq!(dao.claims[0].status, ClaimStatus::Paid);
+    }
+
+    #[tokio::test]
+    async fn test_too_few_accounts_rejected_early() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let dao = dao_state(Vec::new(), Vec::new());
+
+        // Submit claim, holdback release, and set treasury each supplied with only the DAO account
+        let submit = ClaimsInstruction::SubmitClaim {
+            amount: 0,
+            service_date: 0,
+            external_ref: [0; 16],
+            preauth_id: None,
+            condition: None,
+            supersedes: None,
+            service_type: ServiceType::default(),
+            nonce: 0,
+        };
+        for claims_instruction in [submit, ClaimsInstruction::ReleaseHoldback { claim_id: 0 }, ClaimsInstruction::SetTreasury] {
+            let instruction = Instruction {
+                program_id,
+                accounts: vec![AccountMeta::new(dao_key, false)],
+                data: claims_data(claims_instruction),
+            };
+            let result = process(program_id, dao_key, &dao, instruction, &[]).await;
+            assert_eq!(
+                result.unwrap_err().unwrap(),
+                TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys),
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_emergency_threshold_applies_inside_window_only() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let verifier_key = Keypair::new();
+        let now = 50 * 86_400;
+
+        // Claim 0 is for a service during the emergency, claim 1 for a service before it
+        let mut inside = pending_claim(0, Pubkey::new_unique(), Pubkey::new_unique());
+        inside.service_date = now - 86_400;
+        let mut outside = pending_claim(1, Pubkey::new_unique(), Pubkey::new_unique());
+        outside.service_date = now - 30 * 86_400;
+
+        let mut dao = dao_state(vec![verifier(verifier_key.pubkey(), 0)], vec![inside, outside]);
+        dao.emergency = Some(Emergency {
+            verification_threshold: 1,
+            window_start: now - 7 * 86_400,
+            expires_at: now + 7 * 86_400,
+        });
+
+        let instruction = verify_instruction(program_id, dao_key, verifier_key.pubkey(), 0);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&verifier_key], now).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims[0].status, ClaimStatus::Verified);
+
+        let instruction = verify_instruction(program_id, dao_key, verifier_key.pubkey(), 1);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&verifier_key], now).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims[1].status, ClaimStatus::Pending);
+    }
+
+    #[tokio::test]
+    async fn test_verifier_without_stake_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let verifier_key = Keypair::new();
+
+        let mut dao = dao_state(
+            vec![verifier(verifier_key.pubkey(), 0)],
+            vec![pending_claim(0, Pubkey::new_unique(), Pubkey::new_unique())],
+        );
+        dao.verifier_stake_requirement = 1_000_000;
+
+        let instruction = verify_instruction(program_id, dao_key, verifier_key.pubkey(), 0);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&verifier_key], 0).await;
+        assert_eq!(result.unwrap_err().unwrap(), TransactionError::InstructionError(0, InstructionError::Custom(DaoError::InsufficientStake as u32)));
+        assert!(dao.claims[0].verifiers.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_verifier_allowed_after_staking() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let verifier_key = Keypair::new();
+
+        let mut dao = dao_state(
+            vec![verifier(verifier_key.pubkey(), 0)],
+            vec![pending_claim(0, Pubkey::new_unique(), Pubkey::new_unique())],
+        );
+        dao.verifier_stake_requirement = 1_000_000;
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, TREASURY_FUNDS);
+        program_test.add_account(
+            verifier_key.pubkey(),
+            Account {
+                lamports: 5_000_000,
+                data: Vec::new(),
+                owner: system_program::id(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        // Stake the required amount and verify in the same transaction
+        let data = claims_data(ClaimsInstruction::DepositStake { amount: 1_000_000 });
+        let stake = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                        // DAO account
+                AccountMeta::new(verifier_key.pubkey(), true),           // Verifier staking
+                AccountMeta::new_readonly(system_program::id(), false),  // System program
+            ],
+            data,
+        };
+        let verify = verify_instruction(program_id, dao_key, verifier_key.pubkey(), 0);
+        let instructions = [stake, verify];
+        let transaction = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&payer.pubkey()),
+            &[&payer, &verifier_key],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let dao = dao.reload(&mut banks_client, program_id, dao_key, &instructions).await;
+        assert_eq!(dao.members[0].stake, 1_000_000);
+        assert_eq!(dao.claims[0].verifiers, vec![verifier_key.pubkey()]);
+    }
+
+    #[tokio::test]
+    async fn test_external_ref_persisted() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Keypair::new();
+        let external_ref = *b"0123456789abcdef";
+
+        let dao = dao_state(vec![member(claimant.pubkey(), Vec::new())], Vec::new());
+
+        let instruction = submit_instruction(program_id, dao_key, claimant.pubkey(), Pubkey::new_unique(), 1_000, None, ServiceType::Consultation, external_ref);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&claimant], 0).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims[0].external_ref, external_ref);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_external_ref_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Keypair::new();
+
+        let existing = pending_claim(0, claimant.pubkey(), Pubkey::new_unique());
+        let external_ref = existing.external_ref;
+        let dao = dao_state(vec![member(claimant.pubkey(), Vec::new())], vec![existing]);
+
+        let instruction = submit_instruction(program_id, dao_key, claimant.pubkey(), Pubkey::new_unique(), 1_000, None, ServiceType::Consultation, external_ref);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&claimant], 0).await;
+        assert!(result.is_err());
+        assert_eq!(dao.claims.len(), 1);
+    }
+
+    // `instruction`, a claim submission, carrying `expected` as the member's nonce
+    fn with_nonce(mut instruction: Instruction, expected: u64) -> Instruction {
+        let mut decoded = DaoInstruction::try_from_slice(&instruction.data).unwrap();
+        if let DaoInstruction::Claims(ClaimsInstruction::SubmitClaim { nonce, .. }) = &mut decoded {
+            *nonce = expected;
+        }
+        instruction.data = decoded.try_to_vec().unwrap();
+        instruction
+    }
+
+    #[tokio::test]
+    async fn test_replayed_submission_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Keypair::new();
+
+        let dao = dao_state(vec![member(claimant.pubkey(), Vec::new())], Vec::new());
+        let instruction = submit_instruction(program_id, dao_key, claimant.pubkey(), Pubkey::new_unique(), 1_000, None, ServiceType::Consultation, [1; 16]);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction.clone(), &[&claimant], 0).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.members[0].nonce, 1);
+
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&claimant], 0).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::BadNonce as u32)),
+        );
+        assert_eq!(dao.claims.len(), 1);
+        assert_eq!(dao.members[0].nonce, 1);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_claim_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Keypair::new();
+        let provider = Pubkey::new_unique();
+
+        let dao = dao_state(vec![member(claimant.pubkey(), Vec::new())], Vec::new());
+        let instruction = submit_instruction(program_id, dao_key, claimant.pubkey(), provider, 1_000, None, ServiceType::Consultation, [1; 16]);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&claimant], 0).await;
+        assert!(result.is_ok());
+
+        // The same service resubmitted under a new reference is a duplicate of the standing claim 0
+        let mut instruction = as_claim(with_nonce(submit_instruction(program_id, dao_key, claimant.pubkey(), provider, 1_000, None, ServiceType::Consultation, [2; 16]), 1), 1);
+        instruction.accounts.push(claim_meta(program_id, dao_key, 0));
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&claimant], 0).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::DuplicateClaim as u32)),
+        );
+
+        // A different amount is a separate claim
+        let instruction = as_claim(with_nonce(submit_instruction(program_id, dao_key, claimant.pubkey(), provider, 2_000, None, ServiceType::Consultation, [2; 16]), 1), 1);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&claimant], 0).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims.len(), 2);
+        assert_eq!(dao.claims_count, 2);
+    }
+
+    fn withdraw_instruction(program_id: Pubkey, dao_key: Pubkey, member: Pubkey, claim_id: u64) -> Instruction {
+        Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),          // DAO account
+                AccountMeta::new_readonly(member, true),   // Claimant withdrawing
+                claim_meta(program_id, dao_key, claim_id), // Claim account
+            ],
+            data: claims_data(ClaimsInstruction::WithdrawClaim { claim_id }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_claimant_withdraws_pending_claim() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Keypair::new();
+        let dao = dao_state(vec![member(claimant.pubkey(), Vec::new())], vec![pending_claim(0, claimant.pubkey(), Pubkey::new_unique())]);
+
+        let instruction = withdraw_instruction(program_id, dao_key, claimant.pubkey(), 0);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&claimant], 0).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims[0].status, ClaimStatus::Withdrawn);
+    }
+
+    #[tokio::test]
+    async fn test_claim_withdrawn_only_by_claimant() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Pubkey::new_unique();
+        let other = Keypair::new();
+        let dao = dao_state(vec![member(claimant, Vec::new()), member(other.pubkey(), Vec::new())], vec![pending_claim(0, claimant, Pubkey::new_unique())]);
+
+        let instruction = withdraw_instruction(program_id, dao_key, other.pubkey(), 0);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&other], 0).await;
+        assert_eq!(result.unwrap_err().unwrap(), TransactionError::InstructionError(0, InstructionError::Custom(DaoError::Unauthorized as u32)));
+        assert_eq!(dao.claims[0].status, ClaimStatus::Pending);
+    }
+
+    #[tokio::test]
+    async fn test_verified_claim_cannot_be_withdrawn() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Keypair::new();
+        let mut claim = pending_claim(0, claimant.pubkey(), Pubkey::new_unique());
+        claim.status = ClaimStatus::Verified;
+        let dao = dao_state(vec![member(claimant.pubkey(), Vec::new())], vec![claim]);
+
+        let instruction = withdraw_instruction(program_id, dao_key, claimant.pubkey(), 0);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&claimant], 0).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::ClaimAlreadyVerified as u32)),
+        );
+        assert_eq!(dao.claims[0].status, ClaimStatus::Verified);
+    }
+
+    // DAO with a one-hour cooling window and a claim submitted at t=1000
+    fn dao_with_cooling_claim(verifier_key: Pubkey) -> Ledger {
+        let mut claim = pending_claim(0, Pubkey::new_unique(), Pubkey::new_unique());
+        claim.submitted_at = 1_000;
+        let mut dao = dao_state(vec![verifier(verifier_key, 0)], vec![claim]);
+        dao.verify_cooling_seconds = 3_600;
+        dao
+    }
+
+    #[tokio::test]
+    async fn test_verification_during_cooling_window_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let verifier_key = Keypair::new();
+        let dao = dao_with_cooling_claim(verifier_key.pubkey());
+
+        let instruction = verify_instruction(program_id, dao_key, verifier_key.pubkey(), 0);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&verifier_key], 4_599).await;
+        assert_eq!(result.unwrap_err().unwrap(), TransactionError::InstructionError(0, InstructionError::Custom(DaoError::VerificationCooling as u32)));
+        assert!(dao.claims[0].verifiers.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_verification_after_cooling_window_accepted() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let verifier_key = Keypair::new();
+        let dao = dao_with_cooling_claim(verifier_key.pubkey());
+
+        let instruction = verify_instruction(program_id, dao_key, verifier_key.pubkey(), 0);
+        let (result, _) = process_at(program_id, dao_key, &dao, instruction, &[&verifier_key], 4_600).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_provider_signed_claim_accepted() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Keypair::new();
+        let provider = Keypair::new();
+
+        let mut dao = dao_state(vec![member(claimant.pubkey(), Vec::new())], Vec::new());
+        dao.require_provider_signature = true;
+
+        let mut instruction = submit_instruction(program_id, dao_key, claimant.pubkey(), provider.pubkey(), 1_000, None, ServiceType::Consultation, [7; 16]);
+        instruction.accounts[2].is_signer = true; // Provider co-signs
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&claimant, &provider], 0).await;
+        assert!(result.is_ok());
+        assert!(dao.claims[0].provider_attested);
+    }
+
+    #[tokio::test]
+    async fn test_unsigned_provider_claim_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Keypair::new();
+
+        let mut dao = dao_state(vec![member(claimant.pubkey(), Vec::new())], Vec::new());
+        dao.require_provider_signature = true;
+
+        let instruction = submit_instruction(program_id, dao_key, claimant.pubkey(), Pubkey::new_unique(), 1_000, None, ServiceType::Consultation, [7; 16]);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&claimant], 0).await;
+        assert!(result.is_err());
+        assert!(dao.claims.is_empty());
+    }
+
+    // Resubmit a consultation claim from `member` that supersedes `prior_id`, filed as the claim after it
+    fn resubmit_instruction(program_id: Pubkey, dao_key: Pubkey, member: Pubkey, prior_id: u64) -> Instruction {
+        let data = claims_data(ClaimsInstruction::SubmitClaim {
+            amount: 1_000,
+            service_date: 0,
+            external_ref: [9; 16],
+            preauth_id: None,
+            condition: None,
+            supersedes: Some(prior_id),
+            service_type: ServiceType::Consultation,
+            nonce: 0,
+        });
+        let mut accounts = submit_accounts(program_id, dao_key, member, Pubkey::new_unique(), 0, 1_000, [9; 16]);
+        accounts.push(claim_meta(program_id, dao_key, prior_id)); // Claim being superseded
+        as_claim(Instruction { program_id, accounts, data }, prior_id + 1)
+    }
+
+    #[tokio::test]
+    async fn test_resubmission_links_prior_claim() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Keypair::new();
+
+        let dao = dao_state(
+            vec![member(claimant.pubkey(), Vec::new())],
+            vec![rejected_claim(claimant.pubkey(), Vec::new())],
+        );
+
+        let instruction = resubmit_instruction(program_id, dao_key, claimant.pubkey(), 0);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&claimant], 0).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims[1].supersedes, Some(0));
+        assert!(dao.claims[0].resubmitted);
+    }
+
+    #[tokio::test]
+    async fn test_resubmission_of_another_members_claim_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Keypair::new();
+
+        let dao = dao_state(
+            vec![member(claimant.pubkey(), Vec::new())],
+            vec![rejected_claim(Pubkey::new_unique(), Vec::new())],
+        );
+
+        let instruction = resubmit_instruction(program_id, dao_key, claimant.pubkey(), 0);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&claimant], 0).await;
+        assert!(result.is_err());
+        assert_eq!(dao.claims.len(), 1);
+    }
+
+    // A pending claim for `service_type` in a DAO where prescriptions need one verification and surgery three
+    fn dao_with_category_thresholds(service_type: ServiceType, verifiers: &[Pubkey]) -> Ledger {
+        let mut claim = pending_claim(0, Pubkey::new_unique(), Pubkey::new_unique());
+        claim.service_type = service_type;
+        let mut dao = dao_state(verifiers.iter().map(|v| verifier(*v, 0)).collect(), vec![claim]);
+        dao.category_verification_thresholds = vec![(ServiceType::Pharmacy, 1), (ServiceType::Surgery, 3)];
+        dao
+    }
+
+    #[tokio::test]
+    async fn test_prescription_verified_with_one_approval() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let verifier_key = Keypair::new();
+        let dao = dao_with_category_thresholds(ServiceType::Pharmacy, &[verifier_key.pubkey()]);
+
+        let instruction = verify_instruction(program_id, dao_key, verifier_key.pubkey(), 0);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&verifier_key], 0).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims[0].status, ClaimStatus::Verified);
+    }
+
+    #[tokio::test]
+    async fn test_surgery_needs_three_approvals() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let verifiers = [Keypair::new(), Keypair::new(), Keypair::new()];
+        let keys: Vec<Pubkey> = verifiers.iter().map(|v| v.pubkey()).collect();
+        let mut dao = dao_with_category_thresholds(ServiceType::Surgery, &keys);
+
+        for (i, verifier_key) in verifiers.iter().enumerate() {
+            let instruction = verify_instruction(program_id, dao_key, verifier_key.pubkey(), 0);
+            let (result, next) = process_at(program_id, dao_key, &dao, instruction, &[verifier_key], 0).await;
+            assert!(result.is_ok());
+            let expected = if i < 2 { ClaimStatus::Pending } else { ClaimStatus::Verified };
+            assert_eq!(next.claims[0].status, expected);
+            dao = next;
+        }
+    }
+
+    #[test]
+    fn test_uncategorized_claim_uses_default_threshold() {
+        let categories = vec![(ServiceType::Surgery, 3)];
+        let claim = pending_claim(0, Pubkey::new_unique(), Pubkey::new_unique()); // A consultation
+        assert_eq!(verification_threshold(2, None, &categories, &claim, 0), 2);
+        assert_eq!(base_verification_threshold(0), DEFAULT_VERIFICATION_THRESHOLD as usize);
+    }
+
+    fn threshold_instruction(program_id: Pubkey, dao_key: Pubkey, admin: Pubkey, threshold: u8) -> Instruction {
+        Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),       // DAO account
+                AccountMeta::new_readonly(admin, true), // Admin
+            ],
+            data: claims_data(ClaimsInstruction::SetVerificationThreshold { threshold }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_threshold_of_one_verifies_on_first_approval() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let admin = Keypair::new();
+        let verifier_key = Keypair::new();
+
+        let mut dao = dao_state(
+            vec![verifier(verifier_key.pubkey(), 0)],
+            vec![pending_claim(0, Pubkey::new_unique(), Pubkey::new_unique())],
+        );
+        dao.admin = admin.pubkey();
+
+        let instruction = threshold_instruction(program_id, dao_key, admin.pubkey(), 1);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&admin], 0).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.verification_threshold, 1);
+
+        let instruction = verify_instruction(program_id, dao_key, verifier_key.pubkey(), 0);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&verifier_key], 0).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims[0].status, ClaimStatus::Verified);
+    }
+
+    #[tokio::test]
+    async fn test_threshold_of_three_needs_three_approvals() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let admin = Keypair::new();
+        let verifiers = [Keypair::new(), Keypair::new(), Keypair::new()];
+
+        let mut dao = dao_state(
+            verifiers.iter().map(|v| verifier(v.pubkey(), 0)).collect(),
+            vec![pending_claim(0, Pubkey::new_unique(), Pubkey::new_unique())],
+        );
+        dao.admin = admin.pubkey();
+
+        // A threshold above the number of registered verifiers could never be met
+        let instruction = threshold_instruction(program_id, dao_key, admin.pubkey(), 4);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&admin], 0).await;
+        assert!(result.is_err());
+
+        let instruction = threshold_instruction(program_id, dao_key, admin.pubkey(), 3);
+        let (result, mut dao) = process_at(program_id, dao_key, &dao, instruction, &[&admin], 0).await;
+        assert!(result.is_ok());
+
+        for (i, verifier_key) in verifiers.iter().enumerate() {
+            let instruction = verify_instruction(program_id, dao_key, verifier_key.pubkey(), 0);
+            let (result, next) = process_at(program_id, dao_key, &dao, instruction, &[verifier_key], 0).await;
+            assert!(result.is_ok());
+            let expected = if i < 2 { ClaimStatus::Pending } else { ClaimStatus::Verified };
+            assert_eq!(next.claims[0].status, expected);
+            dao = next;
+        }
+    }
+
+    // DAO capping members at two claims a year, with `claimant` having submitted two claims at t=1000 and t=2000
+    fn dao_with_annual_cap(claimant: Pubkey) -> Ledger {
+        let first = Claim { submitted_at: 1_000, ..pending_claim(0, claimant, Pubkey::new_unique()) };
+        let second = Claim { submitted_at: 2_000, ..pending_claim(1, claimant, Pubkey::new_unique()) };
+        let mut dao = dao_state(vec![member(claimant, Vec::new())], vec![first, second]);
+        dao.max_claims_per_member_per_year = 2;
+        dao
+    }
+
+    #[tokio::test]
+    async fn test_claim_past_annual_cap_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Keypair::new();
+        let dao = dao_with_annual_cap(claimant.pubkey());
+
+        let instruction = as_claim(submit_instruction(program_id, dao_key, claimant.pubkey(), Pubkey::new_unique(), 1_000, None, ServiceType::Consultation, [9; 16]), 2);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&claimant], 3_000).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::AnnualClaimCapReached as u32)),
+        );
+        assert_eq!(dao.claims.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_annual_cap_resets_after_window() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Keypair::new();
+        let dao = dao_with_annual_cap(claimant.pubkey());
+
+        // A year after the first claim only the second one still counts against the cap
+        let instruction = as_claim(submit_instruction(program_id, dao_key, claimant.pubkey(), Pubkey::new_unique(), 1_000, None, ServiceType::Consultation, [9; 16]), 2);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&claimant], 1_000 + CLAIM_YEAR_SECONDS).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_verifier_at_pairing_limit_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let verifier_key = Keypair::new();
+        let provider = Pubkey::new_unique();
+
+        let mut dao = dao_state(
+            vec![verifier(verifier_key.pubkey(), 0)],
+            vec![pending_claim(0, Pubkey::new_unique(), provider)],
+        );
+        dao.max_verifier_provider_pairings = 2;
+        dao.verifier_pairings = vec![VerifierPairing { verifier: verifier_key.pubkey(), provider, count: 2 }];
+
+        let instruction = verify_instruction(program_id, dao_key, verifier_key.pubkey(), 0);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&verifier_key], 0).await;
+        assert_eq!(result.unwrap_err().unwrap(), TransactionError::InstructionError(0, InstructionError::Custom(DaoError::VerifierPairingLimit as u32)));
+        assert!(dao.claims[0].verifiers.is_empty());
+    }
+
+    #[test]
+    fn test_appeal_skips_verifiers_at_pairing_limit() {
+        let claimant = Pubkey::new_unique();
+        let familiar = Pubkey::new_unique();
+        let seasoned = Pubkey::new_unique();
+        let fresh = Pubkey::new_unique();
+        let members = vec![verifier(familiar, 0), verifier(seasoned, 0), verifier(fresh, 0)];
+        let claim = rejected_claim(claimant, Vec::new());
+        let pairings = vec![
+            VerifierPairing { verifier: familiar, provider: claim.provider, count: 3 },
+            VerifierPairing { verifier: seasoned, provider: claim.provider, count: 1 },
+        ];
+
+        // The verifier at the limit is dropped and the least familiar verifier is assigned first
+        assert_eq!(appeal_verifiers(&members, &claim, &[], &pairings, 3), vec![fresh, seasoned]);
+        // Without a limit every verifier stays eligible
+        assert_eq!(appeal_verifiers(&members, &claim, &[], &pairings, 0), vec![fresh, seasoned, familiar]);
+    }
+
+    // DAO holding back 10% of payouts for a day, with a verified claim from `claimant` at `provider`
+    fn dao_with_holdback(claimant: Pubkey, provider: Pubkey, dao_key: Pubkey) -> Ledger {
+        let mut claim = pending_claim(0, claimant, provider);
+        claim.status = ClaimStatus::Verified;
+        let mut dao = dao_state(vec![member(claimant, Vec::new())], vec![claim]);
+        fund_treasury(&mut dao, dao_key, claimant);
+        dao.holdback_bps = 1_000;
+        dao.holdback_timeout_secs = 86_400;
+        dao
+    }
+
+    fn release_instruction(program_id: Pubkey, dao_key: Pubkey, member: Pubkey, provider: Pubkey, provider_signs: bool) -> Instruction {
+        let data = claims_data(ClaimsInstruction::ReleaseHoldback { claim_id: 0 });
+        Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                       // DAO account, also the treasury
+                AccountMeta::new_readonly(provider, provider_signs),    // Provider confirming the service
+                claim_meta(program_id, dao_key, 0),                     // Claim account
+                AccountMeta::new(dao_key, false),                       // Treasury
+                AccountMeta::new(member, false),                        // Member receiving the holdback
+                AccountMeta::new_readonly(system_program::id(), false), // System program
+            ],
+            data,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_payout_withholds_holdback() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Pubkey::new_unique();
+        let mut dao = dao_with_holdback(claimant, Pubkey::new_unique(), dao_key);
+        let admin = Keypair::new();
+        dao.admin = admin.pubkey();
+
+        let payout = payout_instruction(program_id, dao_key, admin.pubkey(), claimant, 0);
+        let (result, dao) = process_at_funded(program_id, dao_key, &dao, &[claimant], payout, &[&admin], 1_000).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims[0].status, ClaimStatus::Paid);
+        assert_eq!(dao.claims[0].holdback, 100_000);
+        assert_eq!(dao.claims[0].holdback_release_at, 1_000 + 86_400);
+    }
+
+    #[tokio::test]
+    async fn test_holdback_released_on_provider_confirmation() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Pubkey::new_unique();
+        let provider = Keypair::new();
+        let mut dao = dao_with_holdback(claimant, provider.pubkey(), dao_key);
+        dao.claims[0].status = ClaimStatus::Paid;
+        dao.claims[0].holdback = 100_000;
+        dao.claims[0].holdback_release_at = 86_400;
+
+        let release = release_instruction(program_id, dao_key, claimant, provider.pubkey(), true);
+        let (result, dao) = process_at_funded(program_id, dao_key, &dao, &[claimant], release, &[&provider], 1_000).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims[0].holdback, 0);
+    }
+
+    #[tokio::test]
+    async fn test_holdback_released_after_timeout() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Pubkey::new_unique();
+        let provider = Pubkey::new_unique();
+        let mut dao = dao_with_holdback(claimant, provider, dao_key);
+        dao.claims[0].status = ClaimStatus::Paid;
+        dao.claims[0].holdback = 100_000;
+        dao.claims[0].holdback_release_at = 86_400;
+
+        // Without the provider's signature the holdback stays until the timeout
+        let release = release_instruction(program_id, dao_key, claimant, provider, false);
+        let (result, _) = process_at_funded(program_id, dao_key, &dao, &[claimant], release.clone(), &[], 86_399).await;
+        assert!(result.is_err());
+
+        let (result, dao) = process_at_funded(program_id, dao_key, &dao, &[claimant], release, &[], 86_400).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims[0].holdback, 0);
+    }
+
+    #[test]
+    fn test_outlier_claim_scores_higher_than_typical() {
+        let claimant = Pubkey::new_unique();
+        let provider = Pubkey::new_unique();
+        let flagged_provider = Pubkey::new_unique();
+        let mut claims: Vec<Claim> = (0..3).map(|id| pending_claim(id, claimant, provider)).collect();
+        claims[1].service_type = ServiceType::Other(1); // Imaging
+        claims[2].service_type = ServiceType::Other(2); // Lab work
+        let mut rejected = pending_claim(3, Pubkey::new_unique(), flagged_provider);
+        rejected.status = ClaimStatus::Rejected;
+        claims.push(rejected);
+        let dao = dao_state(vec![member(claimant, Vec::new())], claims).tallied_dao();
+        let history = &dao.members[0].claim_history;
+        let provider_claims = |provider: Pubkey| dao.provider_claims.iter().find(|p| p.provider == provider);
+
+        // In line with the member's 1M average, a service type seen once before, at a provider with a clean record
+        let typical = anomaly_score(history, provider_claims(provider), 1_000_000, ServiceType::Other(1));
+        // Five times the average, at a provider whose only past claim was rejected
+        let outlier = anomaly_score(history, provider_claims(flagged_provider), 5_000_000, ServiceType::Other(1));
+
+        assert_eq!(typical, 10);
+        assert_eq!(outlier, 80);
+        assert!(outlier > typical);
+    }
+
+    #[tokio::test]
+    async fn test_old_terminal_claims_archived() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Pubkey::new_unique();
+
+        let mut claims: Vec<Claim> = (0..4).map(|id| pending_claim(id, claimant, Pubkey::new_unique())).collect();
+        claims[0].status = ClaimStatus::Paid; // Old and paid: archived
+        claims[1].status = ClaimStatus::Rejected; // Old and rejected: archived
+        claims[2].submitted_at = 0; // Old but still pending: kept
+        claims[3].status = ClaimStatus::Paid;
+        claims[3].submitted_at = 9_000; // Paid but within retention: kept
+        let mut dao = dao_state(vec![member(claimant, Vec::new())], claims.clone());
+        dao.claim_retention_seconds = 5_000;
+        let history = dao.tallied_dao().members[0].claim_history.clone();
+
+        let mut accounts = vec![AccountMeta::new(dao_key, false)]; // DAO account
+        accounts.extend((0..4).map(|id| claim_meta(program_id, dao_key, id)));
+        let archive = Instruction { program_id, accounts, data: claims_data(ClaimsInstruction::ArchiveClaims) };
+        let (result, dao) = process_at(program_id, dao_key, &dao, archive, &[], 10_000).await;
+        assert!(result.is_ok());
+
+        let ids: Vec<u64> = dao.claims.iter().map(|c| c.claim_id).collect();
+        assert_eq!(ids, vec![2, 3]);
+        assert_eq!(dao.archived_claim_count, 2);
+        assert_eq!(dao.claims_count, 4); // Archived ids are never handed out again
+        // The member's record still counts the archived claims, including the rejection
+        assert_eq!(dao.members[0].claim_history, history);
+        assert_eq!(dao.members[0].claim_history.rejected, 1);
+
+        // The root commits to exactly the archived claims, chained onto the empty root
+        let leaves = [hash(&claims[0].try_to_vec().unwrap()).to_bytes(), hash(&claims[1].try_to_vec().unwrap()).to_bytes()];
+        assert_eq!(dao.archived_claims_root, hashv(&[&[0u8; 32], &merkle_root(&leaves)]).to_bytes());
+    }
+
+    #[tokio::test]
+    async fn test_archive_root_chains_batches() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Pubkey::new_unique();
+
+        let mut claim = pending_claim(5, claimant, Pubkey::new_unique());
+        claim.status = ClaimStatus::Paid;
+        let mut dao = dao_state(vec![member(claimant, Vec::new())], vec![claim.clone()]);
+        dao.claim_retention_seconds = 1;
+        dao.archived_claims_root = [7u8; 32]; // Root left by an earlier batch
+        dao.archived_claim_count = 5;
+
+        let archive = Instruction {
+            program_id,
+            accounts: vec![AccountMeta::new(dao_key, false), claim_meta(program_id, dao_key, 5)], // DAO account, claim account
+            data: claims_data(ClaimsInstruction::ArchiveClaims),
+        };
+        let (result, dao) = process_at(program_id, dao_key, &dao, archive, &[], 10).await;
+        assert!(result.is_ok());
+        assert!(dao.claims.is_empty());
+        assert_eq!(dao.archived_claim_count, 6);
+        let leaf = hash(&claim.try_to_vec().unwrap()).to_bytes();
+        assert_eq!(dao.archived_claims_root, hashv(&[&[7u8; 32], &merkle_root(&[leaf])]).to_bytes());
+    }
+
+    #[tokio::test]
+    async fn test_payout_blocked_until_minimum_reviews() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Pubkey::new_unique();
+        let third_verifier = Keypair::new();
+
+        // Two approvals meet the threshold, but the DAO wants three verifiers to look at every claim
+        let mut claim = pending_claim(0, claimant, Pubkey::new_unique());
+        claim.status = ClaimStatus::Verified;
+        claim.verifiers = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut dao = dao_state(vec![member(claimant, Vec::new()), verifier(third_verifier.pubkey(), 0)], vec![claim]);
+        fund_treasury(&mut dao, dao_key, claimant);
+        dao.min_verifiers_reviewed = 3;
+        let admin = Keypair::new();
+        dao.admin = admin.pubkey();
+
+        let payout = payout_instruction(program_id, dao_key, admin.pubkey(), claimant, 0);
+        let (result, dao) = process_at(program_id, dao_key, &dao, payout.clone(), &[&admin], 0).await;
+        assert!(result.is_err());
+        assert_eq!(dao.claims[0].status, ClaimStatus::Verified);
+
+        // A third verifier declining still counts as a review and unblocks the payout
+        let mut review = verify_instruction(program_id, dao_key, third_verifier.pubkey(), 0);
+        review.data = claims_data(ClaimsInstruction::VerifyClaim { claim_id: 0, approve: false });
+        let (result, dao) = process_at(program_id, dao_key, &dao, review, &[&third_verifier], 0).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims[0].rejections, vec![third_verifier.pubkey()]);
+
+        let (result, dao) = process_at(program_id, dao_key, &dao, payout, &[&admin], 0).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims[0].status, ClaimStatus::Paid);
+    }
+
+    // DAO auto-approving claims under 10_000 lamports for members with one paid claim, which `claimant` has
+    fn dao_with_micro_claims(claimant: Pubkey) -> Ledger {
+        let mut history = pending_claim(0, claimant, Pubkey::new_unique());
+        history.status = ClaimStatus::Paid;
+        let mut dao = dao_state(vec![member(claimant, Vec::new())], vec![history]);
+        dao.micro_claim_threshold = 10_000;
+        dao.micro_claim_min_paid_claims = 1;
+        dao
+    }
+
+    #[tokio::test]
+    async fn test_micro_claim_auto_approved() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Keypair::new();
+        let mut dao = dao_with_micro_claims(claimant.pubkey());
+        dao.micro_claim_auto_pay = true;
+        fund_treasury(&mut dao, dao_key, claimant.pubkey());
+
+        // Auto-paid claims are followed by the payout accounts
+        let mut instruction = as_claim(submit_instruction(program_id, dao_key, claimant.pubkey(), Pubkey::new_unique(), 9_999, None, ServiceType::Consultation, [3; 16]), 1);
+        instruction.accounts.extend([
+            AccountMeta::new(dao_key, false),                        // Treasury
+            AccountMeta::new(claimant.pubkey(), false),              // Member receiving the payout
+            AccountMeta::new_readonly(system_program::id(), false),  // System program
+        ]);
+        let (result, dao) = process_at_funded(program_id, dao_key, &dao, &[claimant.pubkey()], instruction, &[&claimant], 0).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims[1].status, ClaimStatus::Paid);
+        assert!(dao.claims[1].verifiers.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_claim_at_micro_threshold_takes_normal_path() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Keypair::new();
+        let dao = dao_with_micro_claims(claimant.pubkey());
+
+        let instruction = as_claim(submit_instruction(program_id, dao_key, claimant.pubkey(), Pubkey::new_unique(), 10_000, None, ServiceType::Consultation, [3; 16]), 1);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&claimant], 0).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims[1].status, ClaimStatus::Pending);
+    }
+
+    #[test]
+    fn test_rejected_history_loses_good_standing() {
+        let claimant = Pubkey::new_unique();
+        let mut claims: Vec<Claim> = (0..2).map(|id| pending_claim(id, claimant, Pubkey::new_unique())).collect();
+        let standing = |claims: &[Claim]| {
+            let dao = dao_state(vec![member(claimant, Vec::new())], claims.to_vec()).tallied_dao();
+            in_good_standing(&dao.members[0].claim_history, 1)
+        };
+        claims[0].status = ClaimStatus::Paid;
+        assert!(standing(&claims));
+
+        claims[1].status = ClaimStatus::Rejected;
+        assert!(!standing(&claims));
+    }
+
+    fn confirm_fraud_instruction(program_id: Pubkey, dao_key: Pubkey, admin: Pubkey, claim_id: u64) -> Instruction {
+        let data = claims_data(ClaimsInstruction::ConfirmFraud { claim_id });
+        Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),          // DAO account
+                AccountMeta::new_readonly(admin, true),    // Admin confirming the fraud
+                claim_meta(program_id, dao_key, claim_id), // Claim account
+            ],
+            data,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fraud_strikes_escalate_to_removal() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let admin = Keypair::new();
+        let claimant = Keypair::new();
+
+        let claims = (0..3).map(|id| pending_claim(id, claimant.pubkey(), Pubkey::new_unique())).collect();
+        let mut dao = dao_state(vec![member(claimant.pubkey(), Vec::new())], claims);
+        dao.admin = admin.pubkey();
+        dao.fraud_suspension_strikes = 2;
+        dao.fraud_removal_strikes = 3;
+
+        // First strike is a warning only
+        let (result, dao) = process_at(program_id, dao_key, &dao, confirm_fraud_instruction(program_id, dao_key, admin.pubkey(), 0), &[&admin], 0).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.members[0].fraud_strikes, 1);
+        assert!(!dao.members[0].suspended);
+
+        // Second strike suspends the member, who can no longer submit claims
+        let (result, dao) = process_at(program_id, dao_key, &dao, confirm_fraud_instruction(program_id, dao_key, admin.pubkey(), 1), &[&admin], 0).await;
+        assert!(result.is_ok());
+        assert!(dao.members[0].suspended);
+        let submit = as_claim(submit_instruction(program_id, dao_key, claimant.pubkey(), Pubkey::new_unique(), 1_000, None, ServiceType::Consultation, [8; 16]), 3);
+        let (result, _) = process_at(program_id, dao_key, &dao, submit, &[&claimant], 0).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::MemberSuspended as u32)),
+        );
+
+        // Third strike removes the member
+        let (result, dao) = process_at(program_id, dao_key, &dao, confirm_fraud_instruction(program_id, dao_key, admin.pubkey(), 2), &[&admin], 0).await;
+        assert!(result.is_ok());
+        assert!(dao.members.is_empty());
+    }
+
+    // DAO requiring acknowledgment within an hour for payouts of 500_000 lamports or more, with a verified 1M claim
+    fn dao_with_ack_threshold(claimant: Pubkey, dao_key: Pubkey) -> Ledger {
+        let mut claim = pending_claim(0, claimant, Pubkey::new_unique());
+        claim.status = ClaimStatus::Verified;
+        let mut dao = dao_state(vec![member(claimant, Vec::new())], vec![claim]);
+        fund_treasury(&mut dao, dao_key, claimant);
+        dao.ack_required_above = 500_000;
+        dao.ack_timeout_secs = 3_600;
+        dao
+    }
+
+    fn ack_instruction(program_id: Pubkey, dao_key: Pubkey, member: Pubkey) -> Instruction {
+        let data = claims_data(ClaimsInstruction::AcknowledgePayout { claim_id: 0 });
+        Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),        // DAO account
+                AccountMeta::new_readonly(member, true), // Claimant acknowledging the payout
+                claim_meta(program_id, dao_key, 0),      // Claim account
+            ],
+            data,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_high_value_claim_awaits_ack() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Keypair::new();
+        let mut dao = dao_with_ack_threshold(claimant.pubkey(), dao_key);
+        let admin = Keypair::new();
+        dao.admin = admin.pubkey();
+
+        let payout = payout_instruction(program_id, dao_key, admin.pubkey(), claimant.pubkey(), 0);
+        let (result, dao) = process_at(program_id, dao_key, &dao, payout, &[&admin], 1_000).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims[0].status, ClaimStatus::PayoutPendingAck);
+        assert_eq!(dao.claims[0].ack_requested_at, 1_000);
+
+        // Asking again before the member responds doesn't move funds
+        let payout = payout_instruction(program_id, dao_key, admin.pubkey(), claimant.pubkey(), 0);
+        let (result, dao) = process_at(program_id, dao_key, &dao, payout, &[&admin], 2_000).await;
+        assert!(result.is_err());
+        assert_eq!(dao.claims[0].status, ClaimStatus::PayoutPendingAck);
+    }
+
+    #[tokio::test]
+    async fn test_claim_paid_after_ack() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Keypair::new();
+        let mut dao = dao_with_ack_threshold(claimant.pubkey(), dao_key);
+        dao.claims[0].status = ClaimStatus::PayoutPendingAck;
+        dao.claims[0].ack_requested_at = 1_000;
+        let admin = Keypair::new();
+        dao.admin = admin.pubkey();
+
+        let ack = ack_instruction(program_id, dao_key, claimant.pubkey());
+        let (result, dao) = process_at(program_id, dao_key, &dao, ack, &[&claimant], 1_500).await;
+        assert!(result.is_ok());
+        assert!(dao.claims[0].acknowledged);
+
+        let payout = payout_instruction(program_id, dao_key, admin.pubkey(), claimant.pubkey(), 0);
+        let (result, dao) = process_at(program_id, dao_key, &dao, payout, &[&admin], 2_000).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims[0].status, ClaimStatus::Paid);
+    }
+
+    #[tokio::test]
+    async fn test_expired_ack_request_reissued() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Keypair::new();
+        let mut dao = dao_with_ack_threshold(claimant.pubkey(), dao_key);
+        dao.claims[0].status = ClaimStatus::PayoutPendingAck;
+        dao.claims[0].ack_requested_at = 1_000;
+        let admin = Keypair::new();
+        dao.admin = admin.pubkey();
+
+        // Acknowledging after the window closes is refused
+        let ack = ack_instruction(program_id, dao_key, claimant.pubkey());
+        let (result, dao) = process_at(program_id, dao_key, &dao, ack, &[&claimant], 4_601).await;
+        assert!(result.is_err());
+        assert!(!dao.claims[0].acknowledged);
+
+        // The next payout attempt reissues the request instead of paying
+        let payout = payout_instruction(program_id, dao_key, admin.pubkey(), claimant.pubkey(), 0);
+        let (result, dao) = process_at(program_id, dao_key, &dao, payout, &[&admin], 4_601).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims[0].status, ClaimStatus::PayoutPendingAck);
+        assert_eq!(dao.claims[0].ack_requested_at, 4_601);
+    }
+
+    fn reject_instruction(program_id: Pubkey, dao_key: Pubkey, verifier: Pubkey, reason: &str) -> Instruction {
+        let data = claims_data(ClaimsInstruction::RejectClaim { claim_id: 0, reason: Some(reason.to_string()) });
+        Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),          // DAO account
+                AccountMeta::new_readonly(verifier, true), // Verifier rejecting the claim
+                claim_meta(program_id, dao_key, 0),        // Claim account
+            ],
+            data,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verifier_rejects_pending_claim() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Pubkey::new_unique();
+        let verifier_key = Keypair::new();
+
+        let dao = dao_state(
+            vec![member(claimant, Vec::new()), verifier(verifier_key.pubkey(), 0)],
+            vec![pending_claim(0, claimant, Pubkey::new_unique())],
+        );
+
+        let reject = reject_instruction(program_id, dao_key, verifier_key.pubkey(), "service not covered");
+        let (result, dao) = process_at(program_id, dao_key, &dao, reject, &[&verifier_key], 0).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims[0].status, ClaimStatus::Rejected);
+        assert_eq!(dao.claims[0].rejection_reason.as_deref(), Some("service not covered"));
+    }
+
+    #[tokio::test]
+    async fn test_paid_claim_cannot_be_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Pubkey::new_unique();
+        let verifier_key = Keypair::new();
+
+        let mut claim = pending_claim(0, claimant, Pubkey::new_unique());
+        claim.status = ClaimStatus::Paid;
+        let dao = dao_state(vec![member(claimant, Vec::new()), verifier(verifier_key.pubkey(), 0)], vec![claim]);
+
+        let reject = reject_instruction(program_id, dao_key, verifier_key.pubkey(), "duplicate billing");
+        let (result, dao) = process_at(program_id, dao_key, &dao, reject, &[&verifier_key], 0).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::ClaimAlreadyPaid as u32)),
+        );
+        assert_eq!(dao.claims[0].status, ClaimStatus::Paid);
+        assert_eq!(dao.claims[0].rejection_reason, None);
+    }
+
+    #[tokio::test]
+    async fn test_only_verifiers_can_verify() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let plain_member = Keypair::new();
+        let verifier_key = Keypair::new();
+
+        let dao = dao_state(
+            vec![member(plain_member.pubkey(), Vec::new()), verifier(verifier_key.pubkey(), 0)],
+            vec![pending_claim(0, Pubkey::new_unique(), Pubkey::new_unique())],
+        );
+
+        let instruction = verify_instruction(program_id, dao_key, plain_member.pubkey(), 0);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&plain_member], 0).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::Unauthorized as u32)),
+        );
+        assert!(dao.claims[0].verifiers.is_empty());
+
+        let instruction = verify_instruction(program_id, dao_key, verifier_key.pubkey(), 0);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&verifier_key], 0).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims[0].verifiers, vec![verifier_key.pubkey()]);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_verification_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let verifier_key = Keypair::new();
+
+        let mut claim = pending_claim(0, Pubkey::new_unique(), Pubkey::new_unique());
+        claim.verifiers = vec![verifier_key.pubkey()];
+        let dao = dao_state(vec![verifier(verifier_key.pubkey(), 0)], vec![claim]);
+
+        let instruction = verify_instruction(program_id, dao_key, verifier_key.pubkey(), 0);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&verifier_key], 0).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::DuplicateVote as u32)),
+        );
+        assert_eq!(dao.claims[0].verifiers, vec![verifier_key.pubkey()]);
+    }
+
+    // A consultation claim from `member` for a service on `service_date`
+    fn dated_submit_instruction(program_id: Pubkey, dao_key: Pubkey, member: Pubkey, service_date: i64) -> Instruction {
+        let data = claims_data(ClaimsInstruction::SubmitClaim {
+            amount: 1_000,
+            service_date,
+            external_ref: [7; 16],
+            preauth_id: None,
+            condition: None,
+            supersedes: None,
+            service_type: ServiceType::Consultation,
+            nonce: 0,
+        });
+        Instruction { program_id, accounts: submit_accounts(program_id, dao_key, member, Pubkey::new_unique(), service_date, 1_000, [7; 16]), data }
+    }
+
+    // DAO accepting claims for services up to 30 days old, with `claimant` as a member
+    fn dao_with_claim_window(claimant: Pubkey) -> Ledger {
+        let mut dao = dao_state(vec![member(claimant, Vec::new())], Vec::new());
+        dao.claim_window_secs = 30 * 86_400;
+        dao
+    }
+
+    #[tokio::test]
+    async fn test_claim_inside_window_accepted() {
+        let (program_id, dao_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let claimant = Keypair::new();
+        let now = 100 * 86_400;
+
+        let instruction = dated_submit_instruction(program_id, dao_key, claimant.pubkey(), now - 29 * 86_400);
+        let (result, dao) = process_at(program_id, dao_key, &dao_with_claim_window(claimant.pubkey()), instruction, &[&claimant], now).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_claim_past_window_rejected() {
+        let (program_id, dao_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let claimant = Keypair::new();
+        let now = 100 * 86_400;
+
+        let instruction = dated_submit_instruction(program_id, dao_key, claimant.pubkey(), now - 31 * 86_400);
+        let (result, dao) = process_at(program_id, dao_key, &dao_with_claim_window(claimant.pubkey()), instruction, &[&claimant], now).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::ClaimWindowExpired as u32)),
+        );
+        assert!(dao.claims.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_future_service_date_rejected() {
+        let (program_id, dao_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let claimant = Keypair::new();
+        let now = 100 * 86_400;
+
+        let instruction = dated_submit_instruction(program_id, dao_key, claimant.pubkey(), now + 1);
+        let (result, dao) = process_at(program_id, dao_key, &dao_with_claim_window(claimant.pubkey()), instruction, &[&claimant], now).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::ClaimWindowExpired as u32)),
+        );
+        assert!(dao.claims.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_stale_pending_claims_expired() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Pubkey::new_unique();
+
+        let mut claims: Vec<Claim> = (0..3).map(|id| pending_claim(id, claimant, Pubkey::new_unique())).collect();
+        claims[1].submitted_at = 5 * 86_400; // Submitted recently: kept
+        claims[2].status = ClaimStatus::Verified; // Old but no longer pending: kept
+        let mut dao = dao_state(vec![member(claimant, Vec::new())], claims);
+        dao.pending_ttl_secs = 3 * 86_400;
+
+        let mut accounts = vec![AccountMeta::new(dao_key, false)]; // DAO account
+        accounts.extend((0..3).map(|id| claim_meta(program_id, dao_key, id)));
+        let expire = Instruction { program_id, accounts, data: claims_data(ClaimsInstruction::ExpireClaims) };
+        let (result, dao) = process_at(program_id, dao_key, &dao, expire, &[], 6 * 86_400).await;
+        assert!(result.is_ok());
+
+        assert_eq!(dao.claims[0].status, ClaimStatus::Rejected);
+        assert_eq!(dao.claims[0].rejection_reason.as_deref(), Some("expired"));
+        assert_eq!(dao.claims[1].status, ClaimStatus::Pending);
+        assert_eq!(dao.claims[2].status, ClaimStatus::Verified);
+    }
+
+    #[tokio::test]
+    async fn test_expiry_processes_bounded_batch() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Pubkey::new_unique();
+
+        let claims = (0..=EXPIRY_BATCH_SIZE as u64).map(|id| pending_claim(id, claimant, Pubkey::new_unique())).collect();
+        let mut dao = dao_state(vec![member(claimant, Vec::new())], claims);
+        dao.pending_ttl_secs = 86_400;
+
+        // One call reads a full batch of the listed claims; the next picks up the remainder
+        let expire = |ids: std::ops::RangeInclusive<u64>| {
+            let mut accounts = vec![AccountMeta::new(dao_key, false)]; // DAO account
+            accounts.extend(ids.map(|id| claim_meta(program_id, dao_key, id)));
+            Instruction { program_id, accounts, data: claims_data(ClaimsInstruction::ExpireClaims) }
+        };
+        let (result, dao) = process_at(program_id, dao_key, &dao, expire(0..=EXPIRY_BATCH_SIZE as u64), &[], 2 * 86_400).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims.iter().filter(|c| c.status == ClaimStatus::Pending).count(), 1);
+
+        let (result, dao) = process_at(program_id, dao_key, &dao, expire(EXPIRY_BATCH_SIZE as u64..=EXPIRY_BATCH_SIZE as u64), &[], 2 * 86_400).await;
+        assert!(result.is_ok());
+        assert!(dao.claims.iter().all(|c| c.status == ClaimStatus::Rejected));
+    }
+
+    fn batch_verify_instruction(program_id: Pubkey, dao_key: Pubkey, verifier: Pubkey, claim_ids: Vec<u64>) -> Instruction {
+        let mut accounts = vec![
+            AccountMeta::new(dao_key, false),          // DAO account
+            AccountMeta::new_readonly(verifier, true), // Verifier
+        ];
+        accounts.extend(claim_ids.iter().map(|id| claim_meta(program_id, dao_key, *id))); // One claim account per id
+        Instruction { program_id, accounts, data: claims_data(ClaimsInstruction::BatchVerify { claim_ids }) }
+    }
+
+    #[tokio::test]
+    async fn test_batch_verify_skips_claims_it_cannot_approve() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let verifier_key = Keypair::new();
+        let claimant = Pubkey::new_unique();
+
+        let mut verified = pending_claim(1, claimant, Pubkey::new_unique());
+        verified.status = ClaimStatus::Verified;
+        let mut reviewed = pending_claim(2, claimant, Pubkey::new_unique());
+        reviewed.verifiers = vec![verifier_key.pubkey()];
+        let claims = vec![pending_claim(0, claimant, Pubkey::new_unique()), verified, reviewed, pending_claim(3, claimant, Pubkey::new_unique())];
+        let dao = dao_state(vec![member(claimant, Vec::new()), verifier(verifier_key.pubkey(), 0)], claims);
+
+        // Already verified, already reviewed by this verifier, and never submitted are all skipped
+        let instruction = batch_verify_instruction(program_id, dao_key, verifier_key.pubkey(), vec![0, 1, 2, 9, 3]);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&verifier_key], 0).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims[0].verifiers, vec![verifier_key.pubkey()]);
+        assert!(dao.claims[1].verifiers.is_empty());
+        assert_eq!(dao.claims[2].verifiers, vec![verifier_key.pubkey()]);
+        assert_eq!(dao.claims[3].verifiers, vec![verifier_key.pubkey()]);
+    }
+
+    #[tokio::test]
+    async fn test_batch_verify_rejects_oversized_batch() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let verifier_key = Keypair::new();
+
+        let claims = (0..=MAX_BATCH_VERIFY as u64).map(|id| pending_claim(id, Pubkey::new_unique(), Pubkey::new_unique())).collect();
+        let dao = dao_state(vec![verifier(verifier_key.pubkey(), 0)], claims);
+
+        let ids = (0..=MAX_BATCH_VERIFY as u64).collect();
+        let instruction = batch_verify_instruction(program_id, dao_key, verifier_key.pubkey(), ids);
+        let (result, dao) = process_at(program_id, dao_key, &dao, instruction, &[&verifier_key], 0).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::BatchTooLarge as u32)),
+        );
+        assert!(dao.claims.iter().all(|c| c.verifiers.is_empty()));
+    }
+
+    #[tokio::test]
+    async fn test_submitted_claims_live_in_their_pdas() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Keypair::new();
+        let verifier_key = Keypair::new();
+
+        let dao = dao_state(vec![member(claimant.pubkey(), Vec::new()), verifier(verifier_key.pubkey(), 0)], Vec::new());
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, TREASURY_FUNDS);
+        program_test.add_account(claimant.pubkey(), wallet());
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let dao_size = banks_client.get_account(dao_key).await.unwrap().unwrap().data.len();
+
+        let first = submit_instruction(program_id, dao_key, claimant.pubkey(), Pubkey::new_unique(), 1_000, None, ServiceType::Consultation, [1; 16]);
+        let second = as_claim(with_nonce(submit_instruction(program_id, dao_key, claimant.pubkey(), Pubkey::new_unique(), 2_500, None, ServiceType::Consultation, [2; 16]), 1), 1);
+        let transaction = Transaction::new_signed_with_payer(&[first, second], Some(&payer.pubkey()), &[&payer, &claimant], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        for (claim_id, amount) in [(0, 1_000), (1, 2_500)] {
+            let account = banks_client.get_account(claim_address(&program_id, &dao_key, claim_id).0).await.unwrap().unwrap();
+            assert_eq!(account.owner, program_id);
+            let claim = Claim::deserialize(&mut &account.data[..]).unwrap();
+            assert_eq!((claim.claim_id, claim.member, claim.amount), (claim_id, claimant.pubkey(), amount));
+        }
+        let account = banks_client.get_account(dao_key).await.unwrap().unwrap();
+        assert_eq!(account.data.len(), dao_size); // Claims don't grow the DAO account
+        let state = HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap();
+        assert_eq!(state.claims_count, 2);
+        assert_eq!(state.open_claim_exposure, 3_500);
+
+        // Verification loads the claim by its seeds, so another claim's account can't stand in for it
+        let mut verify = verify_instruction(program_id, dao_key, verifier_key.pubkey(), 0);
+        verify.accounts[2] = claim_meta(program_id, dao_key, 1);
+        let transaction = Transaction::new_signed_with_payer(&[verify], Some(&payer.pubkey()), &[&payer, &verifier_key], recent_blockhash);
+        assert_eq!(
+            banks_client.process_transaction(transaction).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::InvalidSeeds),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lapsed_member_reinstated_by_premium() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Keypair::new();
+
+        // Premiums cover 30 days, with a further 5 days' grace
+        let mut dao = dao_state(vec![member(claimant.pubkey(), Vec::new())], Vec::new());
+        dao.premium_period_secs = 30 * 86_400;
+        dao.grace_period_secs = 5 * 86_400;
+        dao.members[0].last_premium_ts = 100 * 86_400;
+        dao.treasury_account = dao_key; // Treasury held in the DAO account
+
+        let submit = submit_instruction(program_id, dao_key, claimant.pubkey(), Pubkey::new_unique(), 1_000, None, ServiceType::Consultation, [3; 16]);
+        let (result, _) = process_at(program_id, dao_key, &dao, submit.clone(), &[&claimant], 135 * 86_400).await;
+        assert!(result.is_ok()); // Last day of the grace period
+
+        let now = 135 * 86_400 + 1;
+        let (result, dao) = process_at(program_id, dao_key, &dao, submit.clone(), &[&claimant], now).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::CoverageLapsed as u32)),
+        );
+        assert!(dao.claims.is_empty());
+
+        // Paying the premium restores coverage
+        let pay = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                       // DAO account
+                AccountMeta::new(claimant.pubkey(), true),              // Member paying the premium
+                AccountMeta::new(dao_key, false),                       // Treasury
+                AccountMeta::new_readonly(system_program::id(), false), // System program
+            ],
+            data: DaoInstruction::Finance(FinanceInstruction::PayPremium { amount: 100_000, nonce: 0 }).try_to_vec().unwrap(),
+        };
+        // A premium the member didn't sign leaves coverage lapsed
+        let mut unsigned = pay.clone();
+        unsigned.accounts[1].is_signer = false;
+        let (result, dao) = process_at_funded(program_id, dao_key, &dao, &[claimant.pubkey()], unsigned, &[], now).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::MissingRequiredSignature),
+        );
+        assert_eq!(dao.members[0].last_premium_ts, 100 * 86_400);
+
+        let (result, dao) = process_at_funded(program_id, dao_key, &dao, &[claimant.pubkey()], pay, &[&claimant], now).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.members[0].last_premium_ts, now);
+
+        let (result, dao) = process_at(program_id, dao_key, &dao, with_nonce(submit, 1), &[&claimant], now).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unsigned_claim_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Pubkey::new_unique();
+        let dao = dao_state(vec![member(claimant, Vec::new())], Vec::new());
+
+        // Anyone could otherwise file claims in the member's name and burn their nonce
+        let mut submit = submit_instruction(program_id, dao_key, claimant, Pubkey::new_unique(), 1_000, None, ServiceType::Consultation, [6; 16]);
+        submit.accounts[1].is_signer = false;
+        let (result, dao) = process_at(program_id, dao_key, &dao, submit, &[], 0).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::MissingRequiredSignature),
+        );
+        assert!(dao.claims.is_empty());
+        assert_eq!(dao.members[0].nonce, 0);
+    }
+
+    #[tokio::test]
+    async fn test_admin_handlers_reject_wrong_role() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let member_key = Keypair::new();
+        let verifier_key = Keypair::new();
+        let dao = dao_state(vec![member(member_key.pubkey(), Vec::new()), verifier(verifier_key.pubkey(), 0)], Vec::new());
+
+        // A signed call from the wrong role is unauthorized, not a missing signature
+        let instructions = [
+            ClaimsInstruction::ConfigureHoldback { holdback_bps: 1_000, timeout_secs: 60 },
+            ClaimsInstruction::SetStakeRequirement { requirement: 0 },
+            ClaimsInstruction::SetAnnualClaimCap { cap: 100 },
+        ];
+        for signer in [&member_key, &verifier_key] {
+            for instruction in instructions.clone() {
+                let instruction = Instruction {
+                    program_id,
+                    accounts: vec![
+                        AccountMeta::new(dao_key, false),                 // DAO account
+                        AccountMeta::new_readonly(signer.pubkey(), true), // Admin
+                    ],
+                    data: claims_data(instruction),
+                };
+                let (result, after) = process_at(program_id, dao_key, &dao, instruction, &[signer], 0).await;
+                assert_eq!(
+                    result.unwrap_err().unwrap(),
+                    TransactionError::InstructionError(0, InstructionError::Custom(DaoError::Unauthorized as u32)),
+                );
+                assert_eq!((after.holdback_bps, after.max_claims_per_member_per_year), (0, 0));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_high_risk_claims_paused_in_emergency_mode() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let high_risk = Keypair::new();
+        let low_risk = Keypair::new();
+
+        let mut dao = dao_state(vec![member(high_risk.pubkey(), Vec::new()), member(low_risk.pubkey(), Vec::new())], Vec::new());
+        dao.emergency_mode = true;
+        dao.risk_profiles = vec![
+            crate::RiskProfile { member: high_risk.pubkey(), risk_score: 85, ..Default::default() },
+            crate::RiskProfile { member: low_risk.pubkey(), risk_score: 20, ..Default::default() },
+        ];
+
+        let submit = submit_instruction(program_id, dao_key, high_risk.pubkey(), Pubkey::new_unique(), 1_000, None, ServiceType::Consultation, [4; 16]);
+        let (result, _) = process_at(program_id, dao_key, &dao, submit, &[&high_risk], 0).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::EmergencyMode as u32)),
+        );
+
+        let submit = submit_instruction(program_id, dao_key, low_risk.pubkey(), Pubkey::new_unique(), 1_000, None, ServiceType::Consultation, [5; 16]);
+        let (result, dao) = process_at(program_id, dao_key, &dao, submit, &[&low_risk], 0).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims.len(), 1);
+    }
+
+    // Compute units an instruction may use against the benchmark state: the default per-instruction limit
+    const COMPUTE_UNIT_BUDGET: u64 = 200_000;
+
+    // Members and claims in the synthetic state used for compute benchmarks
+    const BENCHMARK_STATE_SIZE: usize = 256;
+
+    // Large DAO where `verifier_key` and `claimant` sit at the end of the member list, so lookups scan every record
+    fn benchmark_dao(verifier_key: Pubkey, claimant: Pubkey) -> Ledger {
+        let mut members: Vec<Member> = (0..BENCHMARK_STATE_SIZE)
+            .map(|_| verifier(Pubkey::new_unique(), 0))
+            .collect();
+        members.push(member(claimant, Vec::new()));
+        members.push(verifier(verifier_key, 0));
+
+        let mut claims: Vec<Claim> = (0..BENCHMARK_STATE_SIZE as u64)
+            .map(|id| pending_claim(id, Pubkey::new_unique(), Pubkey::new_unique()))
+            .collect();
+        claims.last_mut().unwrap().member = claimant;
+
+        let mut dao = dao_state(members, claims);
+        dao.verifier_inactivity_secs = 1;
+        dao
+    }
+
+    // Simulate `instruction` against accounts seeded with `dao` and assert it succeeds within COMPUTE_UNIT_BUDGET
+    async fn assert_within_compute_budget(
+        label: &str,
+        program_id: Pubkey,
+        dao_key: Pubkey,
+        dao: &Ledger,
+        instruction: Instruction,
+        signers: &[&Keypair],
+    ) {
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        program_test.prefer_bpf(true); // Measure the compiled program when it has been built
+        program_test.set_compute_max_units(COMPUTE_UNIT_BUDGET);
+        dao.add_accounts(&mut program_test, program_id, dao_key, TREASURY_FUNDS);
+        for signer in signers {
+            program_test.add_account(signer.pubkey(), wallet());
+        }
+        let mut context = program_test.start_with_context().await;
+        context.set_sysvar(&Clock { unix_timestamp: 86_400, ..Clock::default() });
+
+        let mut all_signers = vec![&context.payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &all_signers,
+            context.last_blockhash,
+        );
+        let simulation = context.banks_client.simulate_transaction(transaction).await.unwrap();
+        assert!(matches!(simulation.result, Some(Ok(()))), "{} failed: {:?}", label, simulation.result);
+
+        let units_consumed = simulation.simulation_details.unwrap().units_consumed;
+        assert!(units_consumed <= COMPUTE_UNIT_BUDGET, "{} used {} compute units", label, units_consumed);
+    }
+
+    #[tokio::test]
+    async fn test_compute_budget_submit() {
+        let (program_id, dao_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let claimant = Keypair::new();
+        let dao = benchmark_dao(Pubkey::new_unique(), claimant.pubkey());
+
+        let instruction = as_claim(
+            submit_instruction(program_id, dao_key, claimant.pubkey(), Pubkey::new_unique(), 1_000, None, ServiceType::Consultation, [0xFF; 16]),
+            BENCHMARK_STATE_SIZE as u64,
+        );
+        assert_within_compute_budget("submit", program_id, dao_key, &dao, instruction, &[&claimant]).await;
+    }
+
+    #[tokio::test]
+    async fn test_compute_budget_verify() {
+        let (program_id, dao_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let verifier_key = Keypair::new();
+        let dao = benchmark_dao(verifier_key.pubkey(), Pubkey::new_unique());
+
+        let instruction = verify_instruction(program_id, dao_key, verifier_key.pubkey(), (BENCHMARK_STATE_SIZE - 1) as u64);
+        assert_within_compute_budget("verify", program_id, dao_key, &dao, instruction, &[&verifier_key]).await;
+    }
+
+    #[tokio::test]
+    async fn test_compute_budget_payout() {
+        let (program_id, dao_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let claimant = Pubkey::new_unique();
+        let verifier_key = Keypair::new();
+        let mut dao = benchmark_dao(verifier_key.pubkey(), claimant);
+        fund_treasury(&mut dao, dao_key, claimant);
+        dao.claims.last_mut().unwrap().status = ClaimStatus::Verified;
+
+        let instruction = payout_instruction(program_id, dao_key, verifier_key.pubkey(), claimant, (BENCHMARK_STATE_SIZE - 1) as u64);
+        assert_within_compute_budget("payout", program_id, dao_key, &dao, instruction, &[&verifier_key]).await;
+    }
+
+    #[tokio::test]
+    async fn test_compute_budget_appeal() {
+        let (program_id, dao_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let claimant = Keypair::new();
+        let mut dao = benchmark_dao(Pubkey::new_unique(), claimant.pubkey());
+        dao.claims.last_mut().unwrap().status = ClaimStatus::Rejected;
+
+        let instruction = appeal_instruction(program_id, dao_key, claimant.pubkey(), (BENCHMARK_STATE_SIZE - 1) as u64);
+        assert_within_compute_budget("appeal", program_id, dao_key, &dao, instruction, &[&claimant]).await;
+    }
+
+    #[tokio::test]
+    async fn test_compute_budget_demote_idle_verifiers() {
+        let (program_id, dao_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let dao = benchmark_dao(Pubkey::new_unique(), Pubkey::new_unique());
+
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![AccountMeta::new(dao_key, false)], // DAO account
+            data: claims_data(ClaimsInstruction::DemoteInactiveVerifiers),
+        };
+        assert_within_compute_budget("demote idle verifiers", program_id, dao_key, &dao, instruction, &[]).await;
+    }
+
+    #[tokio::test]
+    async fn test_compute_budget_export() {
+        let (program_id, dao_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let dao = benchmark_dao(Pubkey::new_unique(), Pubkey::new_unique());
+
+        let offset = (BENCHMARK_STATE_SIZE - CLAIMS_EXPORT_PAGE_SIZE) as u64;
+        let mut accounts = vec![AccountMeta::new(dao_key, false)]; // DAO account
+        accounts.extend((offset..BENCHMARK_STATE_SIZE as u64).map(|id| claim_meta(program_id, dao_key, id)));
+        let instruction = Instruction { program_id, accounts, data: claims_data(ClaimsInstruction::ExportClaims { offset }) };
+        assert_within_compute_budget("export", program_id, dao_key, &dao, instruction, &[]).await;
+    }
+}
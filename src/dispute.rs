@@ -0,0 +1,1561 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    hash::hashv,
+    msg,
+    program::invoke,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_instruction, system_program,
+    sysvar::{clock::Clock, Sysvar},
+};
+
+use crate::{require_role, require_signed_role, Claim, ClaimStatus, DepositStatus, Dispute, DisputeStatus, HealthInsuranceDAO, Member, Role};
+use crate::claims;
+use crate::error::DaoError;
+use crate::instruction::DisputeInstruction;
+
+// Votes needed to close a dispute decided by an open vote of all members
+const BASE_QUORUM: usize = 6;
+
+// With tenure weighting, every full period of membership adds one vote to a member's weight
+const TENURE_WEIGHT_PERIOD_SECS: i64 = 90 * 24 * 60 * 60;
+
+// Reputation every member starts with, so new members can be drawn for juries and lose standing for absences
+pub(crate) const STARTING_REPUTATION: u32 = 100;
+
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction: DisputeInstruction,
+) -> ProgramResult {
+    // Fail early with a clear message when the client supplies too few accounts for the instruction
+    let expected = expected_account_count(&instruction);
+    if accounts.len() < expected {
+        msg!("Instruction {:?} expects at least {} accounts but received {}", instruction, expected, accounts.len());
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let accounts_iter = &mut accounts.iter();
+    let account = next_account_info(accounts_iter)?;
+
+    // Verify program ownership of the account
+    if account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let mut dao_data = HealthInsuranceDAO::load(account)?;
+
+    // Every dispute instruction changes state, so none run while the DAO is paused
+    dao_data.ensure_active()?;
+
+    match instruction {
+        // ... existing instructions ...
+
+        DisputeInstruction::SubmitDispute { description } => {
+            // Submit a Dispute - Allows members to raise disputes within the DAO
+            let initiator = next_account_info(accounts_iter)?; // Account of the member starting the dispute
+            let respondent = next_account_info(accounts_iter)?; // Account of the member or entity being disputed against
+            require_signed_role(&dao_data, initiator, Role::Member)?;
+
+            // Protect governance throughput by capping how many disputes are open at once
+            if open_dispute_limit_reached(&dao_data.disputes, dao_data.max_open_disputes) {
+                msg!("The DAO already has the maximum of {} open disputes; please wait for one to close and try again", dao_data.max_open_disputes);
+                return Err(DaoError::OpenDisputeLimitReached.into());
+            }
+
+            // Escrow the filing deposit in the DAO account
+            let deposit = dao_data.dispute_deposit;
+            if deposit > 0 {
+                let system_program_account = next_account_info(accounts_iter)?; // System program for the deposit transfer
+                if *system_program_account.key != system_program::id() {
+                    return Err(ProgramError::IncorrectProgramId);
+                }
+                invoke(
+                    &system_instruction::transfer(initiator.key, account.key, deposit),
+                    &[initiator.clone(), account.clone(), system_program_account.clone()],
+                )?;
+            }
+
+            let dispute_id = file_dispute(&mut dao_data, *initiator.key, *respondent.key, None, description, deposit)?;
+            msg!("Dispute submitted with ID: {}", dispute_id);
+        }
+
+        DisputeInstruction::VoteDispute { dispute_index, vote } => {
+            // Vote on a Dispute - Allows members to cast votes on existing disputes
+            let voter = next_account_info(accounts_iter)?; // Account of the member voting
+            if !voter.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            require_role(&dao_data, voter.key, Role::Member)?;
+            let mut disputed = load_disputed_claim(program_id, account.key, &mut dao_data, dispute_index, accounts_iter)?;
+
+            if let Some(dispute) = dao_data.disputes.get_mut(dispute_index as usize) {
+                if dispute.status == DisputeStatus::Open {
+                    // Disputes with a jury are decided by the selected jurors only
+                    if !dispute.jurors.is_empty() && !dispute.jurors.contains(voter.key) {
+                        msg!("{} is not on the jury for dispute {}", voter.key, dispute.dispute_id);
+                        return Err(DaoError::NotOnJury.into());
+                    }
+
+                    // The parties to a dispute have a conflict of interest and can't vote on it
+                    if *voter.key == dispute.initiator || *voter.key == dispute.respondent {
+                        msg!("{} is a party to dispute {} and cannot vote on it", voter.key, dispute.dispute_id);
+                        return Err(DaoError::ConflictOfInterest.into());
+                    }
+
+                    // Ensure voter hasn't voted on this dispute before
+                    if !dispute.votes.iter().any(|(v, _)| v == voter.key) {
+                        dispute.votes.push((*voter.key, vote));
+                        if let Some(member) = dao_data.members.iter_mut().find(|m| m.member_address == *voter.key) {
+                            member.votes_cast = member.votes_cast.saturating_add(1);
+                        }
+                        msg!("Vote cast on dispute {}", dispute.dispute_id);
+                    } else {
+                        return Err(DaoError::DuplicateVote.into());
+                    }
+
+                    // Logic to close the dispute based on the weight of votes cast against its (possibly decayed) quorum
+                    let now = Clock::get()?.unix_timestamp;
+                    let quorum = effective_quorum(dispute, now, dao_data.quorum_decay_secs);
+                    let cast_weight = tenure_weighted_votes(&dispute.votes, &dao_data.members, dao_data.max_tenure_weight, now);
+                    // Early close counts heads, so it only applies while votes are unweighted
+                    let decided = dao_data.early_close_enabled
+                        && dao_data.vote_weight_cap_pct == 0
+                        && dao_data.max_tenure_weight == 0
+                        && outcome_decided(&dispute.votes, eligible_voter_count(dispute, &dao_data.members));
+                    let weighting = VoteWeighting { cap_pct: dao_data.vote_weight_cap_pct, max_tenure_weight: dao_data.max_tenure_weight, now };
+                    if decided {
+                        msg!("Dispute {} closed early: the outcome can no longer change", dispute.dispute_id);
+                        let slashed = close_dispute(dispute, disputed.as_mut().map(|(_, claim)| claim), &dao_data.members, &weighting, dao_data.slash_margin_pct);
+                        credit_slashed_deposit(account.key, &mut dao_data, slashed)?;
+                    } else if cast_weight >= quorum as u64 {
+                        msg!("Dispute {} closed due to sufficient votes", dispute.dispute_id);
+                        let slashed = close_dispute(dispute, disputed.as_mut().map(|(_, claim)| claim), &dao_data.members, &weighting, dao_data.slash_margin_pct);
+                        credit_slashed_deposit(account.key, &mut dao_data, slashed)?;
+                    }
+                } else {
+                    return Err(DaoError::DisputeClosed.into());
+                }
+            } else {
+                return Err(DaoError::DisputeNotFound.into());
+            }
+            settle_disputed_claim(&mut dao_data, disputed)?;
+        }
+
+        DisputeInstruction::RolloverParticipation => {
+            // Participation Period Rollover - Penalizes members who skipped too many dispute votes and starts a new period
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin can roll the period over
+
+            let min_participation_pct = dao_data.min_participation_pct;
+            let penalty = dao_data.participation_penalty;
+            let mut penalized = 0;
+            for member in dao_data.members.iter_mut() {
+                if participation_below_threshold(member.votes_cast, member.disputes_eligible, min_participation_pct) {
+                    member.reputation = member.reputation.saturating_sub(penalty);
+                    penalized += 1;
+                }
+                member.votes_cast = 0;
+                member.disputes_eligible = 0;
+            }
+            msg!("Participation period rolled over, {} members penalized", penalized);
+        }
+
+        DisputeInstruction::ReclaimDeposit { dispute_index } => {
+            // Reclaim Dispute Deposit - Returns the filing deposit to the initiator of a closed, non-frivolous dispute
+            let initiator = next_account_info(accounts_iter)?;
+            if !initiator.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+
+            if let Some(dispute) = dao_data.disputes.get_mut(dispute_index as usize) {
+                if dispute.initiator != *initiator.key {
+                    return Err(ProgramError::InvalidArgument); // Only the initiator can reclaim the deposit
+                }
+                if dispute.deposit_status != DepositStatus::Refundable {
+                    return Err(ProgramError::InvalidAccountData); // Deposit is still held, already refunded, or slashed
+                }
+
+                // The DAO account is owned by this program, so lamports can be moved out directly
+                **account.try_borrow_mut_lamports()? = account.lamports().checked_sub(dispute.deposit).ok_or(ProgramError::InsufficientFunds)?;
+                **initiator.try_borrow_mut_lamports()? = initiator.lamports().checked_add(dispute.deposit).ok_or(ProgramError::ArithmeticOverflow)?;
+                dispute.deposit_status = DepositStatus::Refunded;
+                msg!("Deposit of {} lamports refunded for dispute {}", dispute.deposit, dispute.dispute_id);
+            } else {
+                return Err(DaoError::DisputeNotFound.into());
+            }
+        }
+
+        DisputeInstruction::CloseStaleDispute { dispute_index } => {
+            // Close Stale Dispute - Closes an open dispute whose votes meet its time-decayed quorum
+            let now = Clock::get()?.unix_timestamp;
+            let mut disputed = load_disputed_claim(program_id, account.key, &mut dao_data, dispute_index, accounts_iter)?;
+
+            let dispute = dao_data.disputes.get_mut(dispute_index as usize).ok_or(DaoError::DisputeNotFound)?;
+            if dispute.status != DisputeStatus::Open {
+                return Err(DaoError::DisputeClosed.into());
+            }
+            let quorum = effective_quorum(dispute, now, dao_data.quorum_decay_secs);
+            let cast_weight = tenure_weighted_votes(&dispute.votes, &dao_data.members, dao_data.max_tenure_weight, now);
+            if cast_weight < quorum as u64 {
+                msg!("Dispute {} has {} votes but its quorum is currently {}", dispute.dispute_id, cast_weight, quorum);
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            msg!("Dispute {} closed under decayed quorum of {}", dispute.dispute_id, quorum);
+            let weighting = VoteWeighting { cap_pct: dao_data.vote_weight_cap_pct, max_tenure_weight: dao_data.max_tenure_weight, now };
+            let slashed = close_dispute(dispute, disputed.as_mut().map(|(_, claim)| claim), &dao_data.members, &weighting, dao_data.slash_margin_pct);
+            credit_slashed_deposit(account.key, &mut dao_data, slashed)?;
+            settle_disputed_claim(&mut dao_data, disputed)?;
+        }
+
+        DisputeInstruction::SetVoteWeightCap { cap_pct } => {
+            // Set Vote Weight Cap - Allows the admin to weight dispute votes by reputation, capping each voter's share
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin can change vote weighting
+            if cap_pct > 100 {
+                return Err(ProgramError::InvalidArgument);
+            }
+            dao_data.vote_weight_cap_pct = cap_pct;
+            msg!("Dispute vote weight cap set to {}%", cap_pct);
+        }
+
+        DisputeInstruction::SetMaxTenureWeight { max_weight } => {
+            // Set Max Tenure Weight - Allows the admin to weight dispute votes by membership tenure, up to a cap
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin can change vote weighting
+            dao_data.max_tenure_weight = max_weight;
+            msg!("Dispute votes weighted by tenure up to {} votes per member", max_weight);
+        }
+
+        DisputeInstruction::SetDisputeDeposit { deposit, slash_margin_pct } => {
+            // Set Dispute Deposit - Allows the admin to set the filing deposit and the loss margin that slashes it
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin can change the deposit policy
+            if slash_margin_pct > 100 || (slash_margin_pct > 0 && slash_margin_pct <= 50) {
+                return Err(ProgramError::InvalidArgument); // Only a lopsided loss, never a win, may slash the deposit
+            }
+            dao_data.dispute_deposit = deposit;
+            dao_data.slash_margin_pct = slash_margin_pct;
+            msg!("Dispute deposit set to {} lamports, slashed at {}% against", deposit, slash_margin_pct);
+        }
+
+        DisputeInstruction::SetOpenDisputeLimit { max_open_disputes } => {
+            // Set Open Dispute Limit - Allows the admin to cap how many disputes may be open at once
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin can change the open dispute limit
+            dao_data.max_open_disputes = max_open_disputes;
+            msg!("Open dispute limit set to {}", max_open_disputes);
+        }
+
+        DisputeInstruction::SetQuorumDecay { decay_secs } => {
+            // Set Quorum Decay - Allows the admin to let a stale dispute's quorum drop over time
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin can change quorum decay
+            if decay_secs < 0 {
+                return Err(ProgramError::InvalidArgument);
+            }
+            dao_data.quorum_decay_secs = decay_secs;
+            msg!("Dispute quorum decays by one vote every {} seconds", decay_secs);
+        }
+
+        DisputeInstruction::SetEarlyClose { enabled } => {
+            // Set Early Close - Allows the admin to close disputes as soon as their outcome is decided
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin can change early close
+            dao_data.early_close_enabled = enabled;
+            msg!("Early dispute close {}", if enabled { "enabled" } else { "disabled" });
+        }
+
+        DisputeInstruction::SetParticipationPolicy { min_participation_pct, penalty } => {
+            // Set Participation Policy - Allows the admin to set the voting rate below which members are penalized
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin can change the participation policy
+            if min_participation_pct > 100 {
+                return Err(ProgramError::InvalidArgument);
+            }
+            dao_data.min_participation_pct = min_participation_pct;
+            dao_data.participation_penalty = penalty;
+            msg!("Members voting on under {}% of disputes lose {} reputation", min_participation_pct, penalty);
+        }
+
+        DisputeInstruction::SetJurySize { jury_size } => {
+            // Set Jury Size - Allows the admin to have disputes decided by a drawn jury instead of every member
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin can change the jury size
+            // Only members with standing can be drawn, and the parties to a dispute never sit on its jury
+            let candidates = dao_data.members.iter().filter(|m| m.reputation > 0).count().saturating_sub(2);
+            if jury_size as usize > candidates {
+                msg!("A jury of {} can't be drawn from {} candidates", jury_size, candidates);
+                return Err(ProgramError::InvalidArgument);
+            }
+            dao_data.jury_size = jury_size;
+            msg!("Disputes decided by a jury of {}", jury_size);
+        }
+    }
+
+    dao_data.save(account)
+}
+
+// Minimum number of accounts each instruction needs, including the DAO account
+fn expected_account_count(instruction: &DisputeInstruction) -> usize {
+    match instruction {
+        DisputeInstruction::SubmitDispute { .. } => 3, // DAO, initiator, respondent (plus the system program when a deposit is required)
+        DisputeInstruction::VoteDispute { .. } => 2, // DAO, voter (plus the claim account when the dispute is an appeal)
+        DisputeInstruction::RolloverParticipation => 2, // DAO, admin
+        DisputeInstruction::ReclaimDeposit { .. } => 2, // DAO, initiator
+        DisputeInstruction::CloseStaleDispute { .. } => 1, // DAO (plus the claim account when the dispute is an appeal)
+        DisputeInstruction::SetVoteWeightCap { .. } => 2, // DAO, admin
+        DisputeInstruction::SetMaxTenureWeight { .. } => 2, // DAO, admin
+        DisputeInstruction::SetDisputeDeposit { .. } => 2, // DAO, admin
+        DisputeInstruction::SetOpenDisputeLimit { .. } => 2, // DAO, admin
+        DisputeInstruction::SetQuorumDecay { .. } => 2, // DAO, admin
+        DisputeInstruction::SetEarlyClose { .. } => 2, // DAO, admin
+        DisputeInstruction::SetParticipationPolicy { .. } => 2, // DAO, admin
+        DisputeInstruction::SetJurySize { .. } => 2, // DAO, admin
+    }
+}
+
+// Open a dispute, drawing a reputation-weighted jury when configured, and return its id. `claim_id` links
+// disputes raised over a claim, such as appeals, so the claim can be settled when the dispute closes.
+pub(crate) fn file_dispute(
+    dao_data: &mut HealthInsuranceDAO,
+    initiator: Pubkey,
+    respondent: Pubkey,
+    claim_id: Option<u64>,
+    description: String,
+    deposit: u64,
+) -> Result<u64, ProgramError> {
+    // The jury is seeded from the current slot
+    let clock = Clock::get()?;
+    let dispute_id = assign_dispute_id(&mut dao_data.next_dispute_id)?;
+    let jury_seed = hashv(&[&clock.slot.to_le_bytes(), &dispute_id.to_le_bytes()]).to_bytes();
+    let jurors = if dao_data.jury_size > 0 {
+        select_jury(&dao_data.members, &[initiator, respondent], &jury_seed, dao_data.jury_size as usize).ok_or_else(|| {
+            msg!("A jury of {} can't be drawn from the members with standing", dao_data.jury_size);
+            ProgramError::from(DaoError::JuryUnavailable)
+        })?
+    } else {
+        Vec::new()
+    };
+
+    dao_data.disputes.push(Dispute {
+        dispute_id, // Assign a new ID
+        claim_id,
+        initiator,
+        respondent,
+        description,
+        status: DisputeStatus::Open, // New disputes start as open
+        votes: Vec::new(), // No votes yet
+        deposit,
+        deposit_status: DepositStatus::Held,
+        jury_seed,
+        jurors: jurors.clone(),
+        opened_at: clock.unix_timestamp,
+    });
+
+    // Jurors are eligible to vote on the new dispute; without a jury, every member other than the parties is
+    for member in dao_data.members.iter_mut() {
+        let eligible = if jurors.is_empty() {
+            member.member_address != initiator && member.member_address != respondent
+        } else {
+            jurors.contains(&member.member_address)
+        };
+        if eligible {
+            member.disputes_eligible = member.disputes_eligible.saturating_add(1);
+        }
+    }
+    Ok(dispute_id)
+}
+
+// Draw `jury_size` distinct jurors from the members, excluding the parties, with odds proportional to reputation.
+// The draw is fully determined by the seed, so anyone can re-run it to confirm the jury. Returns None rather than a
+// smaller jury when too few members with standing are available.
+fn select_jury(members: &[Member], parties: &[Pubkey], seed: &[u8; 32], jury_size: usize) -> Option<Vec<Pubkey>> {
+    let mut candidates: Vec<&Member> = members
+        .iter()
+        .filter(|m| m.reputation > 0 && !parties.contains(&m.member_address))
+        .collect();
+    if candidates.len() < jury_size {
+        return None;
+    }
+    let mut jurors = Vec::with_capacity(jury_size);
+
+    for round in 0..jury_size as u64 {
+        let total_weight: u64 = candidates.iter().map(|m| m.reputation as u64).sum();
+        let draw = hashv(&[seed, &round.to_le_bytes()]).to_bytes();
+        let mut target = u64::from_le_bytes(draw[..8].try_into().unwrap()) % total_weight;
+
+        let picked = candidates
+            .iter()
+            .position(|m| {
+                if target < m.reputation as u64 {
+                    true
+                } else {
+                    target -= m.reputation as u64;
+                    false
+                }
+            })
+            .unwrap();
+        jurors.push(candidates.remove(picked).member_address);
+    }
+    Some(jurors)
+}
+
+// Take the next dispute id from the monotonic counter, failing rather than wrapping around to a used id
+fn assign_dispute_id(next_dispute_id: &mut u64) -> Result<u64, ProgramError> {
+    let dispute_id = *next_dispute_id;
+    *next_dispute_id = dispute_id.checked_add(1).ok_or(ProgramError::ArithmeticOverflow)?;
+    Ok(dispute_id)
+}
+
+// Votes needed to close the dispute: the full jury or the base quorum, reduced by one for every
+// `decay_secs` the dispute has been open, but never below a single vote
+fn effective_quorum(dispute: &Dispute, now: i64, decay_secs: i64) -> usize {
+    let base = if dispute.jurors.is_empty() { BASE_QUORUM } else { dispute.jurors.len() };
+    if decay_secs <= 0 {
+        return base;
+    }
+    let decayed = (now.saturating_sub(dispute.opened_at) / decay_secs).max(0) as usize;
+    base.saturating_sub(decayed).max(1)
+}
+
+// Members entitled to vote on the dispute: its jurors, or every member other than the parties
+fn eligible_voter_count(dispute: &Dispute, members: &[Member]) -> usize {
+    if !dispute.jurors.is_empty() {
+        return dispute.jurors.len();
+    }
+    members
+        .iter()
+        .filter(|m| m.member_address != dispute.initiator && m.member_address != dispute.respondent)
+        .count()
+}
+
+// The outcome is decided once either side holds more than half of all eligible votes
+fn outcome_decided(votes: &[(Pubkey, bool)], eligible_voters: usize) -> bool {
+    let agree_count = votes.iter().filter(|(_, v)| *v).count();
+    let disagree_count = votes.len() - agree_count;
+    eligible_voters > 0 && (agree_count * 2 > eligible_voters || disagree_count * 2 > eligible_voters)
+}
+
+// For a dispute raised over a claim, load the claim from the next account so closing the dispute can settle it.
+// The claim is taken out of the DAO's running totals until `settle_disputed_claim` writes it back.
+fn load_disputed_claim<'a, 'b>(
+    program_id: &Pubkey,
+    dao_key: &Pubkey,
+    dao_data: &mut HealthInsuranceDAO,
+    dispute_index: u64,
+    accounts_iter: &mut std::slice::Iter<'b, AccountInfo<'a>>,
+) -> Result<Option<(&'b AccountInfo<'a>, Claim)>, ProgramError> {
+    let claim_id = match dao_data.disputes.get(dispute_index as usize).and_then(|d| d.claim_id) {
+        Some(claim_id) => claim_id,
+        None => return Ok(None),
+    };
+    let claim_account = next_account_info(accounts_iter)?; // PDA holding the claim under appeal
+    // A claim archived since the appeal was filed has nothing left to settle
+    let claim = match claims::load_live_claim(program_id, dao_key, claim_account, claim_id)? {
+        Some(claim) => claim,
+        None => return Ok(None),
+    };
+    claims::untrack_claim(dao_data, &claim);
+    Ok(Some((claim_account, claim)))
+}
+
+// Return a claim loaded by `load_disputed_claim` to the running totals and save it
+fn settle_disputed_claim(dao_data: &mut HealthInsuranceDAO, disputed: Option<(&AccountInfo, Claim)>) -> ProgramResult {
+    if let Some((claim_account, claim)) = disputed {
+        claims::track_claim(dao_data, &claim);
+        claims::save_claim(claim_account, &claim)?;
+    }
+    Ok(())
+}
+
+// How votes are weighted when a dispute is tallied; weights are recomputed from member records at close
+struct VoteWeighting {
+    cap_pct: u8,            // Reputation weighting cap (0 disables reputation weighting)
+    max_tenure_weight: u32, // Tenure weighting cap (0 disables tenure weighting)
+    now: i64,               // Time tenure is measured to
+}
+
+// Close the dispute by simple majority and settle its deposit, returning the lamports slashed to the treasury
+fn close_dispute(dispute: &mut Dispute, claim: Option<&mut Claim>, members: &[Member], weighting: &VoteWeighting, slash_margin_pct: u8) -> u64 {
+    dispute.status = DisputeStatus::Closed;
+
+    // Simple majority of the (possibly weighted) votes decides the outcome
+    let (agree_weight, total_weight) = tally_votes(&dispute.votes, members, weighting);
+    let upheld = agree_weight * 2 > total_weight;
+    if upheld {
+        msg!("Dispute {} resolved in favor of initiator", dispute.dispute_id);
+    } else {
+        msg!("Dispute {} resolved against initiator", dispute.dispute_id);
+    }
+
+    // An upheld appeal sends the claim back for re-verification; a failed one leaves the rejection standing
+    if let Some(claim) = claim {
+        if claim.status == ClaimStatus::Appealed {
+            claim.status = if upheld { ClaimStatus::Pending } else { ClaimStatus::Rejected };
+            msg!("Appealed claim {} returned to {:?}", claim.claim_id, claim.status);
+        }
+    }
+
+    // Frivolous disputes lose their deposit to the treasury, everything else is refundable
+    if should_slash_deposit(agree_weight, total_weight, slash_margin_pct) {
+        dispute.deposit_status = DepositStatus::Slashed;
+        msg!("Deposit of {} lamports slashed for dispute {}", dispute.deposit, dispute.dispute_id);
+        dispute.deposit
+    } else {
+        dispute.deposit_status = DepositStatus::Refundable;
+        0
+    }
+}
+
+// Count a slashed deposit toward the treasury. Deposits are escrowed in the DAO account, so they only join the treasury
+// balance when the treasury is the DAO account itself and holds lamports; a separate or token treasury never received
+// them, and the deposit stays escrowed in the DAO account instead.
+fn credit_slashed_deposit(dao_key: &Pubkey, dao_data: &mut HealthInsuranceDAO, slashed: u64) -> ProgramResult {
+    if slashed == 0 {
+        return Ok(());
+    }
+    if dao_data.treasury_account == *dao_key && dao_data.treasury.token_mint.is_none() {
+        dao_data.treasury.balance = dao_data.treasury.balance.checked_add(slashed).ok_or(ProgramError::ArithmeticOverflow)?;
+    } else {
+        msg!("Slashed deposit of {} lamports stays escrowed in the DAO account, outside the configured treasury", slashed);
+    }
+    Ok(())
+}
+
+// Whether the number of open disputes has reached the DAO-wide cap
+pub(crate) fn open_dispute_limit_reached(disputes: &[Dispute], max_open_disputes: u32) -> bool {
+    max_open_disputes > 0 && disputes.iter().filter(|d| d.status == DisputeStatus::Open).count() >= max_open_disputes as usize
+}
+
+// Votes a member's tenure earns them: one, plus one per full tenure period, up to `max_weight` (0 disables weighting)
+fn tenure_weight(joined_timestamp: i64, now: i64, max_weight: u32) -> u64 {
+    if max_weight == 0 {
+        return 1;
+    }
+    let periods = (now.saturating_sub(joined_timestamp).max(0) / TENURE_WEIGHT_PERIOD_SECS) as u64;
+    periods.saturating_add(1).min(max_weight as u64)
+}
+
+// Total tenure weight of the votes cast; equal to the number of votes while tenure weighting is off
+fn tenure_weighted_votes(votes: &[(Pubkey, bool)], members: &[Member], max_weight: u32, now: i64) -> u64 {
+    votes
+        .iter()
+        .map(|(voter, _)| members.iter().find(|m| m.member_address == *voter).map_or(1, |m| tenure_weight(m.joined_timestamp, now, max_weight)))
+        .sum()
+}
+
+// Weight in favor of the initiator and total weight cast. With no cap every vote counts once; otherwise each vote
+// carries the voter's reputation, limited to `cap_pct` of the total so no single member can dominate. Voters who
+// have all lost their reputation are counted once each instead. Tenure weighting, when enabled, multiplies each
+// vote by the voter's tenure weight.
+fn tally_votes(votes: &[(Pubkey, bool)], members: &[Member], weighting: &VoteWeighting) -> (u64, u64) {
+    let record = |voter: &Pubkey| members.iter().find(|m| m.member_address == *voter);
+    let tenure_of = |voter: &Pubkey| record(voter).map_or(1, |m| tenure_weight(m.joined_timestamp, weighting.now, weighting.max_tenure_weight));
+    let weight_of = |voter: &Pubkey| record(voter).map_or(0, |m| m.reputation as u64) * tenure_of(voter);
+    let raw_total: u64 = votes.iter().map(|(voter, _)| weight_of(voter)).sum();
+    if weighting.cap_pct == 0 || raw_total == 0 {
+        return votes.iter().fold((0, 0), |(agree, total), (voter, vote)| {
+            let weight = tenure_of(voter);
+            (if *vote { agree + weight } else { agree }, total + weight)
+        });
+    }
+    let cap = raw_total * weighting.cap_pct.min(100) as u64 / 100;
+    votes.iter().fold((0, 0), |(agree, total), (voter, vote)| {
+        let weight = weight_of(voter).min(cap);
+        (if *vote { agree + weight } else { agree }, total + weight)
+    })
+}
+
+// A deposit is slashed when the share of votes against the initiator reaches the slash margin (0 disables slashing)
+fn should_slash_deposit(agree_weight: u64, total_weight: u64, slash_margin_pct: u8) -> bool {
+    let against_weight = total_weight.saturating_sub(agree_weight);
+    slash_margin_pct > 0 && total_weight > 0 && against_weight * 100 >= total_weight * slash_margin_pct as u64
+}
+
+// Members with no eligible disputes in the period are never penalized
+fn participation_below_threshold(votes_cast: u32, disputes_eligible: u32, min_participation_pct: u8) -> bool {
+    disputes_eligible > 0 && (votes_cast as u64 * 100) < (disputes_eligible as u64 * min_participation_pct as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claims::claim_address;
+    use crate::instruction::{DaoInstruction, SecurityInstruction};
+    use crate::{process_instruction, Treasury};
+    use crate::testing::Ledger;
+    use borsh::{BorshDeserialize, BorshSerialize};
+    use solana_program::instruction::{AccountMeta, Instruction};
+    use solana_program_test::*;
+    use solana_sdk::{
+        account::Account,
+        hash::Hash,
+        instruction::InstructionError,
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    };
+
+    #[tokio::test]
+    async fn test_dispute_submission() {
+        // Test setup and dispute submission logic goes here
+        // For example, initializing a DAO, submitting a dispute, checking if it was added correctly
+    }
+
+    #[tokio::test]
+    async fn test_voting_on_dispute() {
+        // Test setup and voting on dispute logic goes here
+        // For example, submitting votes, checking if votes are recorded, and if the dispute closes correctly
+    }
+
+    fn member(member_address: Pubkey, votes_cast: u32, disputes_eligible: u32) -> Member {
+        Member {
+            member_address,
+            reputation: 100,
+            votes_cast,
+            disputes_eligible,
+            ..Default::default()
+        }
+    }
+
+    // Run the participation rollover against `dao` and return the resulting state
+    async fn rollover(dao: HealthInsuranceDAO, admin: &Keypair) -> HealthInsuranceDAO {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let data = dao.try_to_vec().unwrap();
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        program_test.add_account(
+            dao_key,
+            Account {
+                lamports: solana_program::rent::Rent::default().minimum_balance(data.len()),
+                data,
+                owner: program_id,
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),               // DAO account
+                AccountMeta::new_readonly(admin.pubkey(), true), // Admin
+            ],
+            data: DaoInstruction::Dispute(DisputeInstruction::RolloverParticipation).try_to_vec().unwrap(),
+        };
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[&payer, admin],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let account = banks_client.get_account(dao_key).await.unwrap().unwrap();
+        HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_participating_member_not_penalized() {
+        let admin = Keypair::new();
+        let voter = Pubkey::new_unique();
+        let dao = HealthInsuranceDAO {
+            admin: admin.pubkey(),
+            members: vec![member(voter, 4, 5)], // 80% participation
+            min_participation_pct: 50,
+            participation_penalty: 10,
+            ..Default::default()
+        };
+
+        let dao = rollover(dao, &admin).await;
+        assert_eq!(dao.members[0].reputation, 100);
+        assert_eq!(dao.members[0].votes_cast, 0);
+        assert_eq!(dao.members[0].disputes_eligible, 0);
+    }
+
+    #[tokio::test]
+    async fn test_non_participating_member_penalized() {
+        let admin = Keypair::new();
+        let absentee = Pubkey::new_unique();
+        let dao = HealthInsuranceDAO {
+            admin: admin.pubkey(),
+            members: vec![member(absentee, 0, 5)], // Skipped every eligible dispute
+            min_participation_pct: 50,
+            participation_penalty: 10,
+            ..Default::default()
+        };
+
+        let dao = rollover(dao, &admin).await;
+        assert_eq!(dao.members[0].reputation, 90);
+        assert_eq!(dao.members[0].disputes_eligible, 0);
+    }
+
+    #[tokio::test]
+    async fn test_joined_member_penalized_for_skipping_dispute() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let admin = Keypair::new();
+        let joiners: Vec<Keypair> = (0..3).map(|_| Keypair::new()).collect();
+        let dao = HealthInsuranceDAO { admin: admin.pubkey(), min_participation_pct: 50, participation_penalty: 10, ..Default::default() };
+        let (mut banks_client, payer, recent_blockhash) = start_with_joined_members(program_id, dao_key, &dao, &joiners).await;
+
+        // The third member is eligible to vote on the dispute between the other two, and never does
+        let instruction = submit_dispute(program_id, dao_key, joiners[0].pubkey(), joiners[1].pubkey());
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer, &joiners[0]], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                // DAO account
+                AccountMeta::new_readonly(admin.pubkey(), true), // Admin
+            ],
+            data: DaoInstruction::Dispute(DisputeInstruction::RolloverParticipation).try_to_vec().unwrap(),
+        };
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer, &admin], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let dao = load_dao(&mut banks_client, dao_key).await;
+        let reputation = |joiner: &Keypair| dao.members.iter().find(|m| m.member_address == joiner.pubkey()).unwrap().reputation;
+        assert_eq!(reputation(&joiners[0]), STARTING_REPUTATION);
+        assert_eq!(reputation(&joiners[1]), STARTING_REPUTATION);
+        assert_eq!(reputation(&joiners[2]), STARTING_REPUTATION - 10);
+    }
+
+    #[test]
+    fn test_close_call_deposit_refunded() {
+        // 3 of 6 voters sided with the initiator: a loss, but nowhere near the 80% slash margin
+        assert!(!should_slash_deposit(3, 6, 80));
+    }
+
+    #[test]
+    fn test_lopsided_loss_deposit_slashed() {
+        // 5 of 6 voters sided against the initiator, past the 80% slash margin
+        assert!(should_slash_deposit(1, 6, 80));
+        assert!(should_slash_deposit(0, 6, 80));
+    }
+
+    #[test]
+    fn test_zero_slash_margin_never_slashes() {
+        assert!(!should_slash_deposit(0, 6, 0));
+    }
+
+    // A closed dispute filed by `initiator` whose deposit is in `deposit_status`
+    fn closed_dispute(initiator: Pubkey, deposit_status: DepositStatus) -> Dispute {
+        Dispute {
+            dispute_id: 0,
+            claim_id: None,
+            initiator,
+            respondent: Pubkey::new_unique(),
+            description: "Claim denied without review".to_string(),
+            status: DisputeStatus::Closed,
+            votes: Vec::new(),
+            deposit: 1_000_000,
+            deposit_status,
+            jury_seed: [0u8; 32],
+            jurors: Vec::new(),
+            opened_at: 0,
+        }
+    }
+
+    // Attempt to reclaim the deposit for dispute 0, returning the result and the initiator's balance afterwards
+    async fn reclaim_deposit(dispute: Dispute, initiator: &Keypair) -> (Result<(), BanksClientError>, u64) {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let dao = HealthInsuranceDAO {
+            admin: Pubkey::new_unique(),
+            disputes: vec![dispute],
+            dispute_deposit: 1_000_000,
+            slash_margin_pct: 80,
+            next_dispute_id: 1,
+            ..Default::default()
+        };
+        let data = dao.try_to_vec().unwrap();
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        program_test.add_account(
+            dao_key,
+            Account {
+                // Rent-exempt minimum plus the escrowed deposit
+                lamports: solana_program::rent::Rent::default().minimum_balance(data.len()) + 1_000_000,
+                data,
+                owner: program_id,
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+        program_test.add_account(
+            initiator.pubkey(),
+            Account {
+                lamports: 5_000_000,
+                data: Vec::new(),
+                owner: system_program::id(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let data = DaoInstruction::Dispute(DisputeInstruction::ReclaimDeposit { dispute_index: 0 }).try_to_vec().unwrap();
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),         // DAO account
+                AccountMeta::new(initiator.pubkey(), true), // Initiator reclaiming the deposit
+            ],
+            data,
+        };
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[&payer, initiator],
+            recent_blockhash,
+        );
+        let result = banks_client.process_transaction(transaction).await;
+        let balance = banks_client.get_balance(initiator.pubkey()).await.unwrap();
+        (result, balance)
+    }
+
+    #[tokio::test]
+    async fn test_refundable_deposit_reclaimed() {
+        let initiator = Keypair::new();
+        let (result, balance) = reclaim_deposit(closed_dispute(initiator.pubkey(), DepositStatus::Refundable), &initiator).await;
+        assert!(result.is_ok());
+        assert_eq!(balance, 6_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_slashed_deposit_not_reclaimable() {
+        let initiator = Keypair::new();
+        let (result, balance) = reclaim_deposit(closed_dispute(initiator.pubkey(), DepositStatus::Slashed), &initiator).await;
+        assert!(result.is_err());
+        assert_eq!(balance, 5_000_000);
+    }
+
+    #[test]
+    fn test_jury_selection_deterministic() {
+        let initiator = Pubkey::new_unique();
+        let respondent = Pubkey::new_unique();
+        let mut members: Vec<Member> = (0..10).map(|_| member(Pubkey::new_unique(), 0, 0)).collect();
+        members.push(member(initiator, 0, 0));
+        members.push(member(respondent, 0, 0));
+        members[0].reputation = 0; // No standing, never drawn
+        let seed = [42u8; 32];
+
+        let jury = select_jury(&members, &[initiator, respondent], &seed, 3).unwrap();
+        assert_eq!(jury, select_jury(&members, &[initiator, respondent], &seed, 3).unwrap());
+        assert_eq!(jury.len(), 3);
+        assert!(!jury.contains(&initiator) && !jury.contains(&respondent));
+        assert!(!jury.contains(&members[0].member_address));
+        assert!(jury.iter().enumerate().all(|(i, juror)| !jury[i + 1..].contains(juror)));
+    }
+
+    #[test]
+    fn test_short_jury_draw_rejected() {
+        let mut members = vec![member(Pubkey::new_unique(), 0, 0), member(Pubkey::new_unique(), 0, 0), member(Pubkey::new_unique(), 0, 0)];
+        members[2].reputation = 0; // No standing, so only two candidates remain
+        assert_eq!(select_jury(&members, &[], &[1u8; 32], 3), None);
+        assert_eq!(select_jury(&members, &[], &[1u8; 32], 2).unwrap().len(), 2);
+    }
+
+    // Start `dao` and enroll each of `joiners` through JoinDao, so their records are exactly what a new member gets
+    async fn start_with_joined_members(program_id: Pubkey, dao_key: Pubkey, dao: &HealthInsuranceDAO, joiners: &[Keypair]) -> (BanksClient, Keypair, Hash) {
+        // Leave headroom for the joined members and the disputes they raise
+        let mut data = dao.try_to_vec().unwrap();
+        data.resize(data.len() + 4_096, 0);
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        program_test.add_account(
+            dao_key,
+            Account {
+                lamports: solana_program::rent::Rent::default().minimum_balance(data.len()),
+                data,
+                owner: program_id,
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        for joiner in joiners {
+            let instruction = Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(dao_key, false),                 // DAO account
+                    AccountMeta::new_readonly(joiner.pubkey(), true), // New member
+                ],
+                data: DaoInstruction::Security(SecurityInstruction::JoinDao { encrypted_data_hash: [0u8; 32] }).try_to_vec().unwrap(),
+            };
+            let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer, joiner], recent_blockhash);
+            banks_client.process_transaction(transaction).await.unwrap();
+        }
+        (banks_client, payer, recent_blockhash)
+    }
+
+    // Dispute raised by `initiator` against `respondent`, with no deposit required
+    fn submit_dispute(program_id: Pubkey, dao_key: Pubkey, initiator: Pubkey, respondent: Pubkey) -> Instruction {
+        Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),             // DAO account
+                AccountMeta::new_readonly(initiator, true),   // Initiator
+                AccountMeta::new_readonly(respondent, false), // Respondent
+            ],
+            data: DaoInstruction::Dispute(DisputeInstruction::SubmitDispute { description: "Claim denied without review".to_string() }).try_to_vec().unwrap(),
+        }
+    }
+
+    async fn load_dao(banks_client: &mut BanksClient, dao_key: Pubkey) -> HealthInsuranceDAO {
+        let account = banks_client.get_account(dao_key).await.unwrap().unwrap();
+        HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_jury_drawn_from_joined_members() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let joiners: Vec<Keypair> = (0..5).map(|_| Keypair::new()).collect();
+        let dao = HealthInsuranceDAO { admin: Pubkey::new_unique(), jury_size: 3, ..Default::default() };
+        let (mut banks_client, payer, recent_blockhash) = start_with_joined_members(program_id, dao_key, &dao, &joiners).await;
+
+        // Three members besides the parties: exactly enough for the jury
+        let (initiator, respondent) = (&joiners[0], joiners[1].pubkey());
+        let instruction = submit_dispute(program_id, dao_key, initiator.pubkey(), respondent);
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer, initiator], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+        let after = load_dao(&mut banks_client, dao_key).await;
+        let mut jurors = after.disputes[0].jurors.clone();
+        jurors.sort();
+        let mut expected: Vec<Pubkey> = joiners[2..].iter().map(|j| j.pubkey()).collect();
+        expected.sort();
+        assert_eq!(jurors, expected);
+
+        // A dispute between two of those jurors leaves only two other members to draw from
+        let instruction = submit_dispute(program_id, dao_key, joiners[2].pubkey(), joiners[3].pubkey());
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer, &joiners[2]], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+        let after = load_dao(&mut banks_client, dao_key).await;
+        assert_eq!(after.disputes[1].jurors.len(), 3);
+
+        // Once a member loses all standing, the jury can't be filled and the dispute is refused
+        let mut dao = after;
+        dao.members.iter_mut().find(|m| m.member_address == joiners[4].pubkey()).unwrap().reputation = 0;
+        let program_id = Pubkey::new_unique();
+        let (mut banks_client, payer, recent_blockhash) = start_with_joined_members(program_id, dao_key, &dao, &[]).await;
+        let instruction = submit_dispute(program_id, dao_key, joiners[2].pubkey(), joiners[3].pubkey());
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer, &joiners[2]], recent_blockhash);
+        assert_eq!(
+            banks_client.process_transaction(transaction).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::JuryUnavailable as u32)),
+        );
+    }
+
+    // Cast a vote on dispute 0 as `voter`, returning the result and final DAO state
+    async fn cast_vote(dao: &Ledger, voter: &Keypair) -> (Result<(), BanksClientError>, Ledger) {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, 0);
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let data = DaoInstruction::Dispute(DisputeInstruction::VoteDispute { dispute_index: 0, vote: true }).try_to_vec().unwrap(); // Vote for the initiator
+        let mut accounts = vec![
+            AccountMeta::new(dao_key, false),                // DAO account
+            AccountMeta::new_readonly(voter.pubkey(), true), // Voter
+        ];
+        if let Some(claim_id) = dao.disputes[0].claim_id {
+            accounts.push(AccountMeta::new(claim_address(&program_id, &dao_key, claim_id).0, false)); // Claim under appeal
+        }
+        let instruction = Instruction { program_id, accounts, data };
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[&payer, voter],
+            recent_blockhash,
+        );
+        let result = banks_client.process_transaction(transaction).await;
+        (result, dao.reload(&mut banks_client, program_id, dao_key, &[]).await)
+    }
+
+    // DAO with one open dispute decided by the given jurors
+    fn dao_with_jury(jurors: Vec<Pubkey>) -> Ledger {
+        let mut dispute = closed_dispute(Pubkey::new_unique(), DepositStatus::Held);
+        dispute.status = DisputeStatus::Open;
+        dispute.jurors = jurors;
+        let dao = HealthInsuranceDAO {
+            admin: Pubkey::new_unique(),
+            disputes: vec![dispute],
+            slash_margin_pct: 80,
+            jury_size: 2,
+            next_dispute_id: 1,
+            ..Default::default()
+        };
+        Ledger::new(dao, Vec::new())
+    }
+
+    #[tokio::test]
+    async fn test_dispute_policy_set_by_admin_and_applied() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let admin = Keypair::new();
+        let initiator = Keypair::new();
+        let mut members: Vec<Member> = (0..4).map(|_| member(Pubkey::new_unique(), 0, 0)).collect();
+        members.push(member(initiator.pubkey(), 0, 0));
+        let dao = Ledger::new(HealthInsuranceDAO { admin: admin.pubkey(), members, ..Default::default() }, Vec::new());
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, 0);
+        program_test.add_account(
+            initiator.pubkey(),
+            Account { lamports: 1_000_000_000, data: Vec::new(), owner: system_program::id(), executable: false, rent_epoch: 0 },
+        );
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let set = |signer: &Keypair, instruction: DisputeInstruction| Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                 // DAO account
+                AccountMeta::new_readonly(signer.pubkey(), true), // Admin
+            ],
+            data: DaoInstruction::Dispute(instruction).try_to_vec().unwrap(),
+        };
+        let policy = [
+            DisputeInstruction::SetDisputeDeposit { deposit: 5_000, slash_margin_pct: 80 },
+            DisputeInstruction::SetOpenDisputeLimit { max_open_disputes: 3 },
+            DisputeInstruction::SetQuorumDecay { decay_secs: 86_400 },
+            DisputeInstruction::SetEarlyClose { enabled: true },
+            DisputeInstruction::SetParticipationPolicy { min_participation_pct: 50, penalty: 10 },
+            DisputeInstruction::SetJurySize { jury_size: 3 },
+        ];
+
+        // Members can't change the policy, and the admin can't set it out of range
+        let transaction = Transaction::new_signed_with_payer(&[set(&initiator, policy[0].clone())], Some(&payer.pubkey()), &[&payer, &initiator], recent_blockhash);
+        assert_eq!(
+            banks_client.process_transaction(transaction).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::Unauthorized as u32)),
+        );
+        for invalid in [
+            DisputeInstruction::SetDisputeDeposit { deposit: 5_000, slash_margin_pct: 40 },
+            DisputeInstruction::SetQuorumDecay { decay_secs: -1 },
+            DisputeInstruction::SetParticipationPolicy { min_participation_pct: 101, penalty: 10 },
+            DisputeInstruction::SetJurySize { jury_size: 4 }, // Only three members besides the parties
+        ] {
+            let transaction = Transaction::new_signed_with_payer(&[set(&admin, invalid)], Some(&payer.pubkey()), &[&payer, &admin], recent_blockhash);
+            assert_eq!(
+                banks_client.process_transaction(transaction).await.unwrap_err().unwrap(),
+                TransactionError::InstructionError(0, InstructionError::InvalidArgument),
+            );
+        }
+
+        let instructions: Vec<Instruction> = policy.into_iter().map(|instruction| set(&admin, instruction)).collect();
+        let transaction = Transaction::new_signed_with_payer(&instructions, Some(&payer.pubkey()), &[&payer, &admin], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+        let after = dao.reload(&mut banks_client, program_id, dao_key, &[]).await;
+        assert_eq!((after.dispute_deposit, after.slash_margin_pct), (5_000, 80));
+        assert_eq!(after.max_open_disputes, 3);
+        assert_eq!(after.quorum_decay_secs, 86_400);
+        assert!(after.early_close_enabled);
+        assert_eq!((after.min_participation_pct, after.participation_penalty), (50, 10));
+        assert_eq!(after.jury_size, 3);
+
+        // A new dispute escrows the configured deposit and is handed to a jury of the configured size
+        let lamports_before = banks_client.get_balance(dao_key).await.unwrap();
+        let submit = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                       // DAO account
+                AccountMeta::new(initiator.pubkey(), true),             // Initiator, paying the deposit
+                AccountMeta::new_readonly(after.members[0].member_address, false), // Respondent
+                AccountMeta::new_readonly(system_program::id(), false), // System program
+            ],
+            data: DaoInstruction::Dispute(DisputeInstruction::SubmitDispute { description: "Claim denied without review".to_string() }).try_to_vec().unwrap(),
+        };
+        let transaction = Transaction::new_signed_with_payer(&[submit], Some(&payer.pubkey()), &[&payer, &initiator], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+        assert_eq!(banks_client.get_balance(dao_key).await.unwrap(), lamports_before + 5_000);
+        let after = dao.reload(&mut banks_client, program_id, dao_key, &[]).await;
+        assert_eq!(after.disputes[0].deposit, 5_000);
+        assert_eq!(after.disputes[0].jurors.len(), 3);
+        assert!(!after.disputes[0].jurors.contains(&initiator.pubkey()));
+    }
+
+    #[tokio::test]
+    async fn test_juror_can_vote() {
+        let juror = Keypair::new();
+        let mut dao = dao_with_jury(vec![juror.pubkey(), Pubkey::new_unique()]);
+        dao.members = vec![member(juror.pubkey(), 0, 0)];
+
+        let (result, dao) = cast_vote(&dao, &juror).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.disputes[0].votes, vec![(juror.pubkey(), true)]);
+    }
+
+    #[tokio::test]
+    async fn test_dispute_parties_cannot_vote() {
+        let initiator = Keypair::new();
+        let respondent = Keypair::new();
+        let third_party = Keypair::new();
+        let mut dao = dao_with_jury(Vec::new());
+        dao.disputes[0].initiator = initiator.pubkey();
+        dao.disputes[0].respondent = respondent.pubkey();
+        dao.members = [&initiator, &respondent, &third_party].iter().map(|k| member(k.pubkey(), 0, 0)).collect();
+
+        for party in [&initiator, &respondent] {
+            let (result, dao) = cast_vote(&dao, party).await;
+            assert_eq!(
+                result.unwrap_err().unwrap(),
+                TransactionError::InstructionError(0, InstructionError::Custom(DaoError::ConflictOfInterest as u32)),
+            );
+            assert!(dao.disputes[0].votes.is_empty());
+        }
+
+        // Any other member may still vote on an open dispute
+        let (result, dao) = cast_vote(&dao, &third_party).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.disputes[0].votes, vec![(third_party.pubkey(), true)]);
+    }
+
+    #[tokio::test]
+    async fn test_non_member_cannot_vote() {
+        let outsider = Keypair::new();
+        let dao = dao_with_jury(Vec::new());
+
+        let (result, dao) = cast_vote(&dao, &outsider).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::Unauthorized as u32)),
+        );
+        assert!(dao.disputes[0].votes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_non_juror_cannot_vote() {
+        let outsider = Keypair::new();
+        let mut dao = dao_with_jury(vec![Pubkey::new_unique(), Pubkey::new_unique()]);
+        dao.members = vec![member(outsider.pubkey(), 0, 0)];
+
+        let (result, dao) = cast_vote(&dao, &outsider).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::NotOnJury as u32)),
+        );
+        assert!(dao.disputes[0].votes.is_empty());
+    }
+
+    // An open dispute filed by a fresh initiator
+    fn open_dispute() -> Dispute {
+        let mut dispute = closed_dispute(Pubkey::new_unique(), DepositStatus::Held);
+        dispute.status = DisputeStatus::Open;
+        dispute
+    }
+
+    #[test]
+    fn test_open_dispute_cap() {
+        let mut disputes = vec![open_dispute(), open_dispute()];
+        assert!(!open_dispute_limit_reached(&disputes, 3));
+
+        // Filling the cap blocks further submissions
+        disputes.push(open_dispute());
+        assert!(open_dispute_limit_reached(&disputes, 3));
+        assert!(!open_dispute_limit_reached(&disputes, 0)); // No cap configured
+
+        // Closing a dispute frees a slot
+        disputes[0].status = DisputeStatus::Closed;
+        assert!(!open_dispute_limit_reached(&disputes, 3));
+    }
+
+    #[tokio::test]
+    async fn test_submission_rejected_at_open_dispute_cap() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let initiator = Keypair::new();
+        let dao = HealthInsuranceDAO {
+            admin: Pubkey::new_unique(),
+            members: vec![member(initiator.pubkey(), 0, 0)],
+            disputes: vec![open_dispute(), open_dispute()],
+            slash_margin_pct: 80,
+            max_open_disputes: 2,
+            next_dispute_id: 2,
+            ..Default::default()
+        };
+        let data = dao.try_to_vec().unwrap();
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        program_test.add_account(
+            dao_key,
+            Account {
+                lamports: solana_program::rent::Rent::default().minimum_balance(data.len()),
+                data,
+                owner: program_id,
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let description = "Claim denied without review".to_string();
+        let data = DaoInstruction::Dispute(DisputeInstruction::SubmitDispute { description }).try_to_vec().unwrap();
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                       // DAO account
+                AccountMeta::new_readonly(initiator.pubkey(), true),    // Initiator
+                AccountMeta::new_readonly(Pubkey::new_unique(), false), // Respondent
+            ],
+            data,
+        };
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[&payer, &initiator],
+            recent_blockhash,
+        );
+        assert!(banks_client.process_transaction(transaction).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unsigned_dispute_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let initiator = Pubkey::new_unique();
+        let dao = HealthInsuranceDAO {
+            admin: Pubkey::new_unique(),
+            members: vec![member(initiator, 0, 0)],
+            ..Default::default()
+        };
+        let data = dao.try_to_vec().unwrap();
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        program_test.add_account(
+            dao_key,
+            Account {
+                lamports: solana_program::rent::Rent::default().minimum_balance(data.len()),
+                data,
+                owner: program_id,
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        // Without a deposit there's no transfer to demand the signature, so the handler must check it itself
+        let description = "Filed in someone else's name".to_string();
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                       // DAO account
+                AccountMeta::new_readonly(initiator, false),            // Initiator, not signing
+                AccountMeta::new_readonly(Pubkey::new_unique(), false), // Respondent
+            ],
+            data: DaoInstruction::Dispute(DisputeInstruction::SubmitDispute { description }).try_to_vec().unwrap(),
+        };
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer], recent_blockhash);
+        assert_eq!(
+            banks_client.process_transaction(transaction).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::MissingRequiredSignature),
+        );
+    }
+
+    // Attempt to close dispute 0 under its decayed quorum at `unix_timestamp`, returning the result and final state
+    async fn close_stale(dao: &HealthInsuranceDAO, unix_timestamp: i64) -> (Result<(), BanksClientError>, HealthInsuranceDAO) {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let data = dao.try_to_vec().unwrap();
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        program_test.add_account(
+            dao_key,
+            Account {
+                lamports: solana_program::rent::Rent::default().minimum_balance(data.len()),
+                data,
+                owner: program_id,
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+        let mut context = program_test.start_with_context().await;
+        context.set_sysvar(&Clock { unix_timestamp, ..Clock::default() });
+
+        let data = DaoInstruction::Dispute(DisputeInstruction::CloseStaleDispute { dispute_index: 0 }).try_to_vec().unwrap();
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![AccountMeta::new(dao_key, false)], // DAO account
+            data,
+        };
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        let result = context.banks_client.process_transaction(transaction).await;
+
+        let account = context.banks_client.get_account(dao_key).await.unwrap().unwrap();
+        (result, HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap())
+    }
+
+    // DAO with one open dispute holding two supporting votes, whose quorum decays by a vote per day
+    fn dao_with_stale_dispute() -> HealthInsuranceDAO {
+        let mut dispute = open_dispute();
+        dispute.votes = vec![(Pubkey::new_unique(), true), (Pubkey::new_unique(), true)];
+        HealthInsuranceDAO {
+            admin: Pubkey::new_unique(),
+            disputes: vec![dispute],
+            slash_margin_pct: 80,
+            quorum_decay_secs: 86_400,
+            next_dispute_id: 1,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_slashed_deposit_credited_only_to_dao_account_treasury() {
+        let program_id = Pubkey::new_unique();
+        let dao_keys = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut dispute = open_dispute();
+        dispute.deposit = 1_000_000;
+        dispute.votes = (0..BASE_QUORUM).map(|_| (Pubkey::new_unique(), false)).collect(); // A unanimous loss
+
+        // One DAO holds its treasury in the DAO account, the other pays from a separate wallet
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        for (dao_key, treasury_account) in [(dao_keys[0], dao_keys[0]), (dao_keys[1], Pubkey::new_unique())] {
+            let dao = HealthInsuranceDAO {
+                admin: Pubkey::new_unique(),
+                disputes: vec![dispute.clone()],
+                slash_margin_pct: 80,
+                next_dispute_id: 1,
+                treasury_account,
+                treasury: Treasury { balance: 5_000_000, ..Default::default() },
+                ..Default::default()
+            };
+            Ledger::new(dao, Vec::new()).add_accounts(&mut program_test, program_id, dao_key, dispute.deposit);
+        }
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let close = |dao_key: Pubkey| Instruction {
+            program_id,
+            accounts: vec![AccountMeta::new(dao_key, false)], // DAO account
+            data: DaoInstruction::Dispute(DisputeInstruction::CloseStaleDispute { dispute_index: 0 }).try_to_vec().unwrap(),
+        };
+        let transaction = Transaction::new_signed_with_payer(&[close(dao_keys[0]), close(dao_keys[1])], Some(&payer.pubkey()), &[&payer], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let mut balances = Vec::new();
+        for dao_key in dao_keys {
+            let account = banks_client.get_account(dao_key).await.unwrap().unwrap();
+            let dao = HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap();
+            assert_eq!(dao.disputes[0].deposit_status, DepositStatus::Slashed);
+            balances.push(dao.treasury.balance);
+        }
+        assert_eq!(balances, vec![6_000_000, 5_000_000]);
+    }
+
+    #[tokio::test]
+    async fn test_stale_dispute_fails_initial_quorum() {
+        let (result, dao) = close_stale(&dao_with_stale_dispute(), 86_400).await;
+        assert!(result.is_err()); // Quorum has only decayed to 5
+        assert_eq!(dao.disputes[0].status, DisputeStatus::Open);
+    }
+
+    #[tokio::test]
+    async fn test_stale_dispute_closes_under_decayed_quorum() {
+        let (result, dao) = close_stale(&dao_with_stale_dispute(), 4 * 86_400).await;
+        assert!(result.is_ok()); // Quorum has decayed to the 2 votes cast
+        assert_eq!(dao.disputes[0].status, DisputeStatus::Closed);
+        assert_eq!(dao.disputes[0].deposit_status, DepositStatus::Refundable);
+    }
+
+    #[test]
+    fn test_quorum_never_decays_below_one() {
+        let dispute = open_dispute();
+        assert_eq!(effective_quorum(&dispute, 0, 86_400), BASE_QUORUM);
+        assert_eq!(effective_quorum(&dispute, 365 * 86_400, 86_400), 1);
+        assert_eq!(effective_quorum(&dispute, 365 * 86_400, 0), BASE_QUORUM); // Decay disabled
+    }
+
+    #[test]
+    fn test_dispute_ids_never_collide_after_removal() {
+        let mut next_dispute_id = 0;
+        let mut disputes: Vec<Dispute> = (0..3)
+            .map(|_| Dispute { dispute_id: assign_dispute_id(&mut next_dispute_id).unwrap(), ..open_dispute() })
+            .collect();
+
+        // Removing an entry shrinks the vector but not the counter
+        disputes.remove(1);
+        let new_id = assign_dispute_id(&mut next_dispute_id).unwrap();
+        assert_eq!(new_id, 3);
+        assert_ne!(new_id, disputes.len() as u64);
+        assert!(!disputes.iter().any(|d| d.dispute_id == new_id));
+        assert_eq!(next_dispute_id, 4);
+    }
+
+    #[test]
+    fn test_dispute_id_overflow_rejected() {
+        let mut next_dispute_id = u64::MAX;
+        assert!(assign_dispute_id(&mut next_dispute_id).is_err());
+        assert_eq!(next_dispute_id, u64::MAX);
+    }
+
+    #[test]
+    fn test_outcome_decided_by_majority_of_eligible_voters() {
+        let votes = |agree: usize, disagree: usize| -> Vec<(Pubkey, bool)> {
+            (0..agree).map(|_| (Pubkey::new_unique(), true)).chain((0..disagree).map(|_| (Pubkey::new_unique(), false))).collect()
+        };
+        assert!(!outcome_decided(&votes(2, 1), 5)); // Remaining two voters could still tie or flip it
+        assert!(outcome_decided(&votes(3, 0), 5));
+        assert!(outcome_decided(&votes(0, 3), 5));
+        assert!(!outcome_decided(&votes(5, 5), 20));
+    }
+
+    #[tokio::test]
+    async fn test_dispute_closes_early_once_decided() {
+        let voter = Keypair::new();
+        let mut dao = dao_with_jury(Vec::new());
+        dao.jury_size = 0;
+        dao.early_close_enabled = true;
+
+        // Five eligible voters; two have already sided with the initiator
+        let mut members: Vec<Member> = (0..4).map(|_| member(Pubkey::new_unique(), 0, 1)).collect();
+        members.push(member(voter.pubkey(), 0, 1));
+        dao.disputes[0].votes = vec![(members[0].member_address, true), (members[1].member_address, true)];
+        dao.members = members;
+
+        // A third supporting vote is a majority of all eligible voters, well short of the base quorum
+        let (result, dao) = cast_vote(&dao, &voter).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.disputes[0].status, DisputeStatus::Closed);
+        assert_eq!(dao.disputes[0].deposit_status, DepositStatus::Refundable);
+    }
+
+    #[tokio::test]
+    async fn test_upheld_appeal_returns_claim_to_pending() {
+        let voter = Keypair::new();
+        let mut dao = dao_with_jury(Vec::new());
+        dao.jury_size = 0;
+        dao.early_close_enabled = true;
+        dao.disputes[0].claim_id = Some(7);
+        dao.claims = vec![Claim { claim_id: 7, status: ClaimStatus::Appealed, appealed: true, ..Default::default() }];
+
+        // Two of three eligible voters have sided with the claimant once the vote lands
+        let mut members: Vec<Member> = (0..2).map(|_| member(Pubkey::new_unique(), 0, 1)).collect();
+        members.push(member(voter.pubkey(), 0, 1));
+        dao.disputes[0].votes = vec![(members[0].member_address, true)];
+        dao.members = members;
+
+        let (result, dao) = cast_vote(&dao, &voter).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.disputes[0].status, DisputeStatus::Closed);
+        assert_eq!(dao.claims[0].status, ClaimStatus::Pending);
+    }
+
+    #[test]
+    fn test_whale_vote_weight_capped() {
+        let whale = member(Pubkey::new_unique(), 0, 0);
+        let small = [member(Pubkey::new_unique(), 0, 0), member(Pubkey::new_unique(), 0, 0)];
+        let members = vec![Member { reputation: 1_000, ..whale.clone() }, small[0].clone(), small[1].clone()];
+        let votes = vec![(whale.member_address, false), (small[0].member_address, true), (small[1].member_address, true)];
+
+        // Raw weight 1_200, so a 20% cap limits the whale to 240 while the 100-reputation voters keep their full weight
+        let capped = |cap_pct| VoteWeighting { cap_pct, max_tenure_weight: 0, now: 0 };
+        assert_eq!(tally_votes(&votes, &members, &capped(20)), (200, 440));
+        // Uncapped reputation lets the whale outvote everyone else
+        assert_eq!(tally_votes(&votes, &members, &capped(100)), (200, 1_200));
+        // Without weighting each member counts once
+        assert_eq!(tally_votes(&votes, &members, &capped(0)), (2, 3));
+    }
+
+    #[test]
+    fn test_votes_without_reputation_counted_by_head() {
+        let voters: Vec<Member> = (0..3).map(|_| Member { reputation: 0, ..member(Pubkey::new_unique(), 0, 0) }).collect();
+        let votes = vec![(voters[0].member_address, true), (voters[1].member_address, true), (voters[2].member_address, false)];
+
+        // Nobody has reputation to weight by, so the majority still carries the dispute
+        let weighting = VoteWeighting { cap_pct: 50, max_tenure_weight: 0, now: 0 };
+        assert_eq!(tally_votes(&votes, &voters, &weighting), (2, 3));
+    }
+
+    #[tokio::test]
+    async fn test_reputation_weighted_appeal_with_joined_members() {
+        let dao_key = Pubkey::new_unique();
+        let jurors = [Keypair::new(), Keypair::new()];
+        let (mut banks_client, _, _) = start_with_joined_members(Pubkey::new_unique(), dao_key, &HealthInsuranceDAO::default(), &jurors).await;
+        let joined = load_dao(&mut banks_client, dao_key).await.members;
+
+        // Both jurors, with the reputation they joined with, side with the claimant under a 50% weight cap
+        let mut dao = dao_with_jury(jurors.iter().map(|j| j.pubkey()).collect());
+        dao.members = joined;
+        dao.vote_weight_cap_pct = 50;
+        dao.disputes[0].claim_id = Some(7);
+        dao.disputes[0].votes = vec![(jurors[0].pubkey(), true)];
+        dao.claims = vec![Claim { claim_id: 7, status: ClaimStatus::Appealed, appealed: true, ..Default::default() }];
+
+        let (result, dao) = cast_vote(&dao, &jurors[1]).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.disputes[0].status, DisputeStatus::Closed);
+        assert_eq!(dao.claims[0].status, ClaimStatus::Pending);
+    }
+
+    #[test]
+    fn test_tenure_weight_capped() {
+        let period = TENURE_WEIGHT_PERIOD_SECS;
+        assert_eq!(tenure_weight(0, 0, 5), 1);
+        assert_eq!(tenure_weight(0, 2 * period, 5), 3);
+        assert_eq!(tenure_weight(0, 40 * period, 5), 5);
+        assert_eq!(tenure_weight(10 * period, 0, 5), 1); // Joined after `now`
+        assert_eq!(tenure_weight(0, 40 * period, 0), 1); // Weighting disabled
+    }
+
+    #[test]
+    fn test_long_tenured_votes_outweigh_new_members() {
+        let now = 10 * TENURE_WEIGHT_PERIOD_SECS;
+        let veterans: Vec<Member> = (0..2).map(|_| member(Pubkey::new_unique(), 0, 0)).collect();
+        let newcomers: Vec<Member> = (0..5).map(|_| Member { joined_timestamp: now, ..member(Pubkey::new_unique(), 0, 0) }).collect();
+        let votes: Vec<(Pubkey, bool)> = veterans
+            .iter()
+            .map(|m| (m.member_address, false))
+            .chain(newcomers.iter().map(|m| (m.member_address, true)))
+            .collect();
+        let members: Vec<Member> = veterans.into_iter().chain(newcomers).collect();
+
+        // Two veterans at the cap of 8 votes each outweigh five members who have only just joined
+        let weighting = VoteWeighting { cap_pct: 0, max_tenure_weight: 8, now };
+        assert_eq!(tally_votes(&votes, &members, &weighting), (5, 21));
+        // Counting heads, the newcomers would carry it
+        assert_eq!(tally_votes(&votes, &members, &VoteWeighting { max_tenure_weight: 0, ..weighting }), (5, 7));
+    }
+
+    #[tokio::test]
+    async fn test_tenure_weighted_vote_decides_appeal() {
+        let voter = Keypair::new();
+        let mut dao = dao_with_jury(Vec::new());
+        dao.jury_size = 0;
+        dao.max_tenure_weight = 8;
+        dao.disputes[0].claim_id = Some(7);
+        dao.claims = vec![Claim { claim_id: 7, status: ClaimStatus::Appealed, appealed: true, ..Default::default() }];
+
+        // Newcomers join at the test clock's start, veterans at the epoch
+        let joined_now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() as i64;
+        let newcomer = |address| Member { joined_timestamp: joined_now, ..member(address, 0, 1) };
+        let mut members: Vec<Member> = (0..2).map(|_| member(Pubkey::new_unique(), 0, 1)).collect();
+        members.extend((0..3).map(|_| newcomer(Pubkey::new_unique())));
+        members.push(newcomer(voter.pubkey()));
+        dao.disputes[0].votes = vec![
+            (members[0].member_address, false),
+            (members[1].member_address, false),
+            (members[2].member_address, true),
+            (members[3].member_address, true),
+            (members[4].member_address, true),
+        ];
+        dao.members = members;
+
+        // Four supporting newcomers against two veterans: the veterans' weight leaves the rejection standing
+        let (result, dao) = cast_vote(&dao, &voter).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.disputes[0].status, DisputeStatus::Closed);
+        assert_eq!(dao.claims[0].status, ClaimStatus::Rejected);
+    }
+}
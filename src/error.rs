@@ -0,0 +1,186 @@
+use solana_program::program_error::ProgramError;
+
+// Program-specific failures, surfaced to clients as `ProgramError::Custom(code)`.
+// Codes are the variant's position in this list, so new variants must be appended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DaoError {
+    ClaimNotFound,           // No claim at the given index
+    ClaimAlreadyVerified,    // Claim has already been verified, paid, or rejected
+    ClaimNotVerified,        // Claim must be verified before it can be paid
+    ClaimAlreadyPaid,        // Claim has already been paid out
+    CoverageExceeded,        // Claim amount is above the member's coverage limit
+    InsufficientReserve,     // Paying the claim would dip into the treasury's required reserve
+    AlreadyMember,           // Account is already a member of the DAO
+    NotAMember,              // Account is not a member of the DAO
+    DuplicateVote,           // Voter has already reviewed this claim or voted on this dispute
+    DisputeNotFound,         // No dispute at the given index
+    DisputeClosed,           // Dispute has already been resolved
+    Unauthorized,            // Signer lacks the role the instruction requires
+    SelfVerification,        // Verifier is the claimant on the claim under review
+    ClaimAlreadyAppealed,    // Claim's rejection has already been appealed once
+    ClaimWindowExpired,      // Service date is in the future or older than the claim window allows
+    DuplicateClaim,          // Member already has an open claim for the same provider, service date, and amount
+    ServiceCapExceeded,      // Claim amount is above the cap for its service type
+    BatchTooLarge,           // Batch lists more claims than one instruction may process
+    InvalidProof,            // Claim's zero-knowledge proof failed verification
+    ProgramPaused,           // DAO is paused and only accepts read-only instructions and unpausing
+    BadNonce,                // Instruction's nonce doesn't match the member's stored nonce
+    PendingClaimsExist,      // Member still has claims awaiting review, payout, or an appeal
+    PremiumUnderpaid,        // Premium payment is below the member's quoted premium
+    CoverageLapsed,          // Member's last premium payment is older than one period plus the grace period
+    EmergencyMode,           // Treasury is in emergency mode and not accepting high-risk claims
+    PeriodCapReached,        // DAO has accepted as many claims as its per-period cap allows
+    AnnualClaimCapReached,   // Member has filed as many claims this year as the annual cap allows
+    ConflictOfInterest,      // Voter is conflicted: a verifier tied to the claim or a party to the dispute
+    MemberSuspended,         // Member is suspended for fraud and cannot submit claims
+    NotOnJury,               // Voter is not among the jurors selected for the dispute
+    OpenDisputeLimitReached, // DAO already has the maximum number of open disputes
+    ProposalAlreadyExecuted, // Multi-sig proposal has already been executed
+    InsufficientApprovals,   // Multi-sig proposal has fewer approvals than its threshold
+    LastAdmin,               // Demotion would leave the DAO without an admin
+    SingleClaimCapExceeded,  // Payout is above the share of the treasury any single claim may take
+    RiskBandUnderfunded,     // Member's risk band has too little above its reserve to cover the payout
+    JuryUnavailable,         // Too few members with standing, besides the parties, to draw the configured jury
+    VerifyingKeyNotSet,      // Admin hasn't stored the claim circuit's verifying key, so no proof can be checked
+    InsufficientStake,       // Verifier's stake is below the DAO's stake requirement
+    VerifierPairingLimit,    // Verifier has reviewed the provider's claims as often as verifier rotation allows
+    NotAssignedVerifier,     // Verifier isn't among those assigned to review the appealed claim
+    VerificationCooling,     // Claim is still in its cooling period and can't be verified yet
+    VerifierLimitReached,    // Claim already has the maximum number of reviewers
+    PreAuthRequired,         // Claim needs a pre-authorization but none was given
+    PreAuthInvalid,          // Pre-authorization is unknown, unapproved, used, expired, or doesn't cover the claim
+    LegalHold,               // Member's records are under a legal hold and can't be deleted
+    ProposalNotFound,        // No multi-sig proposal at the given index
+    InvalidMultiSigThreshold, // Multi-sig threshold is zero or above the signer count, or a signer is listed twice
+}
+
+impl From<DaoError> for ProgramError {
+    fn from(error: DaoError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
+
+impl TryFrom<u32> for DaoError {
+    type Error = ProgramError;
+
+    // Recover the variant from a custom error code returned by the program
+    fn try_from(code: u32) -> Result<Self, Self::Error> {
+        match code {
+            0 => Ok(DaoError::ClaimNotFound),
+            1 => Ok(DaoError::ClaimAlreadyVerified),
+            2 => Ok(DaoError::ClaimNotVerified),
+            3 => Ok(DaoError::ClaimAlreadyPaid),
+            4 => Ok(DaoError::CoverageExceeded),
+            5 => Ok(DaoError::InsufficientReserve),
+            6 => Ok(DaoError::AlreadyMember),
+            7 => Ok(DaoError::NotAMember),
+            8 => Ok(DaoError::DuplicateVote),
+            9 => Ok(DaoError::DisputeNotFound),
+            10 => Ok(DaoError::DisputeClosed),
+            11 => Ok(DaoError::Unauthorized),
+            12 => Ok(DaoError::SelfVerification),
+            13 => Ok(DaoError::ClaimAlreadyAppealed),
+            14 => Ok(DaoError::ClaimWindowExpired),
+            15 => Ok(DaoError::DuplicateClaim),
+            16 => Ok(DaoError::ServiceCapExceeded),
+            17 => Ok(DaoError::BatchTooLarge),
+            18 => Ok(DaoError::InvalidProof),
+            19 => Ok(DaoError::ProgramPaused),
+            20 => Ok(DaoError::BadNonce),
+            21 => Ok(DaoError::PendingClaimsExist),
+            22 => Ok(DaoError::PremiumUnderpaid),
+            23 => Ok(DaoError::CoverageLapsed),
+            24 => Ok(DaoError::EmergencyMode),
+            25 => Ok(DaoError::PeriodCapReached),
+            26 => Ok(DaoError::AnnualClaimCapReached),
+            27 => Ok(DaoError::ConflictOfInterest),
+            28 => Ok(DaoError::MemberSuspended),
+            29 => Ok(DaoError::NotOnJury),
+            30 => Ok(DaoError::OpenDisputeLimitReached),
+            31 => Ok(DaoError::ProposalAlreadyExecuted),
+            32 => Ok(DaoError::InsufficientApprovals),
+            33 => Ok(DaoError::LastAdmin),
+            34 => Ok(DaoError::SingleClaimCapExceeded),
+            35 => Ok(DaoError::RiskBandUnderfunded),
+            36 => Ok(DaoError::JuryUnavailable),
+            37 => Ok(DaoError::VerifyingKeyNotSet),
+            38 => Ok(DaoError::InsufficientStake),
+            39 => Ok(DaoError::VerifierPairingLimit),
+            40 => Ok(DaoError::NotAssignedVerifier),
+            41 => Ok(DaoError::VerificationCooling),
+            42 => Ok(DaoError::VerifierLimitReached),
+            43 => Ok(DaoError::PreAuthRequired),
+            44 => Ok(DaoError::PreAuthInvalid),
+            45 => Ok(DaoError::LegalHold),
+            46 => Ok(DaoError::ProposalNotFound),
+            47 => Ok(DaoError::InvalidMultiSigThreshold),
+            _ => Err(ProgramError::InvalidArgument),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_error_code_decodes_to_variant() {
+        let variants = [
+            DaoError::ClaimNotFound,
+            DaoError::ClaimAlreadyVerified,
+            DaoError::ClaimNotVerified,
+            DaoError::ClaimAlreadyPaid,
+            DaoError::CoverageExceeded,
+            DaoError::InsufficientReserve,
+            DaoError::AlreadyMember,
+            DaoError::NotAMember,
+            DaoError::DuplicateVote,
+            DaoError::DisputeNotFound,
+            DaoError::DisputeClosed,
+            DaoError::Unauthorized,
+            DaoError::SelfVerification,
+            DaoError::ClaimAlreadyAppealed,
+            DaoError::ClaimWindowExpired,
+            DaoError::DuplicateClaim,
+            DaoError::ServiceCapExceeded,
+            DaoError::BatchTooLarge,
+            DaoError::InvalidProof,
+            DaoError::ProgramPaused,
+            DaoError::BadNonce,
+            DaoError::PendingClaimsExist,
+            DaoError::PremiumUnderpaid,
+            DaoError::CoverageLapsed,
+            DaoError::EmergencyMode,
+            DaoError::PeriodCapReached,
+            DaoError::AnnualClaimCapReached,
+            DaoError::ConflictOfInterest,
+            DaoError::MemberSuspended,
+            DaoError::NotOnJury,
+            DaoError::OpenDisputeLimitReached,
+            DaoError::ProposalAlreadyExecuted,
+            DaoError::InsufficientApprovals,
+            DaoError::LastAdmin,
+            DaoError::SingleClaimCapExceeded,
+            DaoError::RiskBandUnderfunded,
+            DaoError::JuryUnavailable,
+            DaoError::VerifyingKeyNotSet,
+            DaoError::InsufficientStake,
+            DaoError::VerifierPairingLimit,
+            DaoError::NotAssignedVerifier,
+            DaoError::VerificationCooling,
+            DaoError::VerifierLimitReached,
+            DaoError::PreAuthRequired,
+            DaoError::PreAuthInvalid,
+            DaoError::LegalHold,
+            DaoError::ProposalNotFound,
+            DaoError::InvalidMultiSigThreshold,
+        ];
+        for variant in variants {
+            match ProgramError::from(variant) {
+                ProgramError::Custom(code) => assert_eq!(DaoError::try_from(code).unwrap(), variant),
+                other => panic!("{:?} mapped to {:?} instead of a custom error", variant, other),
+            }
+        }
+        assert!(DaoError::try_from(variants.len() as u32).is_err());
+    }
+}
@@ -0,0 +1,4396 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    log::sol_log_data,
+    msg,
+    program::{invoke, invoke_signed, set_return_data},
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    system_instruction, system_program,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use crate::{
+    is_admin, require_role, require_signed_role, Claim, ClaimShare, ClaimStatus, HealthInsuranceDAO, Member, MemberTier, PaymentPlan, RiskBand, RiskProfile, Role, RoundingMode,
+    ServiceType, Treasury, VOLATILITY_WINDOW,
+};
+use crate::claims;
+use crate::error::DaoError;
+use crate::instruction::FinanceInstruction;
+use crate::parse::{read_i64, read_u64};
+use crate::security;
+
+// Reserve ratio applied when the treasury is initialized without an explicit ratio, in basis points
+const DEFAULT_RESERVE_RATIO_BPS: u16 = 2_000;
+
+// Wellness check-ins count toward a member's streak at most once per interval
+const HEARTBEAT_INTERVAL_SECS: i64 = 7 * 24 * 60 * 60;
+
+// Consecutive check-ins needed for each point of risk reduction, and the most the streak can take off
+const HEARTBEATS_PER_RISK_POINT: u32 = 4;
+const MAX_HEARTBEAT_RISK_REDUCTION: u8 = 10;
+
+// Length of the coverage year over which paid claims count against a member's coverage limit
+const COVERAGE_PERIOD_SECS: i64 = 365 * 24 * 60 * 60;
+
+// Claim-history risk scoring: a neutral starting score, then points per paid claim, per 10% of premiums paid back
+// out in claims, and off per year of membership, each capped
+const BASE_HISTORY_RISK_SCORE: u8 = 40;
+const RISK_POINTS_PER_PAID_CLAIM: u32 = 5;
+const MAX_CLAIM_COUNT_RISK_POINTS: u32 = 30;
+const MAX_LOSS_RATIO_RISK_POINTS: u64 = 30;
+const RISK_POINTS_OFF_PER_TENURE_YEAR: i64 = 5;
+const MAX_TENURE_RISK_REDUCTION: i64 = 20;
+
+// PDA seed for the system-owned vault holding the treasury's yield allocation
+const YIELD_VAULT_SEED: &[u8] = b"yield_vault";
+
+// PDA seed for the authority holding the treasury's SPL token account
+const TREASURY_AUTHORITY_SEED: &[u8] = b"treasury_authority";
+
+// Premium, coverage, and deductible associated with a tier
+#[derive(Debug, Clone, PartialEq)]
+pub struct TierBenefits {
+    pub premium: u64, // Premium due per period, in lamports
+    pub coverage_limit: u64, // Maximum claim amount, in lamports
+    pub deductible: u64, // Amount of each claim the member covers themselves, in lamports
+    pub coinsurance_bps: u16, // Member's share of each claim after the deductible, in basis points
+}
+
+// Treasury value across its token vaults, returned by the valuation instruction
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct TreasuryValuation {
+    pub total: u64, // Combined value of every vault, in the base unit
+    pub vault_values: Vec<u64>, // Value of each vault in registration order, in the base unit
+}
+
+// What a claim would pay, returned to members before they submit
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct PayoutEstimate {
+    pub payable: u64, // Amount the treasury would pay, in lamports (0 when the claim exceeds coverage)
+    pub member_share: u64, // Deductible and coinsurance the member would cover, in lamports
+    pub within_coverage: bool, // Whether the claim amount fits within the member's coverage limit
+}
+
+// Point-in-time view of the treasury's ability to cover outstanding claims
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolvencyMetrics {
+    pub balance: u64, // Current balance of the treasury in lamports
+    pub required_reserve: u64, // Portion of the balance that must be held back under the reserve ratio
+    pub available_for_claims: u64, // Balance left over for payouts once the reserve is held back
+    pub open_claim_exposure: u64, // Total amount of submitted claims that haven't been paid yet
+    pub solvency_ratio_bps: u64, // Available funds relative to open exposure, in basis points (u64::MAX with no exposure)
+}
+
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction: FinanceInstruction,
+) -> ProgramResult {
+    // Fail early with a clear message when the client supplies too few accounts for the instruction
+    let expected = expected_account_count(&instruction);
+    if accounts.len() < expected {
+        msg!("Instruction {:?} expects at least {} accounts but received {}", instruction, expected, accounts.len());
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let accounts_iter = &mut accounts.iter();
+    let account = next_account_info(accounts_iter)?;
+
+    // Verify that this program owns the account we're about to modify
+    if account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Accounts in the legacy layout can't be loaded, so their migration works on the raw data and never saves this state
+    let mut dao_data = match instruction {
+        FinanceInstruction::MigrateReserveRatio => HealthInsuranceDAO::default(),
+        _ => HealthInsuranceDAO::load(account)?,
+    };
+
+    // Only read-only instructions run while the DAO is paused
+    if !allowed_while_paused(&instruction) {
+        dao_data.ensure_active()?;
+    }
+
+    match instruction {
+        // ... existing instructions ...
+
+        FinanceInstruction::PayPremium { amount, nonce } => {
+            // Premium Payment - This instruction handles the payment of insurance premiums by members
+            let payer = next_account_info(accounts_iter)?; // Account of the member paying the premium
+            if !payer.is_signer {
+                return Err(ProgramError::MissingRequiredSignature); // An unsigned premium would reinstate coverage for free
+            }
+            require_role(&dao_data, payer.key, Role::Member)?;
+            dao_data.consume_nonce(payer.key, nonce)?;
+            apply_pending_tier(&mut dao_data, payer.key)?; // The premium pays for a new period on the pending tier
+
+            // Members must pay at least their quoted premium
+            if let Some(member) = dao_data.members.iter().find(|m| m.member_address == *payer.key) {
+                let premium = premium_quote(&dao_data, member);
+                if amount < premium {
+                    msg!("Premium of {} lamports is below the quoted premium of {}", amount, premium);
+                    return Err(DaoError::PremiumUnderpaid.into());
+                }
+            }
+
+            collect_into_treasury(program_id, account, &dao_data, payer, amount, accounts_iter)?;
+
+            // Add the premium payment to the treasury balance, ensuring no arithmetic overflow
+            dao_data.treasury.balance = dao_data.treasury.balance.checked_add(amount).ok_or(ProgramError::ArithmeticOverflow)?;
+
+            credit_risk_band(&mut dao_data.risk_bands, &dao_data.risk_profiles, payer.key, amount)?;
+
+            // Attribute the contribution to the member and record the activity
+            if let Some(member) = dao_data.members.iter_mut().find(|m| m.member_address == *payer.key) {
+                member.premiums_paid = member.premiums_paid.checked_add(amount).ok_or(ProgramError::ArithmeticOverflow)?;
+                member.last_activity_at = Clock::get()?.unix_timestamp;
+                accrue_claim_free_period(member, member.last_activity_at, dao_data.premium_period_secs);
+                member.last_premium_ts = member.last_activity_at;
+                member.last_premium_amount = amount;
+                member.dormant = false;
+            }
+            msg!("Premium payment of {} lamports received", amount);
+        }
+
+        FinanceInstruction::PayoutClaim { claim_id, amount } => {
+            // Claim Payout - This instruction processes full or partial claim payouts based on risk assessment
+            let authority = next_account_info(accounts_iter)?; // Admin or verifier releasing the payout
+            let claim_account = next_account_info(accounts_iter)?; // PDA holding the claim being paid
+            if !authority.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if !is_admin(&dao_data, authority.key) {
+                require_role(&dao_data, authority.key, Role::Verifier)?; // Only admins and verifiers release treasury funds
+            }
+            let mut claim = claims::load_claim(program_id, account.key, claim_account, claim_id)?;
+            disburse_claim(program_id, account, &mut dao_data, &mut claim, amount, accounts_iter)?;
+            claims::save_claim(claim_account, &claim)?;
+        }
+
+        FinanceInstruction::UpdateRiskProfile { risk_score: new_risk_score, coverage_limit: new_coverage_limit } => {
+            // Update Risk Profile - This instruction updates or adds a member's risk profile
+            let member = next_account_info(accounts_iter)?; // Account of the member whose risk profile is being updated
+            let admin = next_account_info(accounts_iter)?; // Admin authorizing the update
+
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin can update risk profiles
+
+            // Check if the member already has a risk profile; members sharing a score keep separate profiles
+            if let Some(risk_profile) = dao_data.risk_profiles.iter_mut().find(|rp| rp.member == *member.key) {
+                // Lowering coverage needs the member's consent; raises only need the admin
+                if new_coverage_limit < risk_profile.coverage_limit && !member.is_signer {
+                    msg!("Lowering coverage for member {} requires their signature", member.key);
+                    return Err(ProgramError::MissingRequiredSignature);
+                }
+                risk_profile.risk_score = new_risk_score;
+                risk_profile.coverage_limit = new_coverage_limit;
+                msg!("Updated risk profile for member {}", member.key);
+            } else {
+                // If no existing profile, add a new one
+                dao_data.risk_profiles.push(RiskProfile {
+                    member: *member.key,
+                    risk_score: new_risk_score,
+                    coverage_limit: new_coverage_limit,
+                    deductible: 0,
+                    coinsurance_bps: 0,
+                    claims_paid: 0,
+                    utilization_bps: 0,
+                    used_coverage: 0,
+                    coverage_period_start: 0,
+                });
+                msg!("New risk profile added for member {}", member.key);
+            }
+        }
+
+        FinanceInstruction::SetReserveRatio { reserve_ratio_bps: new_reserve_ratio_bps } => {
+            // Adjust Treasury Reserve Ratio - This allows the admin to adjust the reserve policy
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin should adjust this
+
+            if new_reserve_ratio_bps > 10_000 {
+                return Err(ProgramError::InvalidArgument); // Reserve can't exceed 100% of the treasury
+            }
+            dao_data.treasury.reserve_ratio_bps = new_reserve_ratio_bps;
+            msg!("Treasury reserve ratio updated to {} bps", new_reserve_ratio_bps);
+        }
+
+        FinanceInstruction::SetSingleClaimCap { cap_bps: new_cap_bps } => {
+            // Adjust Single Claim Cap - This allows the admin to limit how much of the treasury one claim can take
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin should adjust this
+
+            if new_cap_bps > 10_000 {
+                return Err(ProgramError::InvalidArgument); // Cap can't exceed 100% of the treasury
+            }
+            dao_data.treasury.max_single_claim_bps = new_cap_bps;
+            msg!("Single claim cap updated to {} bps", new_cap_bps);
+        }
+
+        FinanceInstruction::InitializeTreasury { reserve_ratio_bps } => {
+            // Initialize Treasury - Sets the reserve policy before any payouts can be made
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin can initialize the treasury
+            if dao_data.treasury.initialized {
+                return Err(ProgramError::AccountAlreadyInitialized);
+            }
+
+            // The reserve ratio is optional; fall back to the default when none is supplied
+            let reserve_ratio_bps = reserve_ratio_bps.unwrap_or(DEFAULT_RESERVE_RATIO_BPS);
+            if reserve_ratio_bps == 0 || reserve_ratio_bps > 10_000 {
+                return Err(ProgramError::InvalidArgument); // Reserve ratio must be within (0, 10000] bps
+            }
+
+            dao_data.treasury.reserve_ratio_bps = reserve_ratio_bps;
+            dao_data.treasury.initialized = true;
+            msg!("Treasury initialized with reserve ratio {} bps", reserve_ratio_bps);
+        }
+
+        FinanceInstruction::GetSolvency => {
+            // Get Solvency - Read-only snapshot of the treasury's position, emitted as log data for off-chain consumers
+            let metrics = solvency_metrics(&dao_data.treasury, dao_data.open_claim_exposure);
+            sol_log_data(&[
+                &metrics.balance.to_le_bytes(),
+                &metrics.required_reserve.to_le_bytes(),
+                &metrics.available_for_claims.to_le_bytes(),
+                &metrics.open_claim_exposure.to_le_bytes(),
+                &metrics.solvency_ratio_bps.to_le_bytes(),
+            ]);
+            msg!("Solvency snapshot: {:?}", metrics);
+        }
+
+        FinanceInstruction::ChangeTier { tier: new_tier } => {
+            // Change Member Tier - Moves a member to a new tier and applies that tier's coverage and deductible
+            let member = next_account_info(accounts_iter)?; // Account of the member changing tier
+            if !member.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+
+            // A member part-way through a paid premium period keeps the tier they paid for, so coverage can't be raised
+            // just ahead of a claim; the change takes effect with their next premium
+            let record = dao_data.members.iter().find(|m| m.member_address == *member.key).ok_or(DaoError::NotAMember)?;
+            let mid_period = record.last_premium_ts > 0 && dao_data.coverage_active(record, Clock::get()?.unix_timestamp);
+            if mid_period && new_tier != record.tier {
+                let record = dao_data.members.iter_mut().find(|m| m.member_address == *member.key).ok_or(DaoError::NotAMember)?;
+                record.pending_tier = Some(new_tier);
+                msg!("Member {} moves to {:?} tier with their next premium", member.key, new_tier);
+            } else {
+                apply_tier(&mut dao_data, member.key, new_tier)?;
+                msg!("Member {} moved to {:?} tier", member.key, new_tier);
+            }
+        }
+
+        FinanceInstruction::SweepDormant => {
+            // Dormancy Sweep - Reclaims contributions of long-inactive members with no open claims into the shared pool
+            let authority = next_account_info(accounts_iter)?; // Multi-sig authority PDA, signing for an approved proposal
+            if !authority.is_signer || *authority.key != security::multisig_authority(program_id, account.key).0 {
+                return Err(ProgramError::MissingRequiredSignature); // Sweeps only run through the multi-sig
+            }
+            if dao_data.dormancy_threshold_secs <= 0 {
+                return Err(ProgramError::InvalidAccountData); // Dormancy sweeps are not configured
+            }
+            let cutoff = Clock::get()?.unix_timestamp.saturating_sub(dao_data.dormancy_threshold_secs);
+
+            let mut swept = 0u64;
+            for member in dao_data.members.iter_mut().filter(|m| !m.dormant) {
+                let last_active = member.last_activity_at.max(member.joined_timestamp);
+                if last_active < cutoff && member.claim_history.open == 0 {
+                    dao_data.treasury.shared_pool = dao_data.treasury.shared_pool.checked_add(member.premiums_paid).ok_or(ProgramError::ArithmeticOverflow)?;
+                    swept = swept.checked_add(member.premiums_paid).ok_or(ProgramError::ArithmeticOverflow)?;
+                    member.premiums_paid = 0;
+                    member.dormant = true;
+                    msg!("Member {} marked dormant", member.member_address);
+                }
+            }
+            msg!("Dormancy sweep reclaimed {} lamports into the shared pool", swept);
+        }
+
+        FinanceInstruction::SetDormancyThreshold { threshold_secs } => {
+            // Set Dormancy Threshold - Allows the admin to configure how long a member must be inactive to be swept
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin should adjust this
+
+            if threshold_secs < 0 {
+                return Err(ProgramError::InvalidArgument);
+            }
+            dao_data.dormancy_threshold_secs = threshold_secs;
+            msg!("Dormancy threshold updated to {} seconds", threshold_secs);
+        }
+
+        FinanceInstruction::SetRoundingMode { rounding_mode } => {
+            // Set Rounding Mode - Allows the admin to choose how fractional lamports are rounded in payouts
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin should adjust this
+
+            dao_data.rounding_mode = rounding_mode;
+            msg!("Rounding mode updated to {:?}", dao_data.rounding_mode);
+        }
+
+        FinanceInstruction::SetAlertMargin { alert_margin } => {
+            // Set Alert Margin - Allows the admin to configure when low-balance alerts are raised
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin should adjust this
+
+            dao_data.alert_margin = alert_margin;
+            msg!("Treasury alert margin updated to {} lamports", alert_margin);
+        }
+
+        FinanceInstruction::SubmitSharedClaim { amount, shares, nonce } => {
+            // Submit Shared Claim - Records a single event covering several members, each drawing on their own coverage
+            let submitter = next_account_info(accounts_iter)?; // Member submitting on behalf of all participants
+            let claim_account = next_account_info(accounts_iter)?; // PDA for the new claim, funded by the submitter
+            let system_program_account = next_account_info(accounts_iter)?; // System program for the account creation
+            if !submitter.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            dao_data.consume_nonce(submitter.key, nonce)?;
+
+            if shares.is_empty() || !shares.iter().any(|share| share.member == *submitter.key) {
+                return Err(ProgramError::InvalidArgument); // The submitter must be one of the participants
+            }
+            if shares.iter().enumerate().any(|(i, share)| shares[..i].iter().any(|earlier| earlier.member == share.member)) {
+                return Err(ProgramError::InvalidArgument); // Each participant holds one share, so nobody draws on their coverage twice
+            }
+            let shares_total = shares.iter().try_fold(0u64, |total, share| total.checked_add(share.amount)).ok_or(ProgramError::ArithmeticOverflow)?;
+            if shares_total != amount {
+                return Err(ProgramError::InvalidArgument); // Shares must add up to the claim amount
+            }
+
+            // Each participant has to be able to claim on their own, and draws on their coverage only with their consent
+            let now = Clock::get()?.unix_timestamp;
+            for share in &shares {
+                claims::check_claimant(&dao_data, &share.member, now)?;
+            }
+            let participants: Vec<Pubkey> = shares.iter().map(|share| share.member).collect();
+            claims::admit_claim(&mut dao_data, &participants, now, now)?; // The event is claimed as it's recorded
+            for share in shares.iter().filter(|share| share.member != *submitter.key) {
+                let participant = next_account_info(accounts_iter)?; // Co-participants follow the system program in share order
+                if participant.key != &share.member || !participant.is_signer {
+                    msg!("Participant {} must co-sign the shared claim", share.member);
+                    return Err(ProgramError::MissingRequiredSignature);
+                }
+            }
+            if let Some(uncovered) = uncovered_participant(&shares, &dao_data.risk_profiles) {
+                msg!("Member {} lacks coverage for their share", uncovered);
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            let claim = Claim {
+                claim_id: claims::assign_claim_id(&mut dao_data.claims_count)?,
+                member: *submitter.key,
+                amount,
+                service_date: now,
+                service_type: ServiceType::default(),
+                provider: Pubkey::default(),
+                status: ClaimStatus::Pending,
+                verifiers: Vec::new(),
+                preauth_id: None,
+                assigned_verifiers: Vec::new(),
+                condition: None,
+                condition_met: false,
+                external_ref: [0u8; 16],
+                submitted_at: now,
+                provider_attested: false,
+                supersedes: None,
+                holdback: 0,
+                holdback_release_at: 0,
+                anomaly_score: 0,
+                rejections: Vec::new(),
+                ack_requested_at: 0,
+                acknowledged: false,
+                shares,
+                zkp_proof: Vec::new(),
+                rejection_reason: None,
+                appealed: false,
+                paid_amount: 0,
+                resubmitted: false,
+            };
+            claims::create_claim(program_id, account.key, submitter, claim_account, system_program_account, &claim)?;
+            claims::track_claim(&mut dao_data, &claim);
+            msg!("Shared claim submitted for {} lamports", amount);
+        }
+
+        FinanceInstruction::RescaleReserveRatio => {
+            // Rescale Reserve Ratio - Recomputes the reserve ratio from recent payout volatility within the admin's bounds
+            if dao_data.reserve_ratio_max_bps == 0 {
+                return Err(ProgramError::InvalidAccountData); // Volatility scaling hasn't been configured
+            }
+            let recorded = dao_data.payouts_recorded.min(VOLATILITY_WINDOW as u64) as usize;
+            let volatility_bps = payout_volatility_bps(&dao_data.recent_payouts[..recorded]);
+            let reserve_ratio_bps = scaled_reserve_ratio_bps(volatility_bps, dao_data.reserve_ratio_min_bps, dao_data.reserve_ratio_max_bps);
+            dao_data.treasury.reserve_ratio_bps = reserve_ratio_bps;
+            msg!("Payout volatility {} bps; reserve ratio rescaled to {} bps", volatility_bps, reserve_ratio_bps);
+        }
+
+        FinanceInstruction::SetReserveRatioBounds { min_bps, max_bps } => {
+            // Set Reserve Ratio Bounds - Allows the admin to bound how far volatility scaling can move the reserve ratio
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin can set the bounds
+
+            if min_bps > max_bps || max_bps > 10_000 {
+                return Err(ProgramError::InvalidArgument); // Bounds must be ordered and within 100%
+            }
+            dao_data.reserve_ratio_min_bps = min_bps;
+            dao_data.reserve_ratio_max_bps = max_bps;
+            msg!("Reserve ratio bounds set to {}-{} bps", min_bps, max_bps);
+        }
+
+        FinanceInstruction::Donate { amount } => {
+            // Donate - Accepts external funding into the treasury without conferring membership or coverage
+            let donor = next_account_info(accounts_iter)?; // Account funding the donation
+            if !donor.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if amount == 0 {
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            collect_into_treasury(program_id, account, &dao_data, donor, amount, accounts_iter)?;
+
+            // Credit the treasury only; no member record, premium attribution, or risk profile is touched
+            dao_data.treasury.balance = dao_data.treasury.balance.checked_add(amount).ok_or(ProgramError::ArithmeticOverflow)?;
+            dao_data.donations_received = dao_data.donations_received.checked_add(amount).ok_or(ProgramError::ArithmeticOverflow)?;
+
+            // Attribute the donation to the donor in the transaction log
+            sol_log_data(&[b"Donation", donor.key.as_ref(), &amount.to_le_bytes()]);
+            msg!("Donation of {} lamports received from {}", amount, donor.key);
+        }
+
+        FinanceInstruction::RecomputeUtilization => {
+            // Recompute Utilization - Rebuilds every risk profile's cached utilization from the paid claim totals on member records
+            for profile in dao_data.risk_profiles.iter_mut() {
+                let record = dao_data.members.iter().find(|m| m.member_address == profile.member);
+                profile.claims_paid = record.map_or(0, |m| m.claim_history.paid_coverage);
+            }
+            for profile in dao_data.risk_profiles.iter_mut() {
+                profile.utilization_bps = utilization_bps(profile.claims_paid, profile.coverage_limit);
+            }
+            msg!("Coverage utilization recomputed for {} risk profiles", dao_data.risk_profiles.len());
+        }
+
+        FinanceInstruction::OpenPaymentPlan { installments_total, interval_secs } => {
+            // Open Payment Plan - Lets a member spread their tier premium over scheduled installments
+            let member = next_account_info(accounts_iter)?; // Member opening the plan
+            if !member.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if installments_total < 2 || interval_secs <= 0 {
+                return Err(ProgramError::InvalidArgument); // A plan needs at least two installments on a positive interval
+            }
+            apply_pending_tier(&mut dao_data, member.key)?; // The plan pays for a new period on the pending tier
+
+            let record = dao_data.members.iter().find(|m| m.member_address == *member.key).ok_or(DaoError::NotAMember)?;
+            if dao_data.payment_plans.iter().any(|plan| plan.member == *member.key && plan.amount_paid < plan.total_owed) {
+                return Err(ProgramError::AccountAlreadyInitialized); // The member already has an unfinished plan
+            }
+
+            let total_owed = tier_benefits(record.tier).premium;
+            let installment_amount = total_owed.div_ceil(installments_total as u64);
+            dao_data.payment_plans.retain(|plan| plan.member != *member.key);
+            dao_data.payment_plans.push(PaymentPlan {
+                member: *member.key,
+                total_owed,
+                amount_paid: 0,
+                installment_amount,
+                installments_total,
+                installments_paid: 0,
+                start_at: Clock::get()?.unix_timestamp,
+                interval_secs,
+            });
+            msg!("Payment plan opened: {} installments of {} lamports", installments_total, installment_amount);
+        }
+
+        FinanceInstruction::PayInstallment { amount, nonce } => {
+            // Pay Installment - Records one scheduled installment toward the member's payment plan
+            let member = next_account_info(accounts_iter)?; // Member paying the installment
+            if !member.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            dao_data.consume_nonce(member.key, nonce)?;
+
+            let plan_index = dao_data.payment_plans.iter().position(|plan| plan.member == *member.key).ok_or(ProgramError::InvalidAccountData)?; // No plan
+            let plan = &dao_data.payment_plans[plan_index];
+            let remaining = plan.total_owed.saturating_sub(plan.amount_paid);
+            if remaining == 0 {
+                return Err(ProgramError::InvalidArgument); // Plan is already paid off
+            }
+            if amount < plan.installment_amount.min(remaining) {
+                return Err(ProgramError::InsufficientFunds); // Below the scheduled installment
+            }
+            collect_into_treasury(program_id, account, &dao_data, member, amount, accounts_iter)?;
+
+            let plan = &mut dao_data.payment_plans[plan_index];
+            plan.amount_paid = plan.amount_paid.checked_add(amount).ok_or(ProgramError::ArithmeticOverflow)?;
+            plan.installments_paid = plan.installments_paid.saturating_add(1);
+            let paid_off = plan.amount_paid >= plan.total_owed;
+            let plan_paid = plan.amount_paid;
+
+            // Installments count as premium payments toward the treasury and the member's contributions
+            dao_data.treasury.balance = dao_data.treasury.balance.checked_add(amount).ok_or(ProgramError::ArithmeticOverflow)?;
+            credit_risk_band(&mut dao_data.risk_bands, &dao_data.risk_profiles, member.key, amount)?;
+            if let Some(record) = dao_data.members.iter_mut().find(|m| m.member_address == *member.key) {
+                record.premiums_paid = record.premiums_paid.checked_add(amount).ok_or(ProgramError::ArithmeticOverflow)?;
+                record.last_activity_at = Clock::get()?.unix_timestamp;
+                record.dormant = false;
+                // The last installment completes the premium, which then covers the period like a full payment
+                if paid_off {
+                    accrue_claim_free_period(record, record.last_activity_at, dao_data.premium_period_secs);
+                    record.last_premium_ts = record.last_activity_at;
+                    record.last_premium_amount = plan_paid;
+                }
+            }
+            msg!("Installment of {} lamports received", amount);
+        }
+
+        FinanceInstruction::EstimatePayout { amount } => {
+            // Estimate Payout - Read-only preview of what a claim of the given amount would pay, returned via return data
+            let member = next_account_info(accounts_iter)?; // Member the estimate is for
+
+            let risk_profile = dao_data.risk_profiles.iter().find(|rp| rp.member == *member.key).ok_or(ProgramError::InvalidAccountData)?; // No risk profile found for this member
+            let estimate = estimate_payout(amount, risk_profile, dao_data.rounding_mode);
+            set_return_data(&estimate.try_to_vec()?);
+            msg!("Payout estimate for {} lamports: {:?}", amount, estimate);
+        }
+
+        FinanceInstruction::RebalanceVaults => {
+            // Rebalance Vaults - Moves treasury funds above the target reserve into the yield vault, or pulls them back on a shortfall
+            let treasury = next_account_info(accounts_iter)?; // The DAO's configured treasury account, holding the liquid reserve
+            let yield_vault = next_account_info(accounts_iter)?; // PDA vault holding the yield allocation
+            let system_program_account = next_account_info(accounts_iter)?; // System program for the transfer
+            if *system_program_account.key != system_program::id() {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+
+            let treasury_kind = classify_treasury(account, treasury, &dao_data.treasury_account, program_id)?;
+            let (yield_key, yield_bump) = Pubkey::find_program_address(&[YIELD_VAULT_SEED, account.key.as_ref()], program_id);
+            if *yield_vault.key != yield_key {
+                return Err(ProgramError::InvalidSeeds); // Vault must be this DAO's PDA
+            }
+
+            let target = target_reserve(&dao_data.treasury, dao_data.yield_balance, dao_data.min_liquidity);
+            if dao_data.treasury.balance > target {
+                let excess = dao_data.treasury.balance.checked_sub(target).ok_or(ProgramError::ArithmeticOverflow)?;
+                if treasury_kind == TreasuryKind::Separate && !treasury.is_signer {
+                    return Err(ProgramError::MissingRequiredSignature); // A separate treasury wallet must authorize the transfer
+                }
+                transfer_from_treasury(treasury, yield_vault, system_program_account, treasury_kind, excess)?;
+                dao_data.treasury.balance = target;
+                dao_data.yield_balance = dao_data.yield_balance.checked_add(excess).ok_or(ProgramError::ArithmeticOverflow)?;
+                msg!("Moved {} lamports of excess reserve into the yield vault", excess);
+            } else if dao_data.treasury.balance < target && dao_data.yield_balance > 0 {
+                let shortfall = target.checked_sub(dao_data.treasury.balance).ok_or(ProgramError::ArithmeticOverflow)?.min(dao_data.yield_balance);
+                invoke_signed(
+                    &system_instruction::transfer(yield_vault.key, treasury.key, shortfall),
+                    &[yield_vault.clone(), treasury.clone(), system_program_account.clone()],
+                    &[&[YIELD_VAULT_SEED, account.key.as_ref(), &[yield_bump]]],
+                )?;
+                dao_data.treasury.balance = dao_data.treasury.balance.checked_add(shortfall).ok_or(ProgramError::ArithmeticOverflow)?;
+                dao_data.yield_balance = dao_data.yield_balance.checked_sub(shortfall).ok_or(ProgramError::ArithmeticOverflow)?;
+                msg!("Pulled {} lamports back from the yield vault to cover a reserve shortfall", shortfall);
+            } else {
+                msg!("Reserve already at its target of {} lamports", target);
+            }
+        }
+
+        FinanceInstruction::SetMinLiquidity { min_liquidity } => {
+            // Set Minimum Liquidity - Allows the admin to set the floor the treasury keeps during rebalancing
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin can set the liquidity floor
+            dao_data.min_liquidity = min_liquidity;
+            msg!("Minimum liquidity set to {} lamports", min_liquidity);
+        }
+
+        FinanceInstruction::PaySplitPremium { amounts, nonce } => {
+            // Split Premium Payment - Collects one premium from several source accounts, e.g. a personal wallet and an HSA
+            let member = next_account_info(accounts_iter)?; // Member the premium is paid for
+            let treasury = next_account_info(accounts_iter)?; // The DAO's configured treasury account, or its token account
+            let transfer_program = next_account_info(accounts_iter)?; // System program, or the SPL token program
+            let mint = dao_data.treasury.token_mint;
+            if let Some(mint) = mint {
+                if *transfer_program.key != spl_token::id() {
+                    return Err(ProgramError::IncorrectProgramId);
+                }
+                let (authority_key, _) = Pubkey::find_program_address(&[TREASURY_AUTHORITY_SEED, account.key.as_ref()], program_id);
+                check_token_account(treasury, &mint, &authority_key)?;
+            } else {
+                if *transfer_program.key != system_program::id() {
+                    return Err(ProgramError::IncorrectProgramId);
+                }
+                classify_treasury(account, treasury, &dao_data.treasury_account, program_id)?;
+            }
+
+            // One amount per source account, in the same order as the remaining accounts
+            if amounts.is_empty() {
+                return Err(ProgramError::InvalidInstructionData);
+            }
+            let sources = amounts.len();
+
+            // The sources pay on the member's behalf, so a replayed payment is caught by the member's nonce
+            dao_data.consume_nonce(member.key, nonce)?;
+            apply_pending_tier(&mut dao_data, member.key)?; // The premium pays for a new period on the pending tier
+            let record = dao_data.members.iter().find(|m| m.member_address == *member.key).ok_or(DaoError::NotAMember)?;
+            let total = amounts.iter().try_fold(0u64, |sum, amount| sum.checked_add(*amount)).ok_or(ProgramError::ArithmeticOverflow)?;
+            let premium = premium_quote(&dao_data, record);
+            if total < premium {
+                msg!("Combined premium of {} lamports is below the quoted premium of {}", total, premium);
+                return Err(DaoError::PremiumUnderpaid.into());
+            }
+
+            for amount in amounts {
+                let source = next_account_info(accounts_iter)?; // Account funding this portion of the premium
+                if !source.is_signer {
+                    return Err(ProgramError::MissingRequiredSignature); // Every source must authorize its share
+                }
+                if let Some(mint) = mint {
+                    let source_tokens = next_account_info(accounts_iter)?; // The source's token account for the treasury mint
+                    check_token_account(source_tokens, &mint, source.key)?;
+                    invoke(
+                        &spl_token::instruction::transfer(transfer_program.key, source_tokens.key, treasury.key, source.key, &[], amount)?,
+                        &[source_tokens.clone(), treasury.clone(), source.clone(), transfer_program.clone()],
+                    )?;
+                } else {
+                    invoke(
+                        &system_instruction::transfer(source.key, treasury.key, amount),
+                        &[source.clone(), treasury.clone(), transfer_program.clone()],
+                    )?;
+                }
+            }
+
+            // The whole premium is attributed to the member, whoever funded each portion
+            dao_data.treasury.balance = dao_data.treasury.balance.checked_add(total).ok_or(ProgramError::ArithmeticOverflow)?;
+            credit_risk_band(&mut dao_data.risk_bands, &dao_data.risk_profiles, member.key, total)?;
+            if let Some(record) = dao_data.members.iter_mut().find(|m| m.member_address == *member.key) {
+                record.premiums_paid = record.premiums_paid.checked_add(total).ok_or(ProgramError::ArithmeticOverflow)?;
+                record.last_activity_at = Clock::get()?.unix_timestamp;
+                accrue_claim_free_period(record, record.last_activity_at, dao_data.premium_period_secs);
+                record.last_premium_ts = record.last_activity_at;
+                record.last_premium_amount = total;
+                record.dormant = false;
+            }
+            msg!("Premium payment of {} lamports received from {} sources", total, sources);
+        }
+
+        FinanceInstruction::Heartbeat => {
+            // Heartbeat - Records a signed wellness check-in, building a streak that modestly lowers the member's risk score
+            let member = next_account_info(accounts_iter)?; // Member checking in
+            if !member.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            let now = Clock::get()?.unix_timestamp;
+
+            let record = dao_data.members.iter_mut().find(|m| m.member_address == *member.key).ok_or(DaoError::NotAMember)?;
+            record_heartbeat(record, now);
+            record.last_activity_at = now;
+            record.dormant = false;
+            msg!("Heartbeat recorded: streak {}, risk score {}", record.heartbeat_streak, compute_risk_score(record));
+        }
+
+        FinanceInstruction::RegisterTokenVault { vault } => {
+            // Register Token Vault - Allows the admin to add an accepted mint's vault and price oracle to the valuation
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin can register vaults
+            if dao_data.token_vaults.iter().any(|v| v.mint == vault.mint) {
+                return Err(ProgramError::AccountAlreadyInitialized); // Each mint is valued through a single vault
+            }
+            msg!("Registered vault {} for mint {}", vault.vault, vault.mint);
+            dao_data.token_vaults.push(vault);
+        }
+
+        FinanceInstruction::SetMaxPriceAge { max_age_secs: max_age } => {
+            // Set Maximum Price Age - Allows the admin to bound how stale an oracle price the valuation accepts
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin can set the price age
+            if max_age <= 0 {
+                return Err(ProgramError::InvalidArgument);
+            }
+            dao_data.max_price_age_secs = max_age;
+            msg!("Oracle prices older than {} seconds will be rejected", max_age);
+        }
+
+        FinanceInstruction::ValueTreasury => {
+            // Treasury Valuation - Read-only total of every token vault in the base unit, returned via return data
+            let now = Clock::get()?.unix_timestamp;
+            let mut vault_values = Vec::with_capacity(dao_data.token_vaults.len());
+            for registered in dao_data.token_vaults.iter() {
+                let vault = next_account_info(accounts_iter)?; // Token vault, in registration order
+                let oracle = next_account_info(accounts_iter)?; // Its price oracle
+                if *vault.key != registered.vault || *oracle.key != registered.oracle {
+                    return Err(ProgramError::InvalidArgument); // Accounts must match the registered vaults in order
+                }
+                if *vault.owner != spl_token::id() {
+                    return Err(ProgramError::IncorrectProgramId);
+                }
+                let token_account = spl_token::state::Account::unpack(&vault.data.borrow())?;
+                if token_account.mint != registered.mint {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+
+                let (price, published_at) = read_oracle_price(&oracle.data.borrow())?;
+                if now.saturating_sub(published_at) > dao_data.max_price_age_secs {
+                    msg!("Price for mint {} was published at {} and is stale", registered.mint, published_at);
+                    return Err(ProgramError::InvalidAccountData);
+                }
+                vault_values.push(vault_value(token_account.amount, price, registered.decimals)?);
+            }
+
+            let total = vault_values.iter().try_fold(0u64, |sum, value| sum.checked_add(*value)).ok_or(ProgramError::ArithmeticOverflow)?;
+            let valuation = TreasuryValuation { total, vault_values };
+            set_return_data(&valuation.try_to_vec()?);
+            msg!("Treasury token vaults valued at {} base units", total);
+        }
+
+        FinanceInstruction::DefineRiskBands { bands: definitions } => {
+            // Define Risk Bands - Allows the admin to split the pool into risk-band sub-pools by risk score
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin can define risk bands
+            if dao_data.risk_bands.iter().any(|b| b.balance > 0) {
+                return Err(ProgramError::InvalidAccountData); // Funded bands can't be redrawn without stranding their balances
+            }
+
+            // Each band is (min risk score, max risk score, reserve ratio in basis points)
+            let bands = definitions
+                .into_iter()
+                .map(|(min_risk_score, max_risk_score, reserve_ratio_bps)| RiskBand { min_risk_score, max_risk_score, reserve_ratio_bps, balance: 0 })
+                .collect::<Vec<_>>();
+            if !valid_risk_bands(&bands) {
+                return Err(ProgramError::InvalidArgument); // Bands must be well-formed and must not overlap
+            }
+            dao_data.risk_bands = bands;
+            msg!("Pool segmented into {} risk bands", dao_data.risk_bands.len());
+        }
+
+        FinanceInstruction::SetServiceCaps { caps } => {
+            // Set Service Caps - Allows the admin to cap claim amounts per service type
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin can set service caps
+            if caps.iter().enumerate().any(|(i, (service_type, _))| caps[..i].iter().any(|(t, _)| t == service_type)) {
+                return Err(ProgramError::InvalidArgument); // Each service type may only be capped once
+            }
+            dao_data.service_coverage_caps = caps;
+            msg!("Claim caps configured for {} service types", dao_data.service_coverage_caps.len());
+        }
+
+        FinanceInstruction::RemoveMember => {
+            // Remove Member - Admin or the member themselves; settles the member's prepaid premium and drops their records
+            let authority = next_account_info(accounts_iter)?; // Admin, or the departing member
+            let member_account = next_account_info(accounts_iter)?; // Member being removed, credited with the refund
+            if !authority.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if authority.key != member_account.key {
+                require_role(&dao_data, authority.key, Role::Admin)?;
+            }
+
+            let index = dao_data.members.iter().position(|m| m.member_address == *member_account.key).ok_or(DaoError::NotAMember)?;
+            let in_flight = dao_data.members[index].claim_history.in_flight;
+            if in_flight > 0 {
+                msg!("Member {} can't be removed while {} of their claims are in flight", member_account.key, in_flight);
+                return Err(DaoError::PendingClaimsExist.into());
+            }
+
+            // The unearned part of the current period's premium goes back to the member from the configured treasury,
+            // drawn from their risk band like a payout and never out of the required reserve
+            let refund = unearned_premium(&dao_data, &dao_data.members[index], Clock::get()?.unix_timestamp);
+            if refund > 0 {
+                let required_reserve = required_reserve(&dao_data.treasury);
+                let available = dao_data.treasury.balance.saturating_sub(required_reserve);
+                if available < refund {
+                    msg!("Treasury has {} lamports available above its {} reserve, below the {} refund", available, required_reserve, refund);
+                    return Err(DaoError::InsufficientReserve.into());
+                }
+                if !dao_data.risk_bands.is_empty() {
+                    let score = band_risk_score(&dao_data.risk_profiles, member_account.key);
+                    let band = dao_data.risk_bands.iter_mut().find(|b| band_contains(b, score)).ok_or(ProgramError::InvalidAccountData)?; // No band covers this member
+                    if band_available(band) < refund {
+                        msg!("Risk band {}-{} has {} lamports available, below the {} refund", band.min_risk_score, band.max_risk_score, band_available(band), refund);
+                        return Err(DaoError::RiskBandUnderfunded.into());
+                    }
+                    band.balance -= refund;
+                }
+                dao_data.treasury.balance -= refund;
+                pay_from_treasury(program_id, account, &dao_data, member_account.key, refund, accounts_iter)?;
+            }
+
+            let removed = dao_data.members.remove(index);
+            dao_data.risk_profiles.retain(|rp| rp.member != removed.member_address);
+            dao_data.payment_plans.retain(|plan| plan.member != removed.member_address);
+            msg!("Member {} removed with a premium refund of {} lamports", removed.member_address, refund);
+        }
+
+        FinanceInstruction::MigrateReserveRatio => {
+            // Migrate Reserve Ratio - Rewrites a treasury saved with an f32 reserve ratio into basis points in place
+            let admin = next_account_info(accounts_iter)?; // Admin's account, checked against the admin stored in the legacy data
+            return migrate_reserve_ratio(account, admin);
+        }
+
+        FinanceInstruction::SetPremiumSchedule { base_premium, multipliers } => {
+            // Set Premium Schedule - Allows the admin to price premiums from a base amount scaled by risk score
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin can set the premium schedule
+            if multipliers.windows(2).any(|pair| pair[0].0 >= pair[1].0) {
+                return Err(ProgramError::InvalidArgument); // Steps must be listed by strictly ascending risk score
+            }
+            dao_data.base_premium = base_premium;
+            dao_data.risk_multiplier_bps = multipliers;
+            msg!("Base premium set to {} lamports with {} risk multiplier steps", base_premium, dao_data.risk_multiplier_bps.len());
+        }
+
+        FinanceInstruction::SetPremiumPeriod { period_secs, grace_secs } => {
+            // Set Premium Period - Allows the admin to set how long a premium payment covers a member, and the grace after it
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin can set the premium period
+            if period_secs < 0 || grace_secs < 0 {
+                return Err(ProgramError::InvalidArgument);
+            }
+            dao_data.premium_period_secs = period_secs;
+            dao_data.grace_period_secs = grace_secs;
+            msg!("Premium period set to {} seconds with a {} second grace period", period_secs, grace_secs);
+        }
+
+        FinanceInstruction::SetNoClaimsDiscount { step_bps, max_bps } => {
+            // Set No-Claims Discount - Allows the admin to set the premium discount earned per claim-free period and its cap
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin can set the discount curve
+            if max_bps > 10_000 {
+                return Err(ProgramError::InvalidArgument); // Discount can't exceed the whole premium
+            }
+            dao_data.no_claims_discount_step_bps = step_bps;
+            dao_data.no_claims_discount_max_bps = max_bps;
+            msg!("No-claims discount set to {} bps per clean period, up to {} bps", step_bps, max_bps);
+        }
+
+        FinanceInstruction::QuotePremium { member } => {
+            // Quote Premium - Read-only premium owed per period by the member, returned via return data
+            let record = dao_data.members.iter().find(|m| m.member_address == member).ok_or(DaoError::NotAMember)?;
+            let quote = premium_quote(&dao_data, record);
+            set_return_data(&quote.to_le_bytes());
+            msg!("Premium quote for {}: {} lamports", member, quote);
+        }
+
+        FinanceInstruction::SetTokenMint { mint } => {
+            // Set Token Mint - Allows the admin to denominate premiums and payouts in an SPL token
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin can change the treasury's currency
+            if mint != dao_data.treasury.token_mint && dao_data.treasury.balance > 0 {
+                return Err(ProgramError::InvalidAccountData); // The recorded balance is held in the current currency
+            }
+            dao_data.treasury.token_mint = mint;
+            match mint {
+                Some(mint) => msg!("Treasury now takes premiums and pays claims in mint {}", mint),
+                None => msg!("Treasury now takes premiums and pays claims in lamports"),
+            }
+        }
+
+        FinanceInstruction::DepositTreasury { amount } => {
+            // Deposit Treasury - Lets grant makers and reinsurers top up the treasury without becoming members
+            let depositor = next_account_info(accounts_iter)?; // Account funding the deposit
+            if !depositor.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if amount == 0 {
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            collect_into_treasury(program_id, account, &dao_data, depositor, amount, accounts_iter)?;
+
+            dao_data.treasury.balance = dao_data.treasury.balance.checked_add(amount).ok_or(ProgramError::ArithmeticOverflow)?;
+            dao_data.deposits_received = dao_data.deposits_received.checked_add(amount).ok_or(ProgramError::ArithmeticOverflow)?;
+
+            // Tagged apart from premiums and donations so accounting can separate the contributions
+            sol_log_data(&[b"TreasuryDeposit", depositor.key.as_ref(), &amount.to_le_bytes()]);
+            msg!("Treasury deposit of {} lamports received from {}", amount, depositor.key);
+        }
+
+        FinanceInstruction::WithdrawTreasury { amount, destination } => {
+            // Withdraw Treasury - Moves treasury funds out for operational costs, only when executed through the multi-sig
+            let authority = next_account_info(accounts_iter)?; // Multi-sig authority PDA, signing for an approved proposal
+            if !authority.is_signer || *authority.key != security::multisig_authority(program_id, account.key).0 {
+                return Err(ProgramError::MissingRequiredSignature); // No single key can withdraw, not even the admin
+            }
+            if amount == 0 {
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            // Withdrawals may never eat into the required reserve
+            let required_reserve = required_reserve(&dao_data.treasury);
+            let available = dao_data.treasury.balance.saturating_sub(required_reserve);
+            if amount > available {
+                msg!("Treasury has {} lamports available above its {} reserve, below the {} withdrawal", available, required_reserve, amount);
+                return Err(DaoError::InsufficientReserve.into());
+            }
+
+            // Funds only go where the signers approved
+            pay_from_treasury(program_id, account, &dao_data, &destination, amount, accounts_iter)?;
+            dao_data.treasury.balance = dao_data.treasury.balance.checked_sub(amount).ok_or(ProgramError::ArithmeticOverflow)?;
+
+            sol_log_data(&[b"TreasuryWithdrawal", destination.as_ref(), &amount.to_le_bytes()]);
+            msg!("Treasury withdrawal of {} lamports sent to {}", amount, destination);
+        }
+
+        FinanceInstruction::ClearEmergency => {
+            // Clear Emergency - Allows the admin to restore full payouts once the treasury is solvent again
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only the admin can clear emergency mode
+            if is_insolvent(&dao_data.treasury, dao_data.outstanding_claims) {
+                msg!("Treasury balance {} still doesn't cover {} outstanding", dao_data.treasury.balance, dao_data.outstanding_claims);
+                return Err(DaoError::InsufficientReserve.into());
+            }
+            dao_data.emergency_mode = false;
+            msg!("Emergency mode cleared");
+        }
+
+        FinanceInstruction::RecalculateRisk { member } => {
+            // Recalculate Risk - Rescores a member from the claims they've been paid, their premiums, and their tenure
+            let record = dao_data.members.iter().find(|m| m.member_address == member).ok_or(DaoError::NotAMember)?;
+            let now = Clock::get()?.unix_timestamp;
+            let (paid_claims, paid_total) = (record.claim_history.paid_out, record.claim_history.paid_out_total);
+            let score = history_risk_score(paid_claims, paid_total, record.premiums_paid, now.saturating_sub(record.joined_timestamp));
+
+            let profile = dao_data.risk_profiles.iter_mut().find(|rp| rp.member == member).ok_or(ProgramError::InvalidAccountData)?; // No risk profile found for this member
+            profile.risk_score = score;
+            msg!("Risk score for member {} recalculated to {} from {} paid claims totalling {} lamports", member, score, paid_claims, paid_total);
+        }
+    }
+
+    // Save the updated DAO state back into the account's data
+    dao_data.save(account)
+}
+
+// Minimum number of accounts each instruction needs, including the DAO account
+fn expected_account_count(instruction: &FinanceInstruction) -> usize {
+    match instruction {
+        FinanceInstruction::PayPremium { .. } => 4, // DAO, payer, treasury, system program (or three token accounts)
+        FinanceInstruction::PayoutClaim { .. } => 6, // DAO, authority, claim account, treasury, claimant, system program (or four token accounts)
+        FinanceInstruction::UpdateRiskProfile { .. } => 3, // DAO, member, admin
+        FinanceInstruction::SetReserveRatio { .. } => 2, // DAO, admin
+        FinanceInstruction::SetSingleClaimCap { .. } => 2, // DAO, admin
+        FinanceInstruction::InitializeTreasury { .. } => 2, // DAO, admin
+        FinanceInstruction::GetSolvency => 1, // DAO
+        FinanceInstruction::ChangeTier { .. } => 2, // DAO, member
+        FinanceInstruction::SweepDormant => 2, // DAO, multi-sig authority PDA
+        FinanceInstruction::SetDormancyThreshold { .. } => 2, // DAO, admin
+        FinanceInstruction::SetRoundingMode { .. } => 2, // DAO, admin
+        FinanceInstruction::SetAlertMargin { .. } => 2, // DAO, admin
+        FinanceInstruction::SubmitSharedClaim { .. } => 4, // DAO, submitter, claim account, system program, then co-participants
+        FinanceInstruction::RescaleReserveRatio => 1, // DAO
+        FinanceInstruction::SetReserveRatioBounds { .. } => 2, // DAO, admin
+        FinanceInstruction::Donate { .. } => 4, // DAO, donor, treasury, system program (or three token accounts)
+        FinanceInstruction::RecomputeUtilization => 1, // DAO
+        FinanceInstruction::OpenPaymentPlan { .. } => 2, // DAO, member
+        FinanceInstruction::PayInstallment { .. } => 4, // DAO, member, treasury, system program (or three token accounts)
+        FinanceInstruction::EstimatePayout { .. } => 2, // DAO, member
+        FinanceInstruction::RebalanceVaults => 4, // DAO, treasury, yield vault, system program
+        FinanceInstruction::SetMinLiquidity { .. } => 2, // DAO, admin
+        FinanceInstruction::PaySplitPremium { .. } => 5, // DAO, member, treasury, system program, followed by one account per premium source (or treasury token account, token program, and each source with its token account)
+        FinanceInstruction::Heartbeat => 2, // DAO, member
+        FinanceInstruction::RegisterTokenVault { .. } => 2, // DAO, admin
+        FinanceInstruction::SetMaxPriceAge { .. } => 2, // DAO, admin
+        FinanceInstruction::ValueTreasury => 1, // DAO, followed by a vault and oracle for each registered mint
+        FinanceInstruction::DefineRiskBands { .. } => 2, // DAO, admin
+        FinanceInstruction::SetServiceCaps { .. } => 2, // DAO, admin
+        FinanceInstruction::RemoveMember => 3, // DAO, admin or member, member being removed, followed by the refund accounts
+        FinanceInstruction::MigrateReserveRatio => 2, // DAO, admin
+        FinanceInstruction::SetPremiumSchedule { .. } => 2, // DAO, admin
+        FinanceInstruction::QuotePremium { .. } => 1, // DAO
+        FinanceInstruction::SetPremiumPeriod { .. } => 2, // DAO, admin
+        FinanceInstruction::SetNoClaimsDiscount { .. } => 2, // DAO, admin
+        FinanceInstruction::SetTokenMint { .. } => 2, // DAO, admin
+        FinanceInstruction::DepositTreasury { .. } => 4, // DAO, depositor, treasury, system program (or three token accounts)
+        FinanceInstruction::WithdrawTreasury { .. } => 5, // DAO, multi-sig authority, treasury, destination, system program (or four token accounts)
+        FinanceInstruction::ClearEmergency => 2, // DAO, admin
+        FinanceInstruction::RecalculateRisk { .. } => 1, // DAO
+    }
+}
+
+// Instructions that only read state, and so still run while the DAO is paused
+fn allowed_while_paused(instruction: &FinanceInstruction) -> bool {
+    matches!(
+        instruction,
+        FinanceInstruction::GetSolvency | FinanceInstruction::EstimatePayout { .. } | FinanceInstruction::ValueTreasury | FinanceInstruction::QuotePremium { .. }
+    )
+}
+
+// First participant in a shared claim whose risk profile doesn't cover their share, if any
+fn uncovered_participant(shares: &[ClaimShare], risk_profiles: &[RiskProfile]) -> Option<Pubkey> {
+    shares
+        .iter()
+        .find(|share| {
+            !risk_profiles
+                .iter()
+                .any(|rp| rp.member == share.member && share.amount <= rp.coverage_limit)
+        })
+        .map(|share| share.member)
+}
+
+// Token accounts moving treasury funds must be SPL accounts of the treasury mint held by the expected owner
+fn check_token_account(account: &AccountInfo, mint: &Pubkey, owner: &Pubkey) -> ProgramResult {
+    if *account.owner != spl_token::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let token_account = spl_token::state::Account::unpack(&account.data.borrow())?;
+    if token_account.mint != *mint || token_account.owner != *owner {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(())
+}
+
+// Where treasury funds live relative to the DAO data account
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum TreasuryKind {
+    DaoAccount, // Treasury funds are held in the DAO data account itself and debited directly by the program
+    Separate,   // Treasury is a separate system-owned wallet that must sign outgoing transfers
+}
+
+// Move `amount` lamports from the treasury to `recipient`. The DAO account is owned by this program, so it is debited
+// directly and never below its rent-exempt minimum; a separate wallet pays through the system program under its own signature.
+pub(crate) fn transfer_from_treasury<'a>(
+    treasury: &AccountInfo<'a>,
+    recipient: &AccountInfo<'a>,
+    system_program_account: &AccountInfo<'a>,
+    treasury_kind: TreasuryKind,
+    amount: u64,
+) -> ProgramResult {
+    match treasury_kind {
+        TreasuryKind::DaoAccount => {
+            let spendable = treasury.lamports().saturating_sub(Rent::get()?.minimum_balance(treasury.data_len()));
+            if amount > spendable {
+                msg!("Treasury holds {} spendable lamports, below the {} payout", spendable, amount);
+                return Err(ProgramError::InsufficientFunds);
+            }
+            let recipient_lamports = recipient.lamports().checked_add(amount).ok_or(ProgramError::ArithmeticOverflow)?;
+            **treasury.try_borrow_mut_lamports()? -= amount;
+            **recipient.try_borrow_mut_lamports()? = recipient_lamports;
+        }
+        TreasuryKind::Separate => {
+            invoke(
+                &system_instruction::transfer(treasury.key, recipient.key, amount),
+                &[treasury.clone(), recipient.clone(), system_program_account.clone()],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+// The treasury may either be the DAO data account itself or a separate system-owned wallet.
+// Any other combination (a mismatched key, or a separate account owned by this or another program) is rejected.
+pub(crate) fn classify_treasury(
+    dao_account: &AccountInfo,
+    treasury: &AccountInfo,
+    configured_treasury: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<TreasuryKind, ProgramError> {
+    if treasury.key != configured_treasury {
+        return Err(ProgramError::InvalidArgument); // Not the DAO's treasury
+    }
+    if treasury.key == dao_account.key {
+        return Ok(TreasuryKind::DaoAccount);
+    }
+    if treasury.owner == program_id || *treasury.owner != system_program::id() {
+        return Err(ProgramError::IllegalOwner); // A separate treasury must be a system-owned wallet
+    }
+    Ok(TreasuryKind::Separate)
+}
+
+// Move `amount` lamports from `payer` into the DAO's configured treasury account. Takes the treasury account and the
+// system program.
+fn transfer_to_treasury<'a, 'b>(
+    program_id: &Pubkey,
+    account: &AccountInfo<'a>,
+    dao_data: &HealthInsuranceDAO,
+    payer: &AccountInfo<'a>,
+    amount: u64,
+    accounts_iter: &mut std::slice::Iter<'b, AccountInfo<'a>>,
+) -> ProgramResult {
+    let treasury = next_account_info(accounts_iter)?; // The DAO's configured treasury account
+    let system_program_account = next_account_info(accounts_iter)?; // System program for the transfer
+    if *system_program_account.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    classify_treasury(account, treasury, &dao_data.treasury_account, program_id)?;
+    invoke(
+        &system_instruction::transfer(payer.key, treasury.key, amount),
+        &[payer.clone(), treasury.clone(), system_program_account.clone()],
+    )
+}
+
+// Take `amount` from `payer` into the treasury, whether a premium, a donation, or a deposit. A token treasury takes it
+// from the payer's token account, followed by the treasury token account and the token program; otherwise it moves in
+// lamports.
+fn collect_into_treasury<'a, 'b>(
+    program_id: &Pubkey,
+    account: &AccountInfo<'a>,
+    dao_data: &HealthInsuranceDAO,
+    payer: &AccountInfo<'a>,
+    amount: u64,
+    accounts_iter: &mut std::slice::Iter<'b, AccountInfo<'a>>,
+) -> ProgramResult {
+    if let Some(mint) = dao_data.treasury.token_mint {
+        let payer_tokens = next_account_info(accounts_iter)?; // Payer's token account for the treasury mint
+        let treasury_tokens = next_account_info(accounts_iter)?; // Treasury token account, held by the treasury authority PDA
+        let token_program = next_account_info(accounts_iter)?; // SPL token program for the transfer
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let (authority_key, _) = Pubkey::find_program_address(&[TREASURY_AUTHORITY_SEED, account.key.as_ref()], program_id);
+        check_token_account(payer_tokens, &mint, payer.key)?;
+        check_token_account(treasury_tokens, &mint, &authority_key)?;
+        invoke(
+            &spl_token::instruction::transfer(token_program.key, payer_tokens.key, treasury_tokens.key, payer.key, &[], amount)?,
+            &[payer_tokens.clone(), treasury_tokens.clone(), payer.clone(), token_program.clone()],
+        )
+    } else {
+        transfer_to_treasury(program_id, account, dao_data, payer, amount, accounts_iter)
+    }
+}
+
+// Send `amount` from the DAO's treasury to `recipient`. A token treasury pays from its token account under the treasury
+// authority PDA and takes the treasury token account, the recipient's token account, the authority, and the token program.
+// A lamport treasury takes the configured treasury account, the recipient, and the system program.
+pub(crate) fn pay_from_treasury<'a, 'b>(
+    program_id: &Pubkey,
+    account: &AccountInfo<'a>,
+    dao_data: &HealthInsuranceDAO,
+    recipient: &Pubkey,
+    amount: u64,
+    accounts_iter: &mut std::slice::Iter<'b, AccountInfo<'a>>,
+) -> ProgramResult {
+    if let Some(mint) = dao_data.treasury.token_mint {
+        let treasury_tokens = next_account_info(accounts_iter)?; // Treasury token account paying out
+        let recipient_tokens = next_account_info(accounts_iter)?; // Recipient's token account for the treasury mint
+        let authority = next_account_info(accounts_iter)?; // Treasury authority PDA
+        let token_program = next_account_info(accounts_iter)?; // SPL token program for the transfer
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let (authority_key, authority_bump) = Pubkey::find_program_address(&[TREASURY_AUTHORITY_SEED, account.key.as_ref()], program_id);
+        if *authority.key != authority_key {
+            return Err(ProgramError::InvalidSeeds); // Authority must be this DAO's PDA
+        }
+        check_token_account(treasury_tokens, &mint, &authority_key)?;
+        check_token_account(recipient_tokens, &mint, recipient)?;
+        invoke_signed(
+            &spl_token::instruction::transfer(token_program.key, treasury_tokens.key, recipient_tokens.key, authority.key, &[], amount)?,
+            &[treasury_tokens.clone(), recipient_tokens.clone(), authority.clone(), token_program.clone()],
+            &[&[TREASURY_AUTHORITY_SEED, account.key.as_ref(), &[authority_bump]]],
+        )
+    } else {
+        let treasury = next_account_info(accounts_iter)?; // The DAO's configured treasury account
+        let recipient_account = next_account_info(accounts_iter)?; // Account receiving the lamports
+        let system_program_account = next_account_info(accounts_iter)?; // System program for the transfer
+        if *system_program_account.key != system_program::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if recipient_account.key != recipient {
+            return Err(ProgramError::InvalidArgument); // Funds only go to the intended recipient
+        }
+        let treasury_kind = classify_treasury(account, treasury, &dao_data.treasury_account, program_id)?;
+        if treasury_kind == TreasuryKind::Separate && !treasury.is_signer {
+            return Err(ProgramError::MissingRequiredSignature); // A separate treasury wallet must authorize the transfer
+        }
+        transfer_from_treasury(treasury, recipient_account, system_program_account, treasury_kind, amount)
+    }
+}
+
+// Pay out `amount` of a claim, or everything that remains on it, and record the disbursement. Every path that pays a
+// claim comes through here, so the same gates, accounting, and transfer apply whichever instruction triggered it; the
+// payout accounts that follow are those `pay_from_treasury` expects.
+pub(crate) fn disburse_claim<'a, 'b>(
+    program_id: &Pubkey,
+    account: &AccountInfo<'a>,
+    dao_data: &mut HealthInsuranceDAO,
+    claim: &mut Claim,
+    amount: Option<u64>,
+    accounts_iter: &mut std::slice::Iter<'b, AccountInfo<'a>>,
+) -> ProgramResult {
+    // Payouts are only allowed once the treasury reserve policy has been initialized
+    if !dao_data.treasury.initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    // Members behind on their premiums aren't paid until they catch up
+    let now = Clock::get()?.unix_timestamp;
+    if let Some((lapsed, _)) = claim_participants(claim)
+        .into_iter()
+        .find(|(participant, _)| dao_data.members.iter().any(|m| m.member_address == *participant && !dao_data.coverage_active(m, now)))
+    {
+        msg!("Coverage for member {} has lapsed on an overdue premium", lapsed);
+        return Err(DaoError::CoverageLapsed.into());
+    }
+
+    let outstanding = dao_data.outstanding_claims;
+    if claim.status == ClaimStatus::Paid {
+        return Err(DaoError::ClaimAlreadyPaid.into());
+    }
+    if claim.condition.is_some() && !claim.condition_met {
+        return Err(ProgramError::InvalidAccountData); // Claim is contingent on an event that hasn't been attested
+    }
+    // Approvals alone aren't enough when the DAO wants a minimum number of verifiers to look at each claim
+    if claims::reviewer_count(claim) < dao_data.min_verifiers_reviewed as usize {
+        msg!("Claim {} has {} reviews but {} are required before payout", claim.claim_id, claims::reviewer_count(claim), dao_data.min_verifiers_reviewed);
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // High-value claims move in two phases: request the member's acknowledgment, then pay once it's given
+    let needs_ack = dao_data.ack_required_above > 0 && claim.amount >= dao_data.ack_required_above && !claim.acknowledged;
+    if claim.status == ClaimStatus::Verified && needs_ack {
+        claims::untrack_claim(dao_data, claim);
+        claim.status = ClaimStatus::PayoutPendingAck;
+        claim.ack_requested_at = now;
+        claims::track_claim(dao_data, claim);
+        msg!("Claim {} awaits acknowledgment from {} before payout", claim.claim_id, claim.member);
+        return Ok(());
+    }
+    if claim.status == ClaimStatus::PayoutPendingAck && now > claim.ack_requested_at.saturating_add(dao_data.ack_timeout_secs) {
+        // The member let the request lapse, so it's reissued with a fresh window
+        claim.ack_requested_at = now;
+        msg!("Acknowledgment request for claim {} expired and was reissued", claim.claim_id);
+        return Ok(());
+    }
+    if claim.status != ClaimStatus::Verified && claim.status != ClaimStatus::PartiallyPaid {
+        return Err(DaoError::ClaimNotVerified.into());
+    }
+
+    // Expensive categories can be capped below the member's overall coverage limit
+    if let Some(cap) = service_cap(&dao_data.service_coverage_caps, claim.service_type) {
+        if claim.amount > cap {
+            msg!("Claim {} of {} lamports exceeds the {:?} cap of {}", claim.claim_id, claim.amount, claim.service_type, cap);
+            return Err(DaoError::ServiceCapExceeded.into());
+        }
+    }
+
+    // Partial payouts disburse part of the remaining balance; the claim is paid once nothing remains
+    let remaining = claim.amount.saturating_sub(claim.paid_amount);
+    let disbursement = amount.unwrap_or(remaining);
+    if disbursement == 0 || disbursement > remaining {
+        msg!("Payout of {} lamports doesn't fit the {} lamports remaining on claim {}", disbursement, remaining, claim.claim_id);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Shared claims draw each participant's share from their own coverage
+    if let Some(uncovered) = uncovered_participant(&claim.shares, &dao_data.risk_profiles) {
+        msg!("Member {} lacks coverage for their share of claim {}", uncovered, claim.claim_id);
+        return Err(DaoError::CoverageExceeded.into());
+    }
+
+    // Members on an installment plan are only covered while the plan is current
+    if let Some((lapsed, _)) = claim_participants(claim)
+        .into_iter()
+        .find(|(participant, _)| dao_data.payment_plans.iter().any(|plan| plan.member == *participant && !plan_is_current(plan, now)))
+    {
+        msg!("Coverage for member {} has lapsed on a missed premium installment", lapsed);
+        return Err(DaoError::CoverageLapsed.into());
+    }
+
+    // Check if the claim amount is within the member's risk profile coverage
+    let risk_profile = dao_data.risk_profiles.iter().find(|rp| rp.member == claim.member).ok_or(ProgramError::InvalidAccountData)?; // No risk profile found for this member
+    if claim.shares.is_empty() && claim.amount > risk_profile.coverage_limit {
+        return Err(DaoError::CoverageExceeded.into());
+    }
+
+    // The coverage limit also bounds everything paid to each participant over their coverage year
+    let paid_after = claim.paid_amount + disbursement;
+    let mut coverage_used = Vec::new();
+    for (participant, drawn) in coverage_draws(claim, paid_after) {
+        let profile = dao_data.risk_profiles.iter().find(|rp| rp.member == participant).ok_or(ProgramError::InvalidAccountData)?;
+        let (period_start, used_coverage) = current_coverage_period(profile, now);
+        if used_coverage.saturating_add(drawn) > profile.coverage_limit {
+            msg!("Member {} has used {} of {} lamports of coverage this year", participant, used_coverage, profile.coverage_limit);
+            return Err(DaoError::CoverageExceeded.into());
+        }
+        coverage_used.push((participant, period_start, used_coverage.saturating_add(drawn)));
+    }
+
+    // The member covers the deductible and their coinsurance share; the treasury pays the remainder. Both
+    // apply to the claim as a whole, so a disbursement pays the treasury's share of its slice of the claim.
+    let payable = treasury_payout(paid_after, risk_profile.deductible, risk_profile.coinsurance_bps, dao_data.rounding_mode)
+        .saturating_sub(treasury_payout(claim.paid_amount, risk_profile.deductible, risk_profile.coinsurance_bps, dao_data.rounding_mode));
+
+    // In emergency mode every claim takes the same haircut: the share of outstanding claims the balance covers
+    let payable = if dao_data.emergency_mode {
+        let scaled = pro_rata_payout(payable, dao_data.treasury.balance, outstanding);
+        msg!("Emergency mode: payout of {} lamports scaled to {} against {} outstanding", payable, scaled, outstanding);
+        scaled
+    } else {
+        payable
+    };
+
+    // No single claim may take more than the configured share of the treasury
+    if dao_data.treasury.max_single_claim_bps > 0
+        && payable > max_single_payout(dao_data.treasury.balance, dao_data.treasury.max_single_claim_bps, dao_data.rounding_mode)
+    {
+        msg!("Claim payout {} exceeds the single-claim cap of {} bps", payable, dao_data.treasury.max_single_claim_bps);
+        return Err(DaoError::SingleClaimCapExceeded.into());
+    }
+
+    // Ensure there's enough balance in the treasury after accounting for the reserve ratio. In emergency mode the
+    // reserve is what's left to pay claims with, so only the balance itself bounds the payout.
+    let required_reserve = if dao_data.emergency_mode { 0 } else { required_reserve(&dao_data.treasury) };
+    // A reserve larger than the balance leaves nothing available rather than wrapping around
+    let available = dao_data.treasury.balance.saturating_sub(required_reserve);
+    if available < payable {
+        msg!("Treasury has {} lamports available above its {} reserve, below the {} payout", available, required_reserve, payable);
+        return Err(DaoError::InsufficientReserve.into());
+    }
+
+    // With risk bands configured, the claim draws only from its member's band, never another band's pool
+    if !dao_data.risk_bands.is_empty() {
+        let score = band_risk_score(&dao_data.risk_profiles, &claim.member);
+        let band = dao_data.risk_bands.iter_mut().find(|b| band_contains(b, score)).ok_or(ProgramError::InvalidAccountData)?; // No band covers this member
+        if band_available(band) < payable {
+            msg!("Risk band {}-{} has {} lamports available, below the {} payout", band.min_risk_score, band.max_risk_score, band_available(band), payable);
+            return Err(DaoError::RiskBandUnderfunded.into());
+        }
+        band.balance = band.balance.checked_sub(payable).ok_or(ProgramError::ArithmeticOverflow)?;
+    }
+
+    // Part of the payout is held back until the provider confirms the service, guarding against billing errors. The
+    // held-back funds stay in the treasury but are no longer part of its balance; releasing them only moves them.
+    let holdback = (payable as u128 * dao_data.holdback_bps as u128 / 10_000) as u64;
+    pay_from_treasury(program_id, account, dao_data, &claim.member, payable - holdback, accounts_iter)?;
+
+    dao_data.treasury.balance = dao_data.treasury.balance.checked_sub(payable).ok_or(ProgramError::ArithmeticOverflow)?;
+    claims::untrack_claim(dao_data, claim);
+    claim.paid_amount = paid_after;
+    claim.status = if paid_after == claim.amount { ClaimStatus::Paid } else { ClaimStatus::PartiallyPaid };
+    claims::track_claim(dao_data, claim);
+    if holdback > 0 {
+        claim.holdback = claim.holdback.checked_add(holdback).ok_or(ProgramError::ArithmeticOverflow)?;
+        claim.holdback_release_at = now.saturating_add(dao_data.holdback_timeout_secs);
+        msg!("Holding back {} lamports until the provider confirms or {}", holdback, claim.holdback_release_at);
+    }
+    for (participant, _) in claim_participants(claim) {
+        if let Some(record) = dao_data.members.iter_mut().find(|m| m.member_address == participant) {
+            record.end_claim_free_streak(now);
+        }
+    }
+    msg!("Claim payout of {} lamports processed, {} of {} disbursed (external ref {:?})", payable, claim.paid_amount, claim.amount, claim.external_ref);
+
+    for (participant, period_start, used_coverage) in coverage_used {
+        if let Some(profile) = dao_data.risk_profiles.iter_mut().find(|rp| rp.member == participant) {
+            profile.coverage_period_start = period_start;
+            profile.used_coverage = used_coverage;
+        }
+    }
+
+    // Keep each participant's cached coverage utilization current, matching the paid claims ledger
+    if claim.status == ClaimStatus::Paid {
+        for (participant, amount) in claim_participants(claim) {
+            if let Some(profile) = dao_data.risk_profiles.iter_mut().find(|rp| rp.member == participant) {
+                record_claim_paid(profile, amount);
+            }
+        }
+    }
+
+    // Keep a rolling window of payouts for reserve scaling
+    dao_data.recent_payouts[(dao_data.payouts_recorded % VOLATILITY_WINDOW as u64) as usize] = payable;
+    dao_data.payouts_recorded = dao_data.payouts_recorded.saturating_add(1);
+
+    // Signal operators when an outflow leaves the treasury close to its reserve floor
+    if let Some((balance, required)) = low_balance_alert(&dao_data.treasury, dao_data.alert_margin) {
+        sol_log_data(&[b"TreasuryLowBalance", &balance.to_le_bytes(), &required.to_le_bytes()]);
+        msg!("Treasury balance {} is within {} lamports of the required reserve {}", balance, dao_data.alert_margin, required);
+    }
+
+    // A treasury that can no longer cover its obligations switches to emergency mode
+    if !dao_data.emergency_mode && is_insolvent(&dao_data.treasury, dao_data.outstanding_claims) {
+        dao_data.emergency_mode = true;
+        sol_log_data(&[b"EmergencyMode", &dao_data.treasury.balance.to_le_bytes()]);
+        msg!("Treasury balance {} no longer covers its obligations; emergency mode activated", dao_data.treasury.balance);
+    }
+    Ok(())
+}
+
+// Benefits for each tier - Higher tiers pay more for more coverage and a lower deductible
+fn tier_benefits(tier: MemberTier) -> TierBenefits {
+    match tier {
+        MemberTier::Bronze => TierBenefits { premium: 100_000, coverage_limit: 10_000_000, deductible: 500_000, coinsurance_bps: 3_000 },
+        MemberTier::Silver => TierBenefits { premium: 200_000, coverage_limit: 25_000_000, deductible: 250_000, coinsurance_bps: 2_000 },
+        MemberTier::Gold => TierBenefits { premium: 400_000, coverage_limit: 60_000_000, deductible: 0, coinsurance_bps: 1_000 },
+    }
+}
+
+// Moves `member` onto `tier`, clearing any pending change. Profile terms still at the old tier's defaults take the new
+// tier's; terms the admin set by hand are kept.
+fn apply_tier(dao: &mut HealthInsuranceDAO, member: &Pubkey, tier: MemberTier) -> ProgramResult {
+    let record = dao.members.iter_mut().find(|m| m.member_address == *member).ok_or(DaoError::NotAMember)?;
+    let (old, new) = (tier_benefits(record.tier), tier_benefits(tier));
+    record.tier = tier;
+    record.pending_tier = None;
+
+    if let Some(risk_profile) = dao.risk_profiles.iter_mut().find(|rp| rp.member == *member) {
+        if risk_profile.coverage_limit == old.coverage_limit {
+            risk_profile.coverage_limit = new.coverage_limit;
+        }
+        if risk_profile.deductible == old.deductible {
+            risk_profile.deductible = new.deductible;
+        }
+        if risk_profile.coinsurance_bps == old.coinsurance_bps {
+            risk_profile.coinsurance_bps = new.coinsurance_bps;
+        }
+    } else {
+        dao.risk_profiles.push(RiskProfile {
+            member: *member,
+            risk_score: calculate_risk_score(member),
+            coverage_limit: new.coverage_limit,
+            deductible: new.deductible,
+            coinsurance_bps: new.coinsurance_bps,
+            claims_paid: 0,
+            utilization_bps: 0,
+            used_coverage: 0,
+            coverage_period_start: 0,
+        });
+    }
+    Ok(())
+}
+
+// Applies a tier change deferred by ChangeTier as the member starts a new premium period
+fn apply_pending_tier(dao: &mut HealthInsuranceDAO, member: &Pubkey) -> ProgramResult {
+    match dao.members.iter().find(|m| m.member_address == *member).and_then(|m| m.pending_tier) {
+        Some(tier) => apply_tier(dao, member, tier),
+        None => Ok(()),
+    }
+}
+
+// Placeholder for risk score calculation - This would be much more complex in practice
+pub(crate) fn calculate_risk_score(member: &Pubkey) -> u8 {
+    // Example: Member's risk score based on their key. In reality, this would involve health data, claim history, etc.
+    member.as_ref()[0] % 100 // Simplified for example, generates a score between 0 and 99
+}
+
+// Risk score from claim history alone: more paid claims and a higher share of premiums paid back out raise it,
+// longer membership lowers it. Scores stay within the 0-99 range of the key-derived score.
+fn history_risk_score(paid_claims: u32, paid_total: u64, premiums_paid: u64, tenure_secs: i64) -> u8 {
+    let claim_points = paid_claims.saturating_mul(RISK_POINTS_PER_PAID_CLAIM).min(MAX_CLAIM_COUNT_RISK_POINTS) as i64;
+    let loss_points = if premiums_paid == 0 {
+        if paid_total > 0 { MAX_LOSS_RATIO_RISK_POINTS } else { 0 }
+    } else {
+        (paid_total as u128 * 10 / premiums_paid as u128).min(MAX_LOSS_RATIO_RISK_POINTS as u128) as u64
+    };
+    let tenure_years = tenure_secs.max(0) / COVERAGE_PERIOD_SECS;
+    let tenure_reduction = tenure_years.saturating_mul(RISK_POINTS_OFF_PER_TENURE_YEAR).min(MAX_TENURE_RISK_REDUCTION);
+    (BASE_HISTORY_RISK_SCORE as i64 + claim_points + loss_points as i64 - tenure_reduction).clamp(0, 99) as u8
+}
+
+// Multiplier for the highest schedule step at or below the risk score; scores below every step pay the base premium
+fn risk_multiplier_bps(schedule: &[(u8, u16)], risk_score: u8) -> u16 {
+    schedule.iter().rev().find(|(min_score, _)| *min_score <= risk_score).map_or(10_000, |(_, bps)| *bps)
+}
+
+// Premium the member owes per period. With a base premium configured it is scaled by the multiplier for the
+// member's risk score, taken from their risk profile when they have one; otherwise the tier premium applies.
+// Either way the member's no-claims discount comes off the result.
+fn premium_quote(dao: &HealthInsuranceDAO, member: &Member) -> u64 {
+    let premium = if dao.base_premium == 0 {
+        tier_benefits(member.tier).premium
+    } else {
+        let risk_score = dao
+            .risk_profiles
+            .iter()
+            .find(|rp| rp.member == member.member_address)
+            .map_or_else(|| compute_risk_score(member), |rp| rp.risk_score);
+        let multiplier = risk_multiplier_bps(&dao.risk_multiplier_bps, risk_score);
+        (dao.base_premium as u128 * multiplier as u128 / 10_000).min(u64::MAX as u128) as u64
+    };
+    let discount_bps = no_claims_discount_bps(member.periods_claim_free, dao.no_claims_discount_step_bps, dao.no_claims_discount_max_bps);
+    (premium as u128 * (10_000 - discount_bps as u128) / 10_000) as u64
+}
+
+// Discount earned by `periods` consecutive claim-free periods, capped at `max_bps`
+fn no_claims_discount_bps(periods: u32, step_bps: u16, max_bps: u16) -> u16 {
+    (periods as u64 * step_bps as u64).min(max_bps.min(10_000) as u64) as u16
+}
+
+// Count a premium payment as another claim-free period when it comes at least a period after the previous payment
+// and no claim has been paid to the member since then
+fn accrue_claim_free_period(member: &mut Member, now: i64, period_secs: i64) {
+    let has_prior_payment = member.last_premium_ts > 0;
+    if has_prior_payment && member.last_claim_paid_at <= member.last_premium_ts && now.saturating_sub(member.last_premium_ts) >= period_secs {
+        member.periods_claim_free = member.periods_claim_free.saturating_add(1);
+    }
+}
+
+// Risk score adjusted for wellness engagement: one point off per HEARTBEATS_PER_RISK_POINT consecutive check-ins,
+// up to MAX_HEARTBEAT_RISK_REDUCTION. Risk bands stay keyed on the stored risk profile score.
+fn compute_risk_score(member: &Member) -> u8 {
+    let reduction = (member.heartbeat_streak / HEARTBEATS_PER_RISK_POINT).min(MAX_HEARTBEAT_RISK_REDUCTION as u32) as u8;
+    calculate_risk_score(&member.member_address).saturating_sub(reduction)
+}
+
+// Count a check-in toward the streak once per interval; a gap of more than two intervals starts the streak over
+fn record_heartbeat(member: &mut Member, now: i64) {
+    let since_last = now.saturating_sub(member.last_heartbeat_at);
+    if member.heartbeat_streak > 0 && since_last < HEARTBEAT_INTERVAL_SECS {
+        return; // Already counted for this interval
+    }
+    member.heartbeat_streak = if member.heartbeat_streak > 0 && since_last <= 2 * HEARTBEAT_INTERVAL_SECS {
+        member.heartbeat_streak.saturating_add(1)
+    } else {
+        1
+    };
+    member.last_heartbeat_at = now;
+}
+
+// Whether the risk score is assigned to the band
+fn band_contains(band: &RiskBand, risk_score: u8) -> bool {
+    band.min_risk_score <= risk_score && risk_score <= band.max_risk_score
+}
+
+// Band balance available for payouts once the band's reserve is held back; a reserve above the balance leaves nothing
+fn band_available(band: &RiskBand) -> u64 {
+    let reserve = (band.balance as u128 * band.reserve_ratio_bps as u128 / 10_000).min(u64::MAX as u128) as u64;
+    band.balance.saturating_sub(reserve)
+}
+
+// Bands need ordered bounds, a reserve within 100%, and no risk score assigned to two bands
+fn valid_risk_bands(bands: &[RiskBand]) -> bool {
+    bands.iter().enumerate().all(|(i, band)| {
+        band.min_risk_score <= band.max_risk_score
+            && band.reserve_ratio_bps <= 10_000
+            && bands[i + 1..].iter().all(|other| other.max_risk_score < band.min_risk_score || band.max_risk_score < other.min_risk_score)
+    })
+}
+
+// Credit a member's premium to their risk band; members outside every configured band can't be funded
+fn credit_risk_band(bands: &mut [RiskBand], profiles: &[RiskProfile], member: &Pubkey, amount: u64) -> ProgramResult {
+    if bands.is_empty() {
+        return Ok(());
+    }
+    let score = band_risk_score(profiles, member);
+    let band = bands.iter_mut().find(|b| band_contains(b, score)).ok_or(ProgramError::InvalidAccountData)?; // No band covers this member
+    band.balance = band.balance.checked_add(amount).ok_or(ProgramError::ArithmeticOverflow)?;
+    Ok(())
+}
+
+// Score that places a member in a risk band: the stored risk profile's score, so premiums are credited to and payouts
+// drawn from the same band. A payer without a profile falls back to the derived score.
+fn band_risk_score(profiles: &[RiskProfile], member: &Pubkey) -> u8 {
+    profiles.iter().find(|rp| rp.member == *member).map_or_else(|| calculate_risk_score(member), |rp| rp.risk_score)
+}
+
+// Mock oracle layout: price of one whole token in the base unit, then the unix timestamp it was published
+fn read_oracle_price(data: &[u8]) -> Result<(u64, i64), ProgramError> {
+    let price = read_u64(data, 0).map_err(|_| ProgramError::InvalidAccountData)?;
+    let published_at = read_i64(data, 8).map_err(|_| ProgramError::InvalidAccountData)?;
+    Ok((price, published_at))
+}
+
+// Value of a raw token amount in the base unit, given the price of one whole token
+fn vault_value(amount: u64, price: u64, decimals: u8) -> Result<u64, ProgramError> {
+    let scale = 10u128.checked_pow(decimals as u32).ok_or(ProgramError::InvalidArgument)?;
+    u64::try_from(amount as u128 * price as u128 / scale).map_err(|_| ProgramError::ArithmeticOverflow)
+}
+
+// Treasury layout written while the reserve ratio was an f32
+#[derive(BorshDeserialize)]
+struct LegacyTreasury {
+    balance: u64,
+    reserve_ratio: f32,
+    max_single_claim_bps: u16,
+    initialized: bool,
+    shared_pool: u64,
+}
+
+// Whether the account already reads as the current layout. A legacy reserve ratio of at least 2^-15 puts its high
+// bytes, read as the single-claim cap, above 10000 bps, so legacy accounts don't pass for current ones.
+fn in_current_layout(data: &[u8]) -> bool {
+    HealthInsuranceDAO::deserialize(&mut &data[..])
+        .map(|dao| dao.treasury.reserve_ratio_bps <= 10_000 && dao.treasury.max_single_claim_bps <= 10_000)
+        .unwrap_or(false)
+}
+
+// Rewrite a DAO account saved while the reserve ratio was an f32. Only the treasury changed size, so it is re-encoded
+// in place and everything after it shifts down into the bytes it freed.
+fn migrate_reserve_ratio(account: &AccountInfo, admin: &AccountInfo) -> ProgramResult {
+    if in_current_layout(&account.data.borrow()) {
+        msg!("DAO account already uses basis-point reserve ratios");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let mut data = account.data.borrow_mut();
+    let (treasury_start, tail_start, legacy) = {
+        let mut rest: &[u8] = &data;
+        let dao_admin = Pubkey::deserialize(&mut rest)?;
+        let members = Vec::<Member>::deserialize(&mut rest)?;
+        if !admin.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        if !(*admin.key == dao_admin || members.iter().any(|m| m.member_address == *admin.key && m.role == Role::Admin)) {
+            return Err(DaoError::Unauthorized.into()); // Only the admin can migrate the account
+        }
+        let treasury_start = data.len() - rest.len();
+        let legacy = LegacyTreasury::deserialize(&mut rest)?;
+        (treasury_start, data.len() - rest.len(), legacy)
+    };
+    if !(0.0..=1.0).contains(&legacy.reserve_ratio) {
+        return Err(ProgramError::InvalidAccountData); // Not a reserve ratio the legacy program could have stored
+    }
+
+    let treasury = Treasury {
+        balance: legacy.balance,
+        reserve_ratio_bps: (legacy.reserve_ratio * 10_000.0).round() as u16,
+        max_single_claim_bps: legacy.max_single_claim_bps,
+        initialized: legacy.initialized,
+        shared_pool: legacy.shared_pool,
+        token_mint: None,
+    };
+    let encoded = treasury.try_to_vec()?;
+    let tail_dest = treasury_start + encoded.len();
+    let end = data.len();
+    data.copy_within(tail_start..end, tail_dest);
+    data[treasury_start..tail_dest].copy_from_slice(&encoded);
+    data[end - (tail_start - tail_dest)..].fill(0);
+    drop(data);
+
+    // The rewritten account must read back in the current layout
+    HealthInsuranceDAO::load(account)?;
+    msg!("Reserve ratio {} migrated to {} bps", legacy.reserve_ratio, treasury.reserve_ratio_bps);
+    Ok(())
+}
+
+// Portion of the treasury balance that must be held back under the reserve ratio
+fn required_reserve(treasury: &Treasury) -> u64 {
+    (treasury.balance as u128 * treasury.reserve_ratio_bps as u128 / 10_000).min(u64::MAX as u128) as u64
+}
+
+// Amount the treasury should keep liquid: the reserve ratio applied to all treasury funds, never below the
+// liquidity floor, and never more than the treasury holds in total
+fn target_reserve(treasury: &Treasury, yield_balance: u64, min_liquidity: u64) -> u64 {
+    let total = treasury.balance.saturating_add(yield_balance);
+    let by_ratio = (total as u128 * treasury.reserve_ratio_bps as u128 / 10_000) as u64;
+    by_ratio.max(min_liquidity).min(total)
+}
+
+// Claims approved for payment whose amount hasn't all been paid; their unpaid amounts are the outstanding obligations
+pub(crate) fn awaiting_payout(claim: &Claim) -> bool {
+    matches!(claim.status, ClaimStatus::Verified | ClaimStatus::PayoutPendingAck | ClaimStatus::PartiallyPaid)
+}
+
+// Whether the balance falls short of the required reserve. The ratio reserve scales with the balance, so it is the
+// approved claims still to be paid (`outstanding`) that a shrinking treasury stops covering.
+fn is_insolvent(treasury: &Treasury, outstanding: u64) -> bool {
+    treasury.balance < required_reserve(treasury).max(outstanding)
+}
+
+// Payout scaled down to the share of outstanding obligations the balance covers; unscaled while the balance covers them all
+fn pro_rata_payout(payable: u64, balance: u64, outstanding: u64) -> u64 {
+    if outstanding == 0 || balance >= outstanding {
+        return payable;
+    }
+    (payable as u128 * balance as u128 / outstanding as u128) as u64
+}
+
+// Current balance and required reserve when the balance is within `alert_margin` of the reserve floor
+fn low_balance_alert(treasury: &Treasury, alert_margin: u64) -> Option<(u64, u64)> {
+    let required = required_reserve(treasury);
+    if treasury.balance <= required.saturating_add(alert_margin) {
+        Some((treasury.balance, required))
+    } else {
+        None
+    }
+}
+
+// Cap configured for the service type, if any; claims of uncapped types are bounded by the coverage limit alone
+fn service_cap(caps: &[(ServiceType, u64)], service_type: ServiceType) -> Option<u64> {
+    caps.iter().find(|(t, _)| *t == service_type).map(|(_, cap)| *cap)
+}
+
+// Paid, rejected, and withdrawn claims no longer draw on the treasury
+pub(crate) fn claim_settled(claim: &Claim) -> bool {
+    matches!(claim.status, ClaimStatus::Paid | ClaimStatus::Rejected | ClaimStatus::Withdrawn)
+}
+
+// Claims still awaiting review, payout, or the outcome of an appeal
+pub(crate) fn claim_in_flight(claim: &Claim) -> bool {
+    matches!(
+        claim.status,
+        ClaimStatus::Pending | ClaimStatus::Verified | ClaimStatus::Appealed | ClaimStatus::PayoutPendingAck | ClaimStatus::PartiallyPaid
+    )
+}
+
+// Part of the member's last premium not yet earned by the DAO: what they paid for the period, pro-rated by the time
+// left in it. The period starts at that payment and runs the premium period, or a coverage year when lapse tracking
+// is off. Members who never paid, or whose coverage has lapsed, have nothing unearned.
+fn unearned_premium(dao: &HealthInsuranceDAO, member: &Member, now: i64) -> u64 {
+    if member.last_premium_ts == 0 || !dao.coverage_active(member, now) {
+        return 0;
+    }
+    let period = if dao.premium_period_secs > 0 { dao.premium_period_secs } else { COVERAGE_PERIOD_SECS };
+    let remaining = member.last_premium_ts.saturating_add(period).saturating_sub(now).clamp(0, period);
+    (member.last_premium_amount as u128 * remaining as u128 / period as u128) as u64
+}
+
+// Compute the full solvency snapshot from the treasury and the unpaid amount of claims not yet settled
+fn solvency_metrics(treasury: &Treasury, open_claim_exposure: u64) -> SolvencyMetrics {
+    let required_reserve = required_reserve(treasury);
+    let available_for_claims = treasury.balance.saturating_sub(required_reserve);
+    let solvency_ratio_bps = if open_claim_exposure == 0 {
+        u64::MAX
+    } else {
+        (available_for_claims as u128 * 10_000 / open_claim_exposure as u128).min(u64::MAX as u128) as u64
+    };
+    SolvencyMetrics {
+        balance: treasury.balance,
+        required_reserve,
+        available_for_claims,
+        open_claim_exposure,
+        solvency_ratio_bps,
+    }
+}
+
+// Multiply `value` by a basis-point fraction, rounding the result in the given direction
+fn apply_bps(value: u64, bps: u16, rounding_mode: RoundingMode) -> u64 {
+    let numerator = value as u128 * bps as u128;
+    let result = match rounding_mode {
+        RoundingMode::FavorMember => numerator.div_ceil(10_000),
+        RoundingMode::FavorPool => numerator / 10_000,
+        RoundingMode::Nearest => (numerator + 5_000) / 10_000,
+    };
+    result as u64
+}
+
+// Amount the treasury pays on a claim after the member's deductible and coinsurance share
+fn treasury_payout(amount: u64, deductible: u64, coinsurance_bps: u16, rounding_mode: RoundingMode) -> u64 {
+    let after_deductible = amount.saturating_sub(deductible);
+    apply_bps(after_deductible, 10_000u16.saturating_sub(coinsurance_bps), rounding_mode)
+}
+
+// Run the payout math for a prospective claim without touching state
+fn estimate_payout(amount: u64, risk_profile: &RiskProfile, rounding_mode: RoundingMode) -> PayoutEstimate {
+    let within_coverage = amount <= risk_profile.coverage_limit;
+    let payable = if within_coverage {
+        treasury_payout(amount, risk_profile.deductible, risk_profile.coinsurance_bps, rounding_mode)
+    } else {
+        0
+    };
+    PayoutEstimate {
+        payable,
+        member_share: if within_coverage { amount - payable } else { amount },
+        within_coverage,
+    }
+}
+
+// Largest payout a single claim may receive given the treasury balance and cap in basis points
+fn max_single_payout(balance: u64, max_single_claim_bps: u16, rounding_mode: RoundingMode) -> u64 {
+    apply_bps(balance, max_single_claim_bps, rounding_mode)
+}
+
+// A plan is current when every installment due by `now` has been paid; the first is due at the start
+pub(crate) fn plan_is_current(plan: &PaymentPlan, now: i64) -> bool {
+    if plan.amount_paid >= plan.total_owed {
+        return true;
+    }
+    if now < plan.start_at {
+        return true;
+    }
+    let elapsed_intervals = (now - plan.start_at) / plan.interval_secs.max(1);
+    let due = (elapsed_intervals + 1).min(plan.installments_total as i64);
+    plan.installments_paid as i64 >= due
+}
+
+// Members a claim draws coverage from, with the amount drawn from each
+pub(crate) fn claim_participants(claim: &Claim) -> Vec<(Pubkey, u64)> {
+    if claim.shares.is_empty() {
+        vec![(claim.member, claim.amount)]
+    } else {
+        claim.shares.iter().map(|share| (share.member, share.amount)).collect()
+    }
+}
+
+// Coverage each participant draws as a claim's paid total rises to `paid_after`. A shared claim's disbursement is
+// split in proportion to the shares, so each participant has drawn exactly their share once the claim is paid.
+fn coverage_draws(claim: &Claim, paid_after: u64) -> Vec<(Pubkey, u64)> {
+    let drawn_by = |amount: u64, paid: u64| (amount as u128 * paid as u128 / claim.amount.max(1) as u128) as u64;
+    claim_participants(claim)
+        .into_iter()
+        .map(|(participant, amount)| (participant, drawn_by(amount, paid_after) - drawn_by(amount, claim.paid_amount)))
+        .collect()
+}
+
+// Start of the profile's coverage year and the coverage used in it as of `now`; once a year has passed since the
+// recorded start, a fresh year begins at `now` with nothing used
+fn current_coverage_period(profile: &RiskProfile, now: i64) -> (i64, u64) {
+    if now >= profile.coverage_period_start.saturating_add(COVERAGE_PERIOD_SECS) {
+        (now, 0)
+    } else {
+        (profile.coverage_period_start, profile.used_coverage)
+    }
+}
+
+// Share of the coverage limit used by paid claims, in basis points and capped at 100%
+fn utilization_bps(claims_paid: u64, coverage_limit: u64) -> u16 {
+    if coverage_limit == 0 {
+        return if claims_paid > 0 { 10_000 } else { 0 };
+    }
+    (claims_paid as u128 * 10_000 / coverage_limit as u128).min(10_000) as u16
+}
+
+// Add a paid claim to a risk profile and refresh its cached utilization
+fn record_claim_paid(profile: &mut RiskProfile, amount: u64) {
+    profile.claims_paid = profile.claims_paid.saturating_add(amount);
+    profile.utilization_bps = utilization_bps(profile.claims_paid, profile.coverage_limit);
+}
+
+// Volatility of the payout amounts as their coefficient of variation (standard deviation over mean), in basis points
+fn payout_volatility_bps(amounts: &[u64]) -> u64 {
+    if amounts.len() < 2 {
+        return 0;
+    }
+    let count = amounts.len() as f64;
+    let mean = amounts.iter().map(|&a| a as f64).sum::<f64>() / count;
+    if mean == 0.0 {
+        return 0;
+    }
+    let variance = amounts.iter().map(|&a| (a as f64 - mean).powi(2)).sum::<f64>() / count;
+    (variance.sqrt() / mean * 10_000.0) as u64
+}
+
+// Reserve ratio interpolated between the bounds, reaching the maximum once volatility hits 100%
+fn scaled_reserve_ratio_bps(volatility_bps: u64, min_bps: u16, max_bps: u16) -> u16 {
+    let span = max_bps.saturating_sub(min_bps) as u64;
+    min_bps + (span * volatility_bps.min(10_000) / 10_000) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claims::claim_address;
+    use crate::instruction::{DaoInstruction, SecurityInstruction};
+    use crate::testing::Ledger;
+    use crate::{process_instruction, ProposalAccount, TokenVault};
+    use solana_program::instruction::{AccountMeta, Instruction};
+    use solana_program_test::*;
+    use solana_sdk::{
+        account::Account,
+        instruction::InstructionError,
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    };
+
+    #[tokio::test]
+    async fn test_premium_payment() {
+        // Test setup and premium payment logic goes here
+    }
+
+    #[tokio::test]
+    async fn test_claim_payout() {
+        // Test setup and claim payout logic goes here
+    }
+
+    // Build a program-owned DAO account holding the given state
+    // Instruction data routing `instruction` to the finance module
+    fn finance_data(instruction: FinanceInstruction) -> Vec<u8> {
+        DaoInstruction::Finance(instruction).try_to_vec().unwrap()
+    }
+
+    fn dao_account(program_id: Pubkey, dao: &HealthInsuranceDAO) -> Account {
+        let data = dao.try_to_vec().unwrap();
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: program_id,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    // DAO state with one claim of `amount` from `member`, covered by a matching risk profile
+    fn dao_with_claim(member: Pubkey, amount: u64, treasury: Treasury) -> Ledger {
+        let dao = HealthInsuranceDAO {
+            admin: Pubkey::new_unique(),
+            members: vec![member_record(member, MemberTier::Gold)],
+            treasury,
+            risk_profiles: vec![RiskProfile {
+                member,
+                risk_score: calculate_risk_score(&member),
+                coverage_limit: u64::MAX,
+                deductible: 0,
+                coinsurance_bps: 0,
+                claims_paid: 0,
+                utilization_bps: 0,
+                used_coverage: 0,
+                coverage_period_start: 0,
+            }],
+            rounding_mode: RoundingMode::FavorPool,
+            ..Default::default()
+        };
+        Ledger::new(dao, vec![Claim { claim_id: 0, member, amount, status: ClaimStatus::Verified, ..Default::default() }])
+    }
+
+    fn member_record(member_address: Pubkey, tier: MemberTier) -> Member {
+        Member {
+            member_address,
+            tier,
+            ..Default::default()
+        }
+    }
+
+    // Program test holding `dao` under `admin` in a DAO account that is also its treasury, funded with the recorded
+    // treasury balance above rent, and a wallet for `member` to pay premiums from and receive payouts
+    fn treasury_test(program_id: Pubkey, dao_key: Pubkey, dao: &Ledger, admin: Pubkey, member: Pubkey) -> ProgramTest {
+        let mut dao = dao.clone();
+        dao.admin = admin;
+        dao.treasury_account = dao_key;
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, dao.treasury.balance);
+        program_test.add_account(
+            member,
+            Account { lamports: 1_000_000_000, data: Vec::new(), owner: system_program::id(), executable: false, rent_epoch: 0 },
+        );
+        program_test
+    }
+
+    // Premium payment of `amount` from `member` into the DAO account acting as treasury
+    fn premium_instruction(program_id: Pubkey, dao_key: Pubkey, member: Pubkey, amount: u64) -> Instruction {
+        Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                       // DAO account
+                AccountMeta::new(member, true),                         // Member paying the premium
+                AccountMeta::new(dao_key, false),                       // Treasury
+                AccountMeta::new_readonly(system_program::id(), false), // System program
+            ],
+            data: finance_data(FinanceInstruction::PayPremium { amount, nonce: 0 }),
+        }
+    }
+
+    // Payout of `amount` of claim `claim_id`, or all that remains on it, released by `authority` and paid from the
+    // DAO account to `member`
+    fn payout_instruction(program_id: Pubkey, dao_key: Pubkey, authority: Pubkey, member: Pubkey, claim_id: u64, amount: Option<u64>) -> Instruction {
+        Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                       // DAO account
+                AccountMeta::new_readonly(authority, true),             // Admin or verifier releasing the payout
+                AccountMeta::new(claim_address(&program_id, &dao_key, claim_id).0, false), // Claim account
+                AccountMeta::new(dao_key, false),                       // Treasury
+                AccountMeta::new(member, false),                        // Member receiving the payout
+                AccountMeta::new_readonly(system_program::id(), false), // System program
+            ],
+            data: finance_data(FinanceInstruction::PayoutClaim { claim_id, amount }),
+        }
+    }
+
+    // Submit a payout for claim 0 and return the processing result
+    async fn process_payout(dao: &Ledger, member: &Keypair) -> Result<(), BanksClientError> {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+
+        let admin = Keypair::new();
+
+        let (mut banks_client, payer, recent_blockhash) = treasury_test(program_id, dao_key, dao, admin.pubkey(), member.pubkey()).start().await;
+
+        let instruction = payout_instruction(program_id, dao_key, admin.pubkey(), member.pubkey(), 0, None);
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[&payer, &admin],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(transaction).await
+    }
+
+    // Pay `amount` of claim 0 and return the result and final DAO state
+    async fn pay_claim(dao: &Ledger, member: &Keypair, amount: u64) -> (Result<(), BanksClientError>, Ledger) {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+
+        let admin = Keypair::new();
+
+        let (mut banks_client, payer, recent_blockhash) = treasury_test(program_id, dao_key, dao, admin.pubkey(), member.pubkey()).start().await;
+
+        let instruction = payout_instruction(program_id, dao_key, admin.pubkey(), member.pubkey(), 0, Some(amount));
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer, &admin], recent_blockhash);
+        let result = banks_client.process_transaction(transaction).await;
+        (result, dao.reload(&mut banks_client, program_id, dao_key, &[]).await)
+    }
+
+    #[tokio::test]
+    async fn test_payout_requires_admin_or_verifier() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let admin = Keypair::new();
+        let member = Keypair::new();
+        let verifier = Keypair::new();
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 0, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(member.pubkey(), 1_000_000, treasury);
+        dao.members.push(Member { role: Role::Verifier, ..member_record(verifier.pubkey(), MemberTier::Gold) });
+
+        let (mut banks_client, payer, recent_blockhash) = treasury_test(program_id, dao_key, &dao, admin.pubkey(), member.pubkey()).start().await;
+
+        // The claimant can't release their own payout
+        let payout = payout_instruction(program_id, dao_key, member.pubkey(), member.pubkey(), 0, None);
+        let transaction = Transaction::new_signed_with_payer(&[payout], Some(&payer.pubkey()), &[&payer, &member], recent_blockhash);
+        assert_eq!(
+            banks_client.process_transaction(transaction).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::Unauthorized as u32)),
+        );
+
+        // A verifier can
+        let payout = payout_instruction(program_id, dao_key, verifier.pubkey(), member.pubkey(), 0, None);
+        let transaction = Transaction::new_signed_with_payer(&[payout], Some(&payer.pubkey()), &[&payer, &verifier], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let after = dao.reload(&mut banks_client, program_id, dao_key, &[]).await;
+        assert_eq!(after.claims[0].status, ClaimStatus::Paid);
+    }
+
+    #[tokio::test]
+    async fn test_claim_paid_in_two_disbursements() {
+        let member = Keypair::new();
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 0, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let dao = dao_with_claim(member.pubkey(), 1_000_000, treasury);
+
+        // 40% first leaves the claim partially paid
+        let (result, dao) = pay_claim(&dao, &member, 400_000).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims[0].status, ClaimStatus::PartiallyPaid);
+        assert_eq!(dao.claims[0].paid_amount, 400_000);
+        assert_eq!(dao.treasury.balance, 9_600_000);
+
+        // More than the remaining 60% is rejected
+        let (result, dao) = pay_claim(&dao, &member, 600_001).await;
+        assert!(result.is_err());
+        assert_eq!(dao.treasury.balance, 9_600_000);
+
+        // The remaining 60% settles the claim
+        let (result, dao) = pay_claim(&dao, &member, 600_000).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.claims[0].status, ClaimStatus::Paid);
+        assert_eq!(dao.claims[0].paid_amount, 1_000_000);
+        assert_eq!(dao.treasury.balance, 9_000_000);
+    }
+
+    // DAO with a 1M lamport claim of `service_type` against a 2M coverage limit, where surgery is capped at 500k
+    fn dao_with_service_caps(member: Pubkey, service_type: ServiceType) -> Ledger {
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 0, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(member, 1_000_000, treasury);
+        dao.claims[0].service_type = service_type;
+        dao.risk_profiles[0].coverage_limit = 2_000_000;
+        dao.service_coverage_caps = vec![(ServiceType::Surgery, 500_000)];
+        dao
+    }
+
+    #[tokio::test]
+    async fn test_claim_over_service_cap_rejected() {
+        let member = Keypair::new();
+        let dao = dao_with_service_caps(member.pubkey(), ServiceType::Surgery);
+
+        // Within the member's coverage limit, but above the surgery cap
+        let error = process_payout(&dao, &member).await.unwrap_err().unwrap();
+        assert_eq!(error, TransactionError::InstructionError(0, InstructionError::Custom(DaoError::ServiceCapExceeded as u32)));
+    }
+
+    #[tokio::test]
+    async fn test_uncapped_service_falls_back_to_coverage_limit() {
+        let member = Keypair::new();
+        let dao = dao_with_service_caps(member.pubkey(), ServiceType::Pharmacy);
+        assert!(process_payout(&dao, &member).await.is_ok());
+
+        // Without a pharmacy cap, the member's coverage limit is what bounds the claim
+        let mut dao = dao_with_service_caps(member.pubkey(), ServiceType::Pharmacy);
+        dao.risk_profiles[0].coverage_limit = 500_000;
+        let error = process_payout(&dao, &member).await.unwrap_err().unwrap();
+        assert_eq!(error, TransactionError::InstructionError(0, InstructionError::Custom(DaoError::CoverageExceeded as u32)));
+    }
+
+    // Pay claim `claim_id` in full with the cluster clock at `now`, returning the result and final DAO state
+    async fn pay_claim_at(dao: &Ledger, member: &Keypair, claim_id: u64, now: i64) -> (Result<(), BanksClientError>, Ledger) {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+
+        let admin = Keypair::new();
+
+        let mut context = treasury_test(program_id, dao_key, dao, admin.pubkey(), member.pubkey()).start_with_context().await;
+        context.set_sysvar(&Clock { unix_timestamp: now, ..Clock::default() });
+
+        let instruction = payout_instruction(program_id, dao_key, admin.pubkey(), member.pubkey(), claim_id, None);
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&context.payer.pubkey()), &[&context.payer, &admin], context.last_blockhash);
+        let result = context.banks_client.process_transaction(transaction).await;
+        (result, dao.reload(&mut context.banks_client, program_id, dao_key, &[]).await)
+    }
+
+    #[tokio::test]
+    async fn test_annual_coverage_exhausted_then_rolled_over() {
+        let member = Keypair::new();
+        let now = 1_000 * 86_400;
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 0, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+
+        // Three 1M claims against a 2M annual coverage limit
+        let mut dao = dao_with_claim(member.pubkey(), 1_000_000, treasury);
+        dao.risk_profiles[0].coverage_limit = 2_000_000;
+        for claim_id in 1..3 {
+            dao.claims.push(Claim { claim_id, member: member.pubkey(), amount: 1_000_000, status: ClaimStatus::Verified, ..Default::default() });
+        }
+
+        let (result, dao) = pay_claim_at(&dao, &member, 0, now).await;
+        assert!(result.is_ok());
+        let (result, dao) = pay_claim_at(&dao, &member, 1, now + 86_400).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.risk_profiles[0].used_coverage, 2_000_000);
+        assert_eq!(dao.risk_profiles[0].coverage_period_start, now);
+
+        // Each claim fits the limit on its own, but the year's coverage is used up
+        let (result, dao) = pay_claim_at(&dao, &member, 2, now + 364 * 86_400).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::CoverageExceeded as u32)),
+        );
+
+        // A year after the first payout the coverage rolls over
+        let (result, dao) = pay_claim_at(&dao, &member, 2, now + 365 * 86_400).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.risk_profiles[0].used_coverage, 1_000_000);
+        assert_eq!(dao.risk_profiles[0].coverage_period_start, now + 365 * 86_400);
+    }
+
+    #[tokio::test]
+    async fn test_claim_within_single_claim_cap() {
+        let member = Keypair::new();
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 0, max_single_claim_bps: 1_000, initialized: true, shared_pool: 0, token_mint: None };
+
+        // 10% cap of 10M lamports allows a 1M claim
+        let dao = dao_with_claim(member.pubkey(), 1_000_000, treasury);
+        assert!(process_payout(&dao, &member).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_claim_exceeding_single_claim_cap() {
+        let member = Keypair::new();
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 0, max_single_claim_bps: 1_000, initialized: true, shared_pool: 0, token_mint: None };
+
+        // One lamport over the 10% cap is rejected even though the treasury could cover it
+        let dao = dao_with_claim(member.pubkey(), 1_000_001, treasury);
+        assert_eq!(
+            process_payout(&dao, &member).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::SingleClaimCapExceeded as u32)),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_payout_blocked_before_treasury_init() {
+        let member = Keypair::new();
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 0, max_single_claim_bps: 0, initialized: false, shared_pool: 0, token_mint: None };
+
+        // A well-funded treasury still refuses payouts until it has been initialized
+        let dao = dao_with_claim(member.pubkey(), 1_000, treasury);
+        assert!(process_payout(&dao, &member).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_second_treasury_init_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let admin = Keypair::new();
+
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 0, max_single_claim_bps: 0, initialized: false, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(Pubkey::new_unique(), 1_000, treasury);
+        dao.admin = admin.pubkey();
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, 0);
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let init = |reserve_ratio_bps: u16| {
+            let data = finance_data(FinanceInstruction::InitializeTreasury { reserve_ratio_bps: Some(reserve_ratio_bps) });
+            let instruction = Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(dao_key, false),               // DAO account
+                    AccountMeta::new_readonly(admin.pubkey(), true), // Admin
+                ],
+                data,
+            };
+            Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer, &admin], recent_blockhash)
+        };
+
+        banks_client.process_transaction(init(3_000)).await.unwrap();
+        assert_eq!(
+            banks_client.process_transaction(init(5_000)).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::AccountAlreadyInitialized),
+        );
+
+        // The first initialization, and everything else in the DAO, is left untouched
+        let after = dao.reload(&mut banks_client, program_id, dao_key, &[]).await;
+        assert!(after.treasury.initialized);
+        assert_eq!(after.treasury.reserve_ratio_bps, 3_000);
+        assert_eq!(after.treasury.balance, 10_000_000);
+        assert_eq!(after.members.len(), 1);
+        assert_eq!(after.claims.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_payout_blocked_when_reserve_exceeds_balance() {
+        let member = Keypair::new();
+        // A misconfigured ratio above 100% makes the required reserve larger than the balance
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 15_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+
+        let dao = dao_with_claim(member.pubkey(), 1_000, treasury);
+        assert!(required_reserve(&dao.treasury) > dao.treasury.balance);
+        assert_eq!(
+            process_payout(&dao, &member).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::InsufficientReserve as u32)),
+        );
+    }
+
+    #[test]
+    fn test_solvency_metrics_healthy_treasury() {
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 2_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let member = Pubkey::new_unique();
+        let claims = vec![
+            Claim { claim_id: 0, member, amount: 1_000_000, status: ClaimStatus::Verified, ..Default::default() },
+            Claim { claim_id: 1, member, amount: 3_000_000, status: ClaimStatus::Paid, ..Default::default() }, // Already paid, not exposure
+            Claim { claim_id: 2, member, amount: 1_000_000, status: ClaimStatus::Pending, ..Default::default() },
+        ];
+        let exposure = Ledger::new(HealthInsuranceDAO::default(), claims).tallied_dao().open_claim_exposure;
+
+        let metrics = solvency_metrics(&treasury, exposure);
+        assert_eq!(metrics.required_reserve, 2_000_000);
+        assert_eq!(metrics.available_for_claims, 8_000_000);
+        assert_eq!(metrics.open_claim_exposure, 2_000_000);
+        assert_eq!(metrics.solvency_ratio_bps, 40_000); // 4x coverage of open claims
+    }
+
+    #[test]
+    fn test_solvency_metrics_under_reserved_treasury() {
+        let treasury = Treasury { balance: 1_000_000, reserve_ratio_bps: 5_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let member = Pubkey::new_unique();
+        let claims = vec![Claim { claim_id: 0, member, amount: 2_000_000, status: ClaimStatus::Verified, ..Default::default() }];
+        let exposure = Ledger::new(HealthInsuranceDAO::default(), claims).tallied_dao().open_claim_exposure;
+
+        let metrics = solvency_metrics(&treasury, exposure);
+        assert_eq!(metrics.required_reserve, 500_000);
+        assert_eq!(metrics.available_for_claims, 500_000);
+        assert_eq!(metrics.open_claim_exposure, 2_000_000);
+        assert_eq!(metrics.solvency_ratio_bps, 2_500); // Only a quarter of open claims can be covered
+    }
+
+    #[test]
+    fn test_solvency_metrics_without_open_claims() {
+        let treasury = Treasury { balance: 1_000_000, reserve_ratio_bps: 2_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        assert_eq!(solvency_metrics(&treasury, 0).solvency_ratio_bps, u64::MAX);
+    }
+
+    #[test]
+    fn test_tier_premiums_and_coverage() {
+        let bronze = tier_benefits(MemberTier::Bronze);
+        let silver = tier_benefits(MemberTier::Silver);
+        let gold = tier_benefits(MemberTier::Gold);
+
+        assert_eq!((bronze.premium, bronze.coverage_limit, bronze.deductible, bronze.coinsurance_bps), (100_000, 10_000_000, 500_000, 3_000));
+        assert_eq!((silver.premium, silver.coverage_limit, silver.deductible, silver.coinsurance_bps), (200_000, 25_000_000, 250_000, 2_000));
+        assert_eq!((gold.premium, gold.coverage_limit, gold.deductible, gold.coinsurance_bps), (400_000, 60_000_000, 0, 1_000));
+    }
+
+    // A Bronze member on the tier's default terms
+    fn bronze_member_dao(member: Pubkey) -> Ledger {
+        let mut dao = dao_with_claim(member, 1_000, Treasury { balance: 0, reserve_ratio_bps: 2_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None });
+        let bronze = tier_benefits(MemberTier::Bronze);
+        dao.members[0].tier = MemberTier::Bronze;
+        dao.risk_profiles[0].coverage_limit = bronze.coverage_limit;
+        dao.risk_profiles[0].deductible = bronze.deductible;
+        dao.risk_profiles[0].coinsurance_bps = bronze.coinsurance_bps;
+        dao
+    }
+
+    // Move `member` to `tier`, then pay `premium` if one is given, returning the final DAO state
+    async fn change_tier(dao: &Ledger, member: &Keypair, tier: MemberTier, premium: Option<u64>) -> HealthInsuranceDAO {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let (mut banks_client, payer, recent_blockhash) = treasury_test(program_id, dao_key, dao, dao.admin, member.pubkey()).start().await;
+
+        let mut instructions = vec![Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                 // DAO account
+                AccountMeta::new_readonly(member.pubkey(), true), // Member changing tier
+            ],
+            data: finance_data(FinanceInstruction::ChangeTier { tier }),
+        }];
+        instructions.extend(premium.map(|amount| premium_instruction(program_id, dao_key, member.pubkey(), amount)));
+        let transaction = Transaction::new_signed_with_payer(&instructions, Some(&payer.pubkey()), &[&payer, member], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let account = banks_client.get_account(dao_key).await.unwrap().unwrap();
+        HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_tier_upgrade_takes_effect() {
+        let member = Keypair::new();
+        let dao = change_tier(&bronze_member_dao(member.pubkey()), &member, MemberTier::Gold, None).await;
+        assert_eq!(dao.members[0].tier, MemberTier::Gold);
+        assert_eq!(dao.risk_profiles[0].coverage_limit, 60_000_000);
+        assert_eq!(dao.risk_profiles[0].deductible, 0);
+        assert_eq!(dao.risk_profiles[0].coinsurance_bps, 1_000);
+    }
+
+    #[tokio::test]
+    async fn test_mid_period_tier_change_waits_for_next_premium() {
+        let member = Keypair::new();
+        let mut dao = bronze_member_dao(member.pubkey());
+        dao.members[0].last_premium_ts = 1; // The member is covered by a Bronze premium already paid
+
+        // The Bronze period runs its course on Bronze terms
+        let after = change_tier(&dao, &member, MemberTier::Gold, None).await;
+        assert_eq!((after.members[0].tier, after.members[0].pending_tier), (MemberTier::Bronze, Some(MemberTier::Gold)));
+        assert_eq!(after.risk_profiles[0].coverage_limit, tier_benefits(MemberTier::Bronze).coverage_limit);
+
+        // The next premium is quoted and paid at the Gold rate, and Gold coverage starts with it
+        let after = change_tier(&dao, &member, MemberTier::Gold, Some(tier_benefits(MemberTier::Gold).premium)).await;
+        assert_eq!((after.members[0].tier, after.members[0].pending_tier), (MemberTier::Gold, None));
+        assert_eq!(after.risk_profiles[0].coverage_limit, 60_000_000);
+        assert_eq!(after.members[0].premiums_paid, tier_benefits(MemberTier::Gold).premium);
+    }
+
+    #[tokio::test]
+    async fn test_tier_change_keeps_admin_overrides() {
+        let member = Keypair::new();
+        let mut dao = bronze_member_dao(member.pubkey());
+        dao.risk_profiles[0].coverage_limit = 15_000_000; // Raised by the admin above the Bronze default
+
+        let dao = change_tier(&dao, &member, MemberTier::Gold, None).await;
+        assert_eq!(dao.risk_profiles[0].coverage_limit, 15_000_000);
+        assert_eq!(dao.risk_profiles[0].deductible, 0);
+        assert_eq!(dao.risk_profiles[0].coinsurance_bps, 1_000);
+    }
+
+    // A DAO with a member inactive for 800 days and one active 10 days ago, under a two-year dormancy threshold
+    fn dormancy_dao(now: i64) -> HealthInsuranceDAO {
+        let treasury = Treasury { balance: 5_000_000, reserve_ratio_bps: 2_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(Pubkey::new_unique(), 1_000, treasury).tallied_dao();
+        dao.members = vec![
+            Member { premiums_paid: 2_000_000, last_activity_at: now - 800 * 86_400, ..member_record(Pubkey::new_unique(), MemberTier::Bronze) },
+            Member { premiums_paid: 3_000_000, last_activity_at: now - 10 * 86_400, ..member_record(Pubkey::new_unique(), MemberTier::Silver) },
+        ];
+        dao.dormancy_threshold_secs = 730 * 86_400; // Two years
+        dao
+    }
+
+    #[tokio::test]
+    async fn test_dormancy_sweep() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let now = 1_000 * 86_400;
+
+        let data = finance_data(FinanceInstruction::SweepDormant);
+        let (result, mut context) = execute_through_multisig(&dormancy_dao(now), program_id, dao_key, 2, Vec::new(), data, Some(now)).await;
+        result.unwrap();
+
+        let account = context.banks_client.get_account(dao_key).await.unwrap().unwrap();
+        let dao = HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap();
+        assert!(dao.members[0].dormant);
+        assert_eq!(dao.members[0].premiums_paid, 0);
+        assert!(!dao.members[1].dormant);
+        assert_eq!(dao.members[1].premiums_paid, 3_000_000);
+        assert_eq!(dao.treasury.shared_pool, 2_000_000);
+        assert_eq!(dao.treasury.balance, 5_000_000); // Sweeping re-attributes funds without moving them
+    }
+
+    #[tokio::test]
+    async fn test_dormancy_sweep_needs_multisig_approval() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let now = 1_000 * 86_400;
+
+        // One approval falls short of the threshold
+        let data = finance_data(FinanceInstruction::SweepDormant);
+        let (result, _) = execute_through_multisig(&dormancy_dao(now), program_id, dao_key, 1, Vec::new(), data, Some(now)).await;
+        assert_eq!(result.unwrap_err().unwrap(), TransactionError::InstructionError(0, InstructionError::Custom(DaoError::InsufficientApprovals as u32)));
+
+        // A multi-sig signer can't sweep on their own
+        let signer = Keypair::new();
+        let mut dao = dormancy_dao(now);
+        dao.multi_sig_signers = vec![signer.pubkey()];
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        program_test.add_account(dao_key, dao_account(program_id, &dao));
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                 // DAO account
+                AccountMeta::new_readonly(signer.pubkey(), true), // Multi-sig signer in place of the authority PDA
+            ],
+            data: finance_data(FinanceInstruction::SweepDormant),
+        };
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer, &signer], recent_blockhash);
+        assert_eq!(
+            banks_client.process_transaction(transaction).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::MissingRequiredSignature),
+        );
+    }
+
+    // Update the member's coverage limit as the admin, optionally with the member co-signing
+    async fn update_coverage(new_coverage_limit: u64, member_signs: bool) -> Result<(), BanksClientError> {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let admin = Keypair::new();
+        let member = Keypair::new();
+
+        let mut dao = dao_with_claim(member.pubkey(), 1_000, Treasury { balance: 0, reserve_ratio_bps: 2_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None });
+        dao.admin = admin.pubkey();
+        dao.risk_profiles[0].coverage_limit = 10_000_000;
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, 0);
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let data = finance_data(FinanceInstruction::UpdateRiskProfile {
+            risk_score: calculate_risk_score(&member.pubkey()),
+            coverage_limit: new_coverage_limit,
+        });
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                             // DAO account
+                AccountMeta::new_readonly(member.pubkey(), member_signs),     // Member whose profile changes
+                AccountMeta::new_readonly(admin.pubkey(), true),              // Admin
+            ],
+            data,
+        };
+        let mut signers = vec![&payer, &admin];
+        if member_signs {
+            signers.push(&member);
+        }
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &signers, recent_blockhash);
+        banks_client.process_transaction(transaction).await
+    }
+
+    #[tokio::test]
+    async fn test_admin_raise_without_member_signature() {
+        assert!(update_coverage(20_000_000, false).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_admin_lower_requires_member_signature() {
+        assert!(update_coverage(5_000_000, false).await.is_err());
+        assert!(update_coverage(5_000_000, true).await.is_ok());
+    }
+
+    #[test]
+    fn test_rounding_modes_on_half_lamport() {
+        // 1,001 lamports at 50% coinsurance leaves the pool owing 500.5 lamports
+        assert_eq!(treasury_payout(1_001, 0, 5_000, RoundingMode::FavorMember), 501);
+        assert_eq!(treasury_payout(1_001, 0, 5_000, RoundingMode::FavorPool), 500);
+        assert_eq!(treasury_payout(1_001, 0, 5_000, RoundingMode::Nearest), 501);
+    }
+
+    #[test]
+    fn test_rounding_modes_below_half_lamport() {
+        // 1,101 lamports less a 100 lamport deductible at 90% coinsurance leaves the pool owing 100.1 lamports
+        assert_eq!(treasury_payout(1_101, 100, 9_000, RoundingMode::FavorMember), 101);
+        assert_eq!(treasury_payout(1_101, 100, 9_000, RoundingMode::FavorPool), 100);
+        assert_eq!(treasury_payout(1_101, 100, 9_000, RoundingMode::Nearest), 100);
+    }
+
+    #[test]
+    fn test_rounding_mode_applies_to_single_claim_cap() {
+        // 10% of 1,005 lamports is 100.5 lamports
+        assert_eq!(max_single_payout(1_005, 1_000, RoundingMode::FavorMember), 101);
+        assert_eq!(max_single_payout(1_005, 1_000, RoundingMode::FavorPool), 100);
+        assert_eq!(max_single_payout(1_005, 1_000, RoundingMode::Nearest), 101);
+    }
+
+    #[test]
+    fn test_low_balance_alert_when_crossing_margin() {
+        // Reserve is half the balance, so the alert fires once balance - balance / 2 <= 1,000,000
+        let before = Treasury { balance: 2_400_000, reserve_ratio_bps: 5_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let after = Treasury { balance: 1_800_000, ..before.clone() };
+
+        assert_eq!(low_balance_alert(&before, 1_000_000), None);
+        assert_eq!(low_balance_alert(&after, 1_000_000), Some((1_800_000, 900_000)));
+    }
+
+    #[test]
+    fn test_no_low_balance_alert_above_margin() {
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 2_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        assert_eq!(low_balance_alert(&treasury, 1_000_000), None);
+    }
+
+    // Submit a shared claim from the first participant, with the other participants co-signing when `co_signed`
+    async fn submit_shared_claim(dao: &Ledger, submitter: &Keypair, co_participants: &[&Keypair], co_signed: bool, shares: Vec<ClaimShare>) -> Result<(), BanksClientError> {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, 0);
+        program_test.add_account(
+            submitter.pubkey(),
+            Account { lamports: 1_000_000_000, data: Vec::new(), owner: system_program::id(), executable: false, rent_epoch: 0 },
+        );
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let (claim_key, _) = claim_address(&program_id, &dao_key, dao.tallied_dao().claims_count);
+
+        let total: u64 = shares.iter().map(|share| share.amount).sum();
+        let data = finance_data(FinanceInstruction::SubmitSharedClaim { amount: total, shares, nonce: 0 });
+        let mut accounts = vec![
+            AccountMeta::new(dao_key, false),                       // DAO account
+            AccountMeta::new(submitter.pubkey(), true),             // Submitting participant, funding the claim account
+            AccountMeta::new(claim_key, false),                     // Claim account
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+        ];
+        accounts.extend(co_participants.iter().map(|participant| AccountMeta::new_readonly(participant.pubkey(), co_signed))); // Co-participants
+        let instruction = Instruction { program_id, accounts, data };
+        let mut signers = vec![&payer, submitter];
+        if co_signed {
+            signers.extend(co_participants);
+        }
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &signers, recent_blockhash);
+        banks_client.process_transaction(transaction).await
+    }
+
+    // A parent and child family claim, with the child covered up to `child_coverage`
+    fn family_dao(parent: &Keypair, child: &Keypair, child_coverage: u64) -> Ledger {
+        let mut dao = dao_with_claim(parent.pubkey(), 1_000, Treasury { balance: 0, reserve_ratio_bps: 2_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None });
+        dao.claims.clear();
+        let child = child.pubkey();
+        dao.members.push(member_record(child, MemberTier::Silver));
+        dao.risk_profiles.push(RiskProfile { member: child, risk_score: calculate_risk_score(&child), coverage_limit: child_coverage, deductible: 0, coinsurance_bps: 0, claims_paid: 0, utilization_bps: 0, used_coverage: 0, coverage_period_start: 0 });
+        dao
+    }
+
+    fn family_shares(parent: &Keypair, child: &Keypair) -> Vec<ClaimShare> {
+        vec![
+            ClaimShare { member: parent.pubkey(), amount: 3_000_000 },
+            ClaimShare { member: child.pubkey(), amount: 2_000_000 },
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_valid_shared_claim() {
+        let (parent, child) = (Keypair::new(), Keypair::new());
+        let dao = family_dao(&parent, &child, 5_000_000);
+        assert!(submit_shared_claim(&dao, &parent, &[&child], true, family_shares(&parent, &child)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_shared_claim_with_uncovered_participant() {
+        let (parent, child) = (Keypair::new(), Keypair::new());
+        // The child's coverage is smaller than their share (assumes the two keys compute different risk scores)
+        let dao = family_dao(&parent, &child, 1_000_000);
+        assert!(submit_shared_claim(&dao, &parent, &[&child], true, family_shares(&parent, &child)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_shared_claim_needs_every_participant_signature() {
+        let (parent, child) = (Keypair::new(), Keypair::new());
+        let dao = family_dao(&parent, &child, 5_000_000);
+        let result = submit_shared_claim(&dao, &parent, &[&child], false, family_shares(&parent, &child)).await;
+        assert_eq!(result.unwrap_err().unwrap(), TransactionError::InstructionError(0, InstructionError::MissingRequiredSignature));
+    }
+
+    #[tokio::test]
+    async fn test_shared_claim_rejects_suspended_participant() {
+        let (parent, child) = (Keypair::new(), Keypair::new());
+        let mut dao = family_dao(&parent, &child, 5_000_000);
+        dao.members.iter_mut().find(|m| m.member_address == child.pubkey()).unwrap().suspended = true;
+        let result = submit_shared_claim(&dao, &parent, &[&child], true, family_shares(&parent, &child)).await;
+        assert_eq!(result.unwrap_err().unwrap(), TransactionError::InstructionError(0, InstructionError::Custom(DaoError::MemberSuspended as u32)));
+    }
+
+    #[tokio::test]
+    async fn test_shared_claim_rejects_duplicated_submitter_share() {
+        let (parent, child) = (Keypair::new(), Keypair::new());
+        let mut dao = family_dao(&parent, &child, 5_000_000);
+        dao.risk_profiles[0].coverage_limit = 3_000_000;
+        // Listing the submitter twice would draw twice their coverage with nobody else co-signing
+        let shares = vec![ClaimShare { member: parent.pubkey(), amount: 3_000_000 }, ClaimShare { member: parent.pubkey(), amount: 3_000_000 }];
+        let result = submit_shared_claim(&dao, &parent, &[], true, shares).await;
+        assert_eq!(result.unwrap_err().unwrap(), TransactionError::InstructionError(0, InstructionError::InvalidArgument));
+    }
+
+    #[tokio::test]
+    async fn test_shared_claim_held_to_claim_caps() {
+        let (parent, child) = (Keypair::new(), Keypair::new());
+        let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() as i64;
+        let mut dao = family_dao(&parent, &child, 5_000_000);
+        dao.max_claims_per_member_per_year = 1;
+
+        // The child used up their annual claim with one of their own, so they can't draw on another through a family claim
+        let mut capped = dao.clone();
+        capped.claims.push(Claim { claim_id: 0, member: child.pubkey(), amount: 1_000, status: ClaimStatus::Paid, submitted_at: now, ..Default::default() });
+        let result = submit_shared_claim(&capped, &parent, &[&child], true, family_shares(&parent, &child)).await;
+        assert_eq!(result.unwrap_err().unwrap(), TransactionError::InstructionError(0, InstructionError::Custom(DaoError::AnnualClaimCapReached as u32)));
+
+        // A shared claim in turn counts toward every participant's annual cap
+        let mut shared = dao.clone();
+        shared.claims.push(Claim { claim_id: 0, member: parent.pubkey(), amount: 5_000_000, submitted_at: now, shares: family_shares(&parent, &child), ..Default::default() });
+        let tallied = shared.tallied_dao();
+        assert!(tallied.members.iter().all(|m| m.claim_history.recent_submissions == vec![now]));
+        let result = submit_shared_claim(&shared, &parent, &[&child], true, family_shares(&parent, &child)).await;
+        assert_eq!(result.unwrap_err().unwrap(), TransactionError::InstructionError(0, InstructionError::Custom(DaoError::AnnualClaimCapReached as u32)));
+
+        // Shared claims take up the period's claim cap like any other
+        dao.max_claims_per_member_per_year = 0;
+        dao.period_claim_cap = 1;
+        dao.period_claims_used = 1;
+        let result = submit_shared_claim(&dao, &parent, &[&child], true, family_shares(&parent, &child)).await;
+        assert_eq!(result.unwrap_err().unwrap(), TransactionError::InstructionError(0, InstructionError::Custom(DaoError::PeriodCapReached as u32)));
+    }
+
+    #[tokio::test]
+    async fn test_shared_claim_payout_counts_toward_each_coverage_year() {
+        let (parent, child) = (Keypair::new(), Keypair::new());
+        let mut dao = family_dao(&parent, &child, 3_000_000);
+        dao.treasury.balance = 20_000_000;
+        dao.claims = vec![Claim { claim_id: 0, member: parent.pubkey(), amount: 5_000_000, status: ClaimStatus::Verified, shares: family_shares(&parent, &child), ..Default::default() }];
+
+        let (result, mut after) = pay_claim(&dao, &parent, 5_000_000).await;
+        result.unwrap();
+        let used = |dao: &Ledger, member: &Keypair| dao.risk_profiles.iter().find(|rp| rp.member == member.pubkey()).unwrap().used_coverage;
+        assert_eq!(used(&after, &parent), 3_000_000);
+        assert_eq!(used(&after, &child), 2_000_000);
+
+        // Another 2M share would take the child past their 3M of coverage for the year
+        let shares = vec![ClaimShare { member: parent.pubkey(), amount: 2_000_000 }, ClaimShare { member: child.pubkey(), amount: 2_000_000 }];
+        after.claims = vec![Claim { claim_id: 0, member: parent.pubkey(), amount: 4_000_000, status: ClaimStatus::Verified, shares, ..Default::default() }];
+        let (result, _) = pay_claim(&after, &parent, 4_000_000).await;
+        assert_eq!(result.unwrap_err().unwrap(), TransactionError::InstructionError(0, InstructionError::Custom(DaoError::CoverageExceeded as u32)));
+    }
+
+    // More tests for risk management and treasury operations
+
+    #[test]
+    fn test_payout_volatility() {
+        assert_eq!(payout_volatility_bps(&[1_000_000; 8]), 0);
+        assert_eq!(payout_volatility_bps(&[1_000_000]), 0);
+        // Alternating 0.5M and 1.5M payouts deviate by half the mean
+        assert_eq!(payout_volatility_bps(&[500_000, 1_500_000, 500_000, 1_500_000]), 5_000);
+    }
+
+    #[test]
+    fn test_scaled_reserve_ratio_bounds() {
+        assert_eq!(scaled_reserve_ratio_bps(0, 1_000, 4_000), 1_000);
+        assert_eq!(scaled_reserve_ratio_bps(5_000, 1_000, 4_000), 2_500);
+        assert_eq!(scaled_reserve_ratio_bps(30_000, 1_000, 4_000), 4_000); // Clamped at the maximum
+    }
+
+    #[tokio::test]
+    async fn test_reserve_rises_after_volatile_payouts() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 1_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(Pubkey::new_unique(), 1_000, treasury);
+        dao.reserve_ratio_min_bps = 1_000;
+        dao.reserve_ratio_max_bps = 4_000;
+        dao.recent_payouts[..5].copy_from_slice(&[100_000, 2_000_000, 50_000, 3_000_000, 80_000]);
+        dao.payouts_recorded = 5;
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, 0);
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![AccountMeta::new(dao_key, false)], // DAO account
+            data: finance_data(FinanceInstruction::RescaleReserveRatio),
+        };
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let account = banks_client.get_account(dao_key).await.unwrap().unwrap();
+        let dao = HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap();
+        assert!(dao.treasury.reserve_ratio_bps > 1_000);
+        assert!(dao.treasury.reserve_ratio_bps <= 4_000);
+    }
+
+    #[tokio::test]
+    async fn test_donation_funds_treasury_without_benefits() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let donor = Keypair::new();
+
+        let treasury = Treasury { balance: 1_000_000, reserve_ratio_bps: 2_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(Pubkey::new_unique(), 1_000, treasury);
+        dao.treasury_account = dao_key;
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, 0);
+        program_test.add_account(
+            donor.pubkey(),
+            Account {
+                lamports: 10_000_000,
+                data: Vec::new(),
+                owner: system_program::id(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let lamports_before = banks_client.get_balance(dao_key).await.unwrap();
+
+        let data = finance_data(FinanceInstruction::Donate { amount: 5_000_000 });
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                       // DAO account
+                AccountMeta::new(donor.pubkey(), true),                 // Donor
+                AccountMeta::new(dao_key, false),                       // Treasury, held in the DAO account
+                AccountMeta::new_readonly(system_program::id(), false), // System program
+            ],
+            data,
+        };
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer, &donor], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let account = banks_client.get_account(dao_key).await.unwrap().unwrap();
+        assert_eq!(account.lamports, lamports_before + 5_000_000);
+        let after = HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap();
+        assert_eq!(after.treasury.balance, 6_000_000);
+        assert_eq!(after.donations_received, 5_000_000);
+
+        // The donor gains no membership, premium credit, or coverage
+        assert!(!after.members.iter().any(|m| m.member_address == donor.pubkey()));
+        assert_eq!(after.members[0].premiums_paid, dao.members[0].premiums_paid);
+        assert_eq!(after.risk_profiles.len(), dao.risk_profiles.len());
+    }
+
+    #[tokio::test]
+    async fn test_utilization_after_several_payouts() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let member = Keypair::new();
+
+        let treasury = Treasury { balance: 100_000_000, reserve_ratio_bps: 0, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(member.pubkey(), 1_000_000, treasury);
+        dao.risk_profiles[0].coverage_limit = 10_000_000;
+        dao.claims.push(Claim { claim_id: 1, member: member.pubkey(), amount: 2_000_000, status: ClaimStatus::Verified, ..Default::default() });
+        dao.claims.push(Claim { claim_id: 2, member: member.pubkey(), amount: 3_000_000, status: ClaimStatus::Verified, ..Default::default() });
+
+        let admin = Keypair::new();
+        let (mut banks_client, payer, recent_blockhash) = treasury_test(program_id, dao_key, &dao, admin.pubkey(), member.pubkey()).start().await;
+
+        let payout = |claim_id: u64| payout_instruction(program_id, dao_key, admin.pubkey(), member.pubkey(), claim_id, None);
+        let recompute = Instruction { program_id, accounts: vec![AccountMeta::new(dao_key, false)], data: finance_data(FinanceInstruction::RecomputeUtilization) };
+
+        // Pay claims 0 and 2, leaving claim 1 open
+        let transaction = Transaction::new_signed_with_payer(&[payout(0), payout(2)], Some(&payer.pubkey()), &[&payer, &admin], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+        let incremental = dao.reload(&mut banks_client, program_id, dao_key, &[]).await;
+        assert_eq!(incremental.risk_profiles[0].claims_paid, 4_000_000);
+        assert_eq!(incremental.risk_profiles[0].utilization_bps, 4_000);
+
+        // A full recompute from the paid claim totals agrees with the incremental updates
+        let transaction = Transaction::new_signed_with_payer(&[recompute], Some(&payer.pubkey()), &[&payer], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+        let recomputed = dao.reload(&mut banks_client, program_id, dao_key, &[]).await;
+        assert_eq!(recomputed.risk_profiles[0].claims_paid, 4_000_000);
+        assert_eq!(recomputed.risk_profiles[0].utilization_bps, 4_000);
+    }
+
+    #[test]
+    fn test_utilization_capped_at_full_coverage() {
+        assert_eq!(utilization_bps(0, 10_000_000), 0);
+        assert_eq!(utilization_bps(2_500_000, 10_000_000), 2_500);
+        assert_eq!(utilization_bps(12_000_000, 10_000_000), 10_000);
+        assert_eq!(utilization_bps(1, 0), 10_000);
+    }
+
+    // A four-installment monthly plan for `member` started at t=0
+    fn monthly_plan(member: Pubkey, installments_paid: u8) -> PaymentPlan {
+        PaymentPlan {
+            member,
+            total_owed: 400_000,
+            amount_paid: installments_paid as u64 * 100_000,
+            installment_amount: 100_000,
+            installments_total: 4,
+            installments_paid,
+            start_at: 0,
+            interval_secs: 30 * 86_400,
+        }
+    }
+
+    #[test]
+    fn test_on_schedule_plan_keeps_coverage() {
+        let member = Pubkey::new_unique();
+        assert!(plan_is_current(&monthly_plan(member, 1), 10 * 86_400)); // First installment paid, second not yet due
+        assert!(plan_is_current(&monthly_plan(member, 2), 45 * 86_400));
+        assert!(plan_is_current(&monthly_plan(member, 4), 400 * 86_400)); // Fully paid plans never lapse
+    }
+
+    #[test]
+    fn test_missed_installment_lapses_coverage() {
+        let member = Pubkey::new_unique();
+        assert!(!plan_is_current(&monthly_plan(member, 0), 0)); // First installment due immediately
+        assert!(!plan_is_current(&monthly_plan(member, 1), 30 * 86_400)); // Second installment due and unpaid
+    }
+
+    #[tokio::test]
+    async fn test_installment_transferred_into_treasury() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let member = Keypair::new();
+        let treasury = Treasury { balance: 0, reserve_ratio_bps: 0, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(member.pubkey(), 1_000, treasury);
+        dao.payment_plans.push(monthly_plan(member.pubkey(), 1));
+
+        let (mut banks_client, payer, recent_blockhash) = treasury_test(program_id, dao_key, &dao, dao.admin, member.pubkey()).start().await;
+        let lamports_before = banks_client.get_account(dao_key).await.unwrap().unwrap().lamports;
+
+        let pay = |amount, nonce, recent_blockhash| {
+            let instruction = Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(dao_key, false),                       // DAO account
+                    AccountMeta::new(member.pubkey(), true),                // Member paying the installment
+                    AccountMeta::new(dao_key, false),                       // Treasury
+                    AccountMeta::new_readonly(system_program::id(), false), // System program
+                ],
+                data: finance_data(FinanceInstruction::PayInstallment { amount, nonce }),
+            };
+            Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer, &member], recent_blockhash)
+        };
+        // Less than the scheduled installment is refused
+        assert_eq!(
+            banks_client.process_transaction(pay(99_999, 0, recent_blockhash)).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::InsufficientFunds),
+        );
+        banks_client.process_transaction(pay(100_000, 0, recent_blockhash)).await.unwrap();
+
+        // Replaying the same installment carries a stale nonce and is refused without charging the member again
+        let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+        assert_eq!(
+            banks_client.process_transaction(pay(100_000, 0, recent_blockhash)).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::BadNonce as u32)),
+        );
+
+        let account = banks_client.get_account(dao_key).await.unwrap().unwrap();
+        assert_eq!(account.lamports, lamports_before + 100_000);
+        let after = HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap();
+        assert_eq!(after.treasury.balance, 100_000);
+        assert_eq!(after.payment_plans[0].installments_paid, 2);
+        assert_eq!(after.members[0].nonce, 1);
+    }
+
+    #[tokio::test]
+    async fn test_payout_blocked_for_lapsed_plan() {
+        let member = Keypair::new();
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 0, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+
+        // A long-running plan with a single installment paid has lapsed by now
+        let mut dao = dao_with_claim(member.pubkey(), 1_000, treasury);
+        dao.payment_plans.push(monthly_plan(member.pubkey(), 1));
+        assert_eq!(
+            process_payout(&dao, &member).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::CoverageLapsed as u32)),
+        );
+
+        // The same claim pays out once the plan is settled
+        dao.payment_plans[0] = monthly_plan(member.pubkey(), 4);
+        assert!(process_payout(&dao, &member).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_current_plan_keeps_coverage_between_premiums() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let member = Keypair::new();
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 0, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+
+        // Premiums fall due monthly, and the member has only ever paid through a plan opened at the test clock's start
+        let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() as i64;
+        let mut dao = dao_with_claim(member.pubkey(), 1_000, treasury);
+        dao.premium_period_secs = 30 * 86_400;
+        dao.payment_plans.push(PaymentPlan { start_at: now, ..monthly_plan(member.pubkey(), 3) });
+        assert!(process_payout(&dao, &member).await.is_ok());
+
+        // Without the plan there's no premium on record, so coverage has lapsed
+        let mut lapsed = dao.clone();
+        lapsed.payment_plans.clear();
+        assert_eq!(
+            process_payout(&lapsed, &member).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::CoverageLapsed as u32)),
+        );
+
+        // Paying the last installment completes the premium, which then counts as paid for the period
+        let (mut banks_client, payer, recent_blockhash) = treasury_test(program_id, dao_key, &dao, dao.admin, member.pubkey()).start().await;
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                       // DAO account
+                AccountMeta::new(member.pubkey(), true),                // Member paying the installment
+                AccountMeta::new(dao_key, false),                       // Treasury
+                AccountMeta::new_readonly(system_program::id(), false), // System program
+            ],
+            data: finance_data(FinanceInstruction::PayInstallment { amount: 100_000, nonce: 0 }),
+        };
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer, &member], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+        let after = dao.reload(&mut banks_client, program_id, dao_key, &[]).await;
+        assert!(after.members[0].last_premium_ts > 0);
+        assert_eq!(after.members[0].last_premium_amount, 400_000); // The whole plan paid for the period
+        assert!(after.coverage_active(&after.members[0], after.members[0].last_premium_ts));
+    }
+
+    #[tokio::test]
+    async fn test_estimate_matches_actual_payout() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let member = Keypair::new();
+
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 0, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(member.pubkey(), 1_000_001, treasury);
+        dao.risk_profiles[0].deductible = 100_000;
+        dao.risk_profiles[0].coinsurance_bps = 2_000;
+        dao.rounding_mode = RoundingMode::Nearest;
+
+        let admin = Keypair::new();
+        let (mut banks_client, payer, recent_blockhash) = treasury_test(program_id, dao_key, &dao, admin.pubkey(), member.pubkey()).start().await;
+
+        // Ask for an estimate of the same amount as the open claim
+        let data = finance_data(FinanceInstruction::EstimatePayout { amount: 1_000_001 });
+        let estimate = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                  // DAO account
+                AccountMeta::new_readonly(member.pubkey(), false), // Member the estimate is for
+            ],
+            data,
+        };
+        let transaction = Transaction::new_signed_with_payer(&[estimate], Some(&payer.pubkey()), &[&payer], recent_blockhash);
+        let simulation = banks_client.simulate_transaction(transaction).await.unwrap();
+        let return_data = simulation.simulation_details.unwrap().return_data.unwrap();
+        let estimate = PayoutEstimate::try_from_slice(&return_data.data).unwrap();
+        assert!(estimate.within_coverage);
+
+        // Pay the claim and compare against the treasury outflow
+        let payout = payout_instruction(program_id, dao_key, admin.pubkey(), member.pubkey(), 0, None);
+        let transaction = Transaction::new_signed_with_payer(&[payout], Some(&payer.pubkey()), &[&payer, &admin], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let account = banks_client.get_account(dao_key).await.unwrap().unwrap();
+        let after = HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap();
+        assert_eq!(10_000_000 - after.treasury.balance, estimate.payable);
+        assert_eq!(estimate.payable + estimate.member_share, 1_000_001);
+    }
+
+    #[test]
+    fn test_estimate_over_coverage_pays_nothing() {
+        let risk_profile = RiskProfile { member: Pubkey::new_unique(), risk_score: 0, coverage_limit: 1_000_000, deductible: 0, coinsurance_bps: 0, claims_paid: 0, utilization_bps: 0, used_coverage: 0, coverage_period_start: 0 };
+        let estimate = estimate_payout(2_000_000, &risk_profile, RoundingMode::FavorPool);
+        assert_eq!(estimate, PayoutEstimate { payable: 0, member_share: 2_000_000, within_coverage: false });
+    }
+
+    // Run a rebalance against a DAO account holding its own treasury and a yield vault, both funded with the
+    // DAO's recorded balances, returning the DAO state and the lamports above rent left in each
+    async fn rebalance(dao: &HealthInsuranceDAO) -> (HealthInsuranceDAO, u64, u64) {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let (yield_vault, _) = Pubkey::find_program_address(&[YIELD_VAULT_SEED, dao_key.as_ref()], &program_id);
+        let rent = Rent::default().minimum_balance(0);
+
+        let dao = HealthInsuranceDAO { treasury_account: dao_key, ..dao.clone() };
+        let mut account = dao_account(program_id, &dao);
+        let dao_rent = Rent::default().minimum_balance(account.data.len());
+        account.lamports = dao_rent + dao.treasury.balance;
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        program_test.add_account(dao_key, account);
+        program_test.add_account(
+            yield_vault,
+            Account { lamports: rent + dao.yield_balance, data: Vec::new(), owner: system_program::id(), executable: false, rent_epoch: 0 },
+        );
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                       // DAO account
+                AccountMeta::new(dao_key, false),                       // Treasury, held in the DAO account
+                AccountMeta::new(yield_vault, false),                   // Yield vault PDA
+                AccountMeta::new_readonly(system_program::id(), false), // System program
+            ],
+            data: finance_data(FinanceInstruction::RebalanceVaults),
+        };
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let account = banks_client.get_account(dao_key).await.unwrap().unwrap();
+        let reserve = account.lamports - dao_rent;
+        let yielded = banks_client.get_account(yield_vault).await.unwrap().unwrap().lamports - rent;
+        (HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap(), reserve, yielded)
+    }
+
+    #[tokio::test]
+    async fn test_rebalance_moves_excess_to_yield() {
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 2_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(Pubkey::new_unique(), 1_000, treasury);
+        dao.min_liquidity = 1_000_000;
+
+        // 20% of 10M is above the liquidity floor, so the reserve keeps 2M and the rest moves to yield
+        let (after, reserve, yielded) = rebalance(&dao).await;
+        assert_eq!(after.treasury.balance, 2_000_000);
+        assert_eq!(after.yield_balance, 8_000_000);
+        assert_eq!((reserve, yielded), (2_000_000, 8_000_000));
+    }
+
+    #[tokio::test]
+    async fn test_rebalance_pulls_back_on_shortfall() {
+        let treasury = Treasury { balance: 500_000, reserve_ratio_bps: 1_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(Pubkey::new_unique(), 1_000, treasury);
+        dao.yield_balance = 9_500_000;
+        dao.min_liquidity = 3_000_000;
+
+        // The liquidity floor outweighs the 10% ratio, so the reserve is topped back up to 3M
+        let (after, reserve, yielded) = rebalance(&dao).await;
+        assert_eq!(after.treasury.balance, 3_000_000);
+        assert_eq!(after.yield_balance, 7_000_000);
+        assert_eq!((reserve, yielded), (3_000_000, 7_000_000));
+    }
+
+    // Pay the member's premium from two funded source wallets carrying `nonce`, returning the result and the final DAO state
+    async fn pay_split_premium(dao: &HealthInsuranceDAO, member: Pubkey, amounts: [u64; 2], nonce: u64) -> (Result<(), BanksClientError>, HealthInsuranceDAO) {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let sources = [Keypair::new(), Keypair::new()];
+        let dao = HealthInsuranceDAO { treasury_account: dao_key, ..dao.clone() };
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        program_test.add_account(dao_key, dao_account(program_id, &dao));
+        for source in &sources {
+            program_test.add_account(
+                source.pubkey(),
+                Account { lamports: 10_000_000, data: Vec::new(), owner: system_program::id(), executable: false, rent_epoch: 0 },
+            );
+        }
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let data = finance_data(FinanceInstruction::PaySplitPremium { amounts: amounts.to_vec(), nonce });
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                       // DAO account
+                AccountMeta::new_readonly(member, false),               // Member the premium is for
+                AccountMeta::new(dao_key, false),                       // Treasury, held in the DAO account
+                AccountMeta::new_readonly(system_program::id(), false), // System program
+                AccountMeta::new(sources[0].pubkey(), true),            // Personal wallet
+                AccountMeta::new(sources[1].pubkey(), true),            // Employer or HSA account
+            ],
+            data,
+        };
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[&payer, &sources[0], &sources[1]],
+            recent_blockhash,
+        );
+        let result = banks_client.process_transaction(transaction).await;
+
+        let account = banks_client.get_account(dao_key).await.unwrap().unwrap();
+        (result, HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_two_source_premium_meets_requirement() {
+        let member = Pubkey::new_unique();
+        let treasury = Treasury { balance: 0, reserve_ratio_bps: 2_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let dao = dao_with_claim(member, 1_000, treasury);
+
+        // The Gold premium of 400_000 lamports is split between the member and their HSA
+        let (result, dao) = pay_split_premium(&dao, member, [250_000, 150_000], 0).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.treasury.balance, 400_000);
+        assert_eq!(dao.members[0].premiums_paid, 400_000);
+    }
+
+    #[tokio::test]
+    async fn test_two_source_premium_short_rejected() {
+        let member = Pubkey::new_unique();
+        let treasury = Treasury { balance: 0, reserve_ratio_bps: 2_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let dao = dao_with_claim(member, 1_000, treasury);
+
+        let (result, dao) = pay_split_premium(&dao, member, [250_000, 100_000], 0).await;
+        assert!(result.is_err());
+        assert_eq!(dao.treasury.balance, 0);
+        assert_eq!(dao.members[0].premiums_paid, 0);
+    }
+
+    #[tokio::test]
+    async fn test_replayed_split_premium_rejected() {
+        let member = Pubkey::new_unique();
+        let treasury = Treasury { balance: 0, reserve_ratio_bps: 2_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(member, 1_000, treasury);
+        dao.members[0].nonce = 1; // A split payment carrying nonce 0 has already gone through
+
+        let (result, dao) = pay_split_premium(&dao, member, [250_000, 150_000], 0).await;
+        assert_eq!(result.unwrap_err().unwrap(), TransactionError::InstructionError(0, InstructionError::Custom(DaoError::BadNonce as u32)));
+        assert_eq!(dao.treasury.balance, 0);
+        assert_eq!(dao.members[0].nonce, 1);
+    }
+
+    #[tokio::test]
+    async fn test_weekly_heartbeats_lower_risk_score() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        // Pick a member whose base score leaves room for the full reduction
+        let member = std::iter::repeat_with(Keypair::new).find(|k| calculate_risk_score(&k.pubkey()) >= MAX_HEARTBEAT_RISK_REDUCTION).unwrap();
+        let treasury = Treasury { balance: 0, reserve_ratio_bps: 2_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let dao = dao_with_claim(member.pubkey(), 1_000, treasury);
+        let base_score = compute_risk_score(&dao.members[0]);
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, 0);
+        let mut context = program_test.start_with_context().await;
+
+        // Eight weekly check-ins, plus one mid-week that doesn't count twice
+        let mut timestamps: Vec<i64> = (1..=8).map(|week| week * HEARTBEAT_INTERVAL_SECS).collect();
+        timestamps.insert(1, HEARTBEAT_INTERVAL_SECS + 60);
+        for unix_timestamp in timestamps {
+            context.set_sysvar(&Clock { unix_timestamp, ..Clock::default() });
+            let blockhash = context.get_new_latest_blockhash().await.unwrap();
+            let instruction = Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(dao_key, false),                 // DAO account
+                    AccountMeta::new_readonly(member.pubkey(), true), // Member checking in
+                ],
+                data: finance_data(FinanceInstruction::Heartbeat),
+            };
+            let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&context.payer.pubkey()), &[&context.payer, &member], blockhash);
+            context.banks_client.process_transaction(transaction).await.unwrap();
+        }
+
+        let account = context.banks_client.get_account(dao_key).await.unwrap().unwrap();
+        let after = HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap();
+        assert_eq!(after.members[0].heartbeat_streak, 8);
+        assert_eq!(compute_risk_score(&after.members[0]), base_score - 2);
+    }
+
+    #[test]
+    fn test_heartbeat_gap_resets_streak() {
+        let mut member = member_record(Pubkey::new_unique(), MemberTier::Gold);
+        record_heartbeat(&mut member, HEARTBEAT_INTERVAL_SECS);
+        record_heartbeat(&mut member, 2 * HEARTBEAT_INTERVAL_SECS);
+        assert_eq!(member.heartbeat_streak, 2);
+
+        // Missing more than two intervals starts a new streak
+        record_heartbeat(&mut member, 5 * HEARTBEAT_INTERVAL_SECS);
+        assert_eq!(member.heartbeat_streak, 1);
+        assert_eq!(member.last_heartbeat_at, 5 * HEARTBEAT_INTERVAL_SECS);
+    }
+
+    // SPL token account holding `amount` of `mint`
+    fn token_vault_account(mint: Pubkey, amount: u64) -> Account {
+        token_account(mint, Pubkey::new_unique(), amount)
+    }
+
+    // SPL token account held by `owner` with `amount` of `mint`
+    fn token_account(mint: Pubkey, owner: Pubkey, amount: u64) -> Account {
+        let mut data = vec![0u8; spl_token::state::Account::LEN];
+        let token_account = spl_token::state::Account {
+            mint,
+            owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        };
+        spl_token::state::Account::pack(token_account, &mut data).unwrap();
+        Account { lamports: Rent::default().minimum_balance(data.len()), data, owner: spl_token::id(), executable: false, rent_epoch: 0 }
+    }
+
+    // Mock oracle account quoting one whole token at `price`, published at `published_at`
+    fn oracle_account(price: u64, published_at: i64) -> Account {
+        let mut data = price.to_le_bytes().to_vec();
+        data.extend_from_slice(&published_at.to_le_bytes());
+        Account { lamports: Rent::default().minimum_balance(data.len()), data, owner: Pubkey::new_unique(), executable: false, rent_epoch: 0 }
+    }
+
+    // Value two vaults at the given clock time: 1.5 tokens of a 6-decimal mint priced at 2_000, and
+    // 20 tokens of a 9-decimal mint priced at 50, with the second price published at `second_published_at`
+    async fn value_two_vaults(now: i64, second_published_at: i64) -> Result<TreasuryValuation, ()> {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let vaults = [
+            TokenVault { mint: Pubkey::new_unique(), vault: Pubkey::new_unique(), oracle: Pubkey::new_unique(), decimals: 6 },
+            TokenVault { mint: Pubkey::new_unique(), vault: Pubkey::new_unique(), oracle: Pubkey::new_unique(), decimals: 9 },
+        ];
+        let treasury = Treasury { balance: 0, reserve_ratio_bps: 2_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(Pubkey::new_unique(), 1_000, treasury);
+        dao.token_vaults = vaults.to_vec();
+        dao.max_price_age_secs = 60;
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, 0);
+        program_test.add_account(vaults[0].vault, token_vault_account(vaults[0].mint, 1_500_000));
+        program_test.add_account(vaults[0].oracle, oracle_account(2_000, now));
+        program_test.add_account(vaults[1].vault, token_vault_account(vaults[1].mint, 20_000_000_000));
+        program_test.add_account(vaults[1].oracle, oracle_account(50, second_published_at));
+        let mut context = program_test.start_with_context().await;
+        context.set_sysvar(&Clock { unix_timestamp: now, ..Clock::default() });
+
+        let mut accounts = vec![AccountMeta::new_readonly(dao_key, false)]; // DAO account
+        for vault in &vaults {
+            accounts.push(AccountMeta::new_readonly(vault.vault, false)); // Token vault
+            accounts.push(AccountMeta::new_readonly(vault.oracle, false)); // Its price oracle
+        }
+        let instruction = Instruction { program_id, accounts, data: finance_data(FinanceInstruction::ValueTreasury) };
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&context.payer.pubkey()), &[&context.payer], context.last_blockhash);
+        let simulation = context.banks_client.simulate_transaction(transaction).await.unwrap();
+        match simulation.result {
+            Some(Ok(())) => {
+                let return_data = simulation.simulation_details.unwrap().return_data.unwrap();
+                Ok(TreasuryValuation::try_from_slice(&return_data.data).unwrap())
+            }
+            _ => Err(()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_valuation_aggregates_token_vaults() {
+        let valuation = value_two_vaults(1_000, 990).await.unwrap();
+        assert_eq!(valuation.vault_values, vec![3_000, 1_000]);
+        assert_eq!(valuation.total, 4_000);
+    }
+
+    #[tokio::test]
+    async fn test_valuation_rejects_stale_price() {
+        // The second oracle last published 61 seconds ago, past the 60 second limit
+        assert!(value_two_vaults(1_000, 939).await.is_err());
+    }
+
+    // DAO split into a low-risk band holding `own_balance` for the member's score and a well-funded band for everyone else
+    fn dao_with_risk_bands(member: Pubkey, amount: u64, own_balance: u64) -> Ledger {
+        let treasury = Treasury { balance: own_balance + 50_000_000, reserve_ratio_bps: 0, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(member, amount, treasury);
+        let score = calculate_risk_score(&member);
+        dao.risk_bands = vec![
+            RiskBand { min_risk_score: score, max_risk_score: score, reserve_ratio_bps: 1_000, balance: own_balance },
+            RiskBand {
+                min_risk_score: if score == 0 { 1 } else { 0 },
+                max_risk_score: if score == 0 { 99 } else { score - 1 },
+                reserve_ratio_bps: 1_000,
+                balance: 50_000_000,
+            },
+        ];
+        dao
+    }
+
+    #[tokio::test]
+    async fn test_claim_draws_from_member_band() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let member = Keypair::new();
+        let dao = dao_with_risk_bands(member.pubkey(), 1_000_000, 2_000_000);
+
+        let admin = Keypair::new();
+        let (mut banks_client, payer, recent_blockhash) = treasury_test(program_id, dao_key, &dao, admin.pubkey(), member.pubkey()).start().await;
+
+        let instruction = payout_instruction(program_id, dao_key, admin.pubkey(), member.pubkey(), 0, None);
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer, &admin], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let account = banks_client.get_account(dao_key).await.unwrap().unwrap();
+        let after = HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap();
+        assert_eq!(after.risk_bands[0].balance, 1_000_000);
+        assert_eq!(after.risk_bands[1].balance, 50_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_claim_band_follows_stored_risk_score() {
+        let member = Keypair::new();
+        // Rescoring the member's profile moves them out of the band their derived score falls in
+        let mut dao = dao_with_risk_bands(member.pubkey(), 1_000_000, 1_000_000);
+        dao.risk_profiles[0].risk_score = dao.risk_bands[1].min_risk_score;
+
+        let (result, after) = pay_claim(&dao, &member, 1_000_000).await;
+        assert!(result.is_ok());
+        assert_eq!(after.risk_bands[0].balance, 1_000_000);
+        assert_eq!(after.risk_bands[1].balance, 49_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_claim_cannot_drain_another_band() {
+        let member = Keypair::new();
+        // The treasury as a whole could pay, but the member's band only has 900_000 available after its reserve
+        let dao = dao_with_risk_bands(member.pubkey(), 1_000_000, 1_000_000);
+        assert_eq!(
+            process_payout(&dao, &member).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::RiskBandUnderfunded as u32)),
+        );
+    }
+
+    #[test]
+    fn test_overlapping_risk_bands_rejected() {
+        let band = |min_risk_score, max_risk_score| RiskBand { min_risk_score, max_risk_score, reserve_ratio_bps: 0, balance: 0 };
+        assert!(valid_risk_bands(&[band(0, 49), band(50, 99)]));
+        assert!(!valid_risk_bands(&[band(0, 50), band(50, 99)]));
+        assert!(!valid_risk_bands(&[band(60, 40)]));
+    }
+
+    #[tokio::test]
+    async fn test_premium_rejected_while_paused() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let admin = Keypair::new();
+        let member = Keypair::new();
+
+        let treasury = Treasury { balance: 0, reserve_ratio_bps: 1_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(member.pubkey(), 1_000, treasury);
+        dao.admin = admin.pubkey();
+        dao.paused = true;
+
+        let (mut banks_client, payer, recent_blockhash) = treasury_test(program_id, dao_key, &dao, admin.pubkey(), member.pubkey()).start().await;
+
+        let premium = tier_benefits(MemberTier::Gold).premium;
+        let pay = |recent_blockhash| {
+            let instruction = premium_instruction(program_id, dao_key, member.pubkey(), premium);
+            Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer, &member], recent_blockhash)
+        };
+        assert_eq!(
+            banks_client.process_transaction(pay(recent_blockhash)).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::ProgramPaused as u32)),
+        );
+
+        let unpause = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                // DAO account
+                AccountMeta::new_readonly(admin.pubkey(), true), // Admin
+            ],
+            data: DaoInstruction::Security(SecurityInstruction::Unpause).try_to_vec().unwrap(),
+        };
+        let transaction = Transaction::new_signed_with_payer(&[unpause], Some(&payer.pubkey()), &[&payer, &admin], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        // A fresh blockhash keeps the retried payment from being deduplicated as the failed one
+        let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+        banks_client.process_transaction(pay(recent_blockhash)).await.unwrap();
+        let account = banks_client.get_account(dao_key).await.unwrap().unwrap();
+        let after = HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap();
+        assert!(!after.paused);
+        assert_eq!(after.treasury.balance, premium);
+    }
+
+    #[tokio::test]
+    async fn test_replayed_premium_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let member = Keypair::new();
+
+        let treasury = Treasury { balance: 0, reserve_ratio_bps: 1_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let dao = dao_with_claim(member.pubkey(), 1_000, treasury);
+
+        let (mut banks_client, payer, recent_blockhash) = treasury_test(program_id, dao_key, &dao, dao.admin, member.pubkey()).start().await;
+
+        let premium = tier_benefits(MemberTier::Gold).premium;
+        let instruction = premium_instruction(program_id, dao_key, member.pubkey(), premium);
+        let transaction = Transaction::new_signed_with_payer(std::slice::from_ref(&instruction), Some(&payer.pubkey()), &[&payer, &member], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        // The same instruction in a new transaction carries a stale nonce
+        let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer, &member], recent_blockhash);
+        assert_eq!(
+            banks_client.process_transaction(transaction).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::BadNonce as u32)),
+        );
+
+        let account = banks_client.get_account(dao_key).await.unwrap().unwrap();
+        let after = HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap();
+        assert_eq!(after.members[0].nonce, 1);
+        assert_eq!(after.treasury.balance, premium);
+    }
+
+    #[tokio::test]
+    async fn test_reserve_ratio_requires_admin() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let admin = Keypair::new();
+        let member = Keypair::new();
+
+        let treasury = Treasury { balance: 0, reserve_ratio_bps: 1_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(member.pubkey(), 1_000, treasury);
+        dao.admin = admin.pubkey();
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        program_test.add_account(dao_key, dao_account(program_id, &dao));
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let mut results = Vec::new();
+        for signer in [&member, &admin] {
+            let instruction = Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(dao_key, false),                 // DAO account
+                    AccountMeta::new_readonly(signer.pubkey(), true), // Member, then admin
+                ],
+                data: finance_data(FinanceInstruction::SetReserveRatio { reserve_ratio_bps: 3_000 }),
+            };
+            let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer, signer], recent_blockhash);
+            results.push(banks_client.process_transaction(transaction).await);
+        }
+        let admin_result = results.pop().unwrap();
+        let member_result = results.pop().unwrap();
+        assert_eq!(
+            member_result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::Unauthorized as u32)),
+        );
+        assert!(admin_result.is_ok());
+
+        let account = banks_client.get_account(dao_key).await.unwrap().unwrap();
+        assert_eq!(HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap().treasury.reserve_ratio_bps, 3_000);
+    }
+
+    // Premium period the refund tests run on
+    const REFUND_PERIOD: i64 = 30 * 86_400;
+
+    // Have `signer` remove `member` at `now` from a DAO account that holds its own treasury of `funds` above rent,
+    // returning the result, the final DAO state, and the member's lamports
+    async fn remove_member(dao: &Ledger, signer: &Keypair, member: Pubkey, now: i64, funds: u64) -> (Result<(), BanksClientError>, HealthInsuranceDAO, u64) {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let mut dao = dao.clone();
+        dao.treasury_account = dao_key;
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, funds);
+        program_test.add_account(member, Account { lamports: 1_000_000, data: Vec::new(), owner: system_program::id(), executable: false, rent_epoch: 0 });
+        let mut context = program_test.start_with_context().await;
+        context.set_sysvar(&Clock { unix_timestamp: now, ..Clock::default() });
+
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                 // DAO account
+                AccountMeta::new_readonly(signer.pubkey(), true), // Admin or the member
+                AccountMeta::new(member, signer.pubkey() == member), // Member being removed
+                AccountMeta::new(dao_key, false),                 // Treasury, held in the DAO account
+                AccountMeta::new(member, signer.pubkey() == member), // Member receiving the refund
+                AccountMeta::new_readonly(system_program::id(), false), // System program
+            ],
+            data: finance_data(FinanceInstruction::RemoveMember),
+        };
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&context.payer.pubkey()), &[&context.payer, signer], context.last_blockhash);
+        let result = context.banks_client.process_transaction(transaction).await;
+
+        let account = context.banks_client.get_account(dao_key).await.unwrap().unwrap();
+        let member_lamports = context.banks_client.get_balance(member).await.unwrap();
+        (result, HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap(), member_lamports)
+    }
+
+    #[tokio::test]
+    async fn test_member_removed_with_premium_refund() {
+        let admin = Keypair::new();
+        let member = Pubkey::new_unique();
+        let premium = tier_benefits(MemberTier::Gold).premium;
+
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 1_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(member, 1_000, treasury);
+        dao.admin = admin.pubkey();
+        dao.claims[0].status = ClaimStatus::Paid;
+        dao.premium_period_secs = REFUND_PERIOD;
+        dao.members[0].joined_timestamp = 1_000;
+        dao.members[0].premiums_paid = 5 * premium; // Premiums from earlier periods are earned and never come back
+        dao.members[0].last_premium_ts = 1_000;
+        dao.members[0].last_premium_amount = premium;
+
+        // A quarter of the way through the period the last premium paid for, three quarters of it is unearned
+        let now = 1_000 + REFUND_PERIOD / 4;
+        let (result, after, member_lamports) = remove_member(&dao, &admin, member, now, 10_000_000).await;
+        assert!(result.is_ok());
+        let refund = premium * 3 / 4;
+        assert_eq!(member_lamports, 1_000_000 + refund);
+        assert_eq!(after.treasury.balance, 10_000_000 - refund);
+        assert!(after.members.is_empty());
+        assert!(after.risk_profiles.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_lapsed_member_removed_without_refund() {
+        let member = Keypair::new();
+        let premium = tier_benefits(MemberTier::Gold).premium;
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 1_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(member.pubkey(), 1_000, treasury);
+        dao.claims[0].status = ClaimStatus::Paid;
+        dao.premium_period_secs = REFUND_PERIOD;
+        dao.members[0].premiums_paid = premium;
+        dao.members[0].last_premium_ts = 1_000;
+        dao.members[0].last_premium_amount = premium;
+
+        // Coverage ran out a period after the last premium, so nothing paid for it is unearned
+        let (result, after, member_lamports) = remove_member(&dao, &member, member.pubkey(), 1_000 + 2 * REFUND_PERIOD, 10_000_000).await;
+        assert!(result.is_ok());
+        assert_eq!(member_lamports, 1_000_000);
+        assert_eq!(after.treasury.balance, 10_000_000);
+        assert!(after.members.is_empty());
+
+        // A member who never paid a premium has nothing to refund either
+        dao.members[0].last_premium_ts = 0;
+        let (result, _, member_lamports) = remove_member(&dao, &member, member.pubkey(), 1_000 + REFUND_PERIOD / 2, 10_000_000).await;
+        assert!(result.is_ok());
+        assert_eq!(member_lamports, 1_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_member_refund_debits_band_and_respects_reserve() {
+        let member = Keypair::new();
+        let premium = tier_benefits(MemberTier::Gold).premium;
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 1_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(member.pubkey(), 1_000, treasury);
+        dao.claims[0].status = ClaimStatus::Paid;
+        dao.premium_period_secs = REFUND_PERIOD;
+        dao.members[0].last_premium_ts = 1_000;
+        dao.members[0].last_premium_amount = premium;
+        dao.risk_bands = vec![RiskBand { min_risk_score: 0, max_risk_score: 99, reserve_ratio_bps: 0, balance: 5_000_000 }];
+        let now = 1_000 + REFUND_PERIOD / 2;
+        let refund = premium / 2;
+
+        // The refund comes out of the member's band along with the treasury
+        let (result, after, member_lamports) = remove_member(&dao, &member, member.pubkey(), now, 10_000_000).await;
+        assert!(result.is_ok());
+        assert_eq!(member_lamports, 1_000_000 + refund);
+        assert_eq!(after.risk_bands[0].balance, 5_000_000 - refund);
+
+        // A band without the funds can't refund the member, who stays on the books
+        dao.risk_bands[0].balance = refund - 1;
+        let (result, after, member_lamports) = remove_member(&dao, &member, member.pubkey(), now, 10_000_000).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::RiskBandUnderfunded as u32)),
+        );
+        assert_eq!(after.members.len(), 1);
+        assert_eq!(member_lamports, 1_000_000);
+
+        // Nor can a treasury whose balance above the reserve is short of the refund
+        dao.risk_bands.clear();
+        dao.treasury.balance = refund;
+        let (result, after, _) = remove_member(&dao, &member, member.pubkey(), now, 10_000_000).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::InsufficientReserve as u32)),
+        );
+        assert_eq!(after.treasury.balance, refund);
+        assert_eq!(after.members.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_member_refund_paid_from_configured_treasury() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let member = Keypair::new();
+        let treasury_wallet = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let member_tokens = Pubkey::new_unique();
+        let treasury_tokens = Pubkey::new_unique();
+        let premium = tier_benefits(MemberTier::Gold).premium;
+        let now = 1_000 + REFUND_PERIOD / 2;
+        let refund = premium / 2;
+
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 1_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(member.pubkey(), 1_000, treasury);
+        dao.claims[0].status = ClaimStatus::Paid;
+        dao.premium_period_secs = REFUND_PERIOD;
+        dao.members[0].joined_timestamp = 1_000;
+        dao.members[0].premiums_paid = premium;
+        dao.members[0].last_premium_ts = 1_000;
+        dao.members[0].last_premium_amount = premium;
+        dao.treasury_account = treasury_wallet.pubkey();
+
+        // The same member leaves a DAO paying from a separate treasury wallet and one paying in tokens
+        let mut token_dao = dao.clone();
+        token_dao.treasury.token_mint = Some(mint);
+        let token_key = Pubkey::new_unique();
+        let (token_authority, _) = Pubkey::find_program_address(&[TREASURY_AUTHORITY_SEED, token_key.as_ref()], &program_id);
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, 0);
+        token_dao.add_accounts(&mut program_test, program_id, token_key, 0);
+        for wallet in [member.pubkey(), treasury_wallet.pubkey()] {
+            program_test.add_account(wallet, Account { lamports: 10_000_000, data: Vec::new(), owner: system_program::id(), executable: false, rent_epoch: 0 });
+        }
+        program_test.add_account(member_tokens, token_account(mint, member.pubkey(), 0));
+        program_test.add_account(treasury_tokens, token_account(mint, token_authority, 10_000_000));
+        let mut context = program_test.start_with_context().await;
+        context.set_sysvar(&Clock { unix_timestamp: now, ..Clock::default() });
+
+        let remove = |dao_key: Pubkey, refund_accounts: Vec<AccountMeta>| {
+            let mut accounts = vec![
+                AccountMeta::new(dao_key, false),        // DAO account
+                AccountMeta::new(member.pubkey(), true), // Departing member
+                AccountMeta::new(member.pubkey(), true), // Member being removed
+            ];
+            accounts.extend(refund_accounts);
+            Instruction { program_id, accounts, data: finance_data(FinanceInstruction::RemoveMember) }
+        };
+        let separate = remove(
+            dao_key,
+            vec![
+                AccountMeta::new(treasury_wallet.pubkey(), true),       // Separate treasury wallet
+                AccountMeta::new(member.pubkey(), true),                // Member receiving the refund
+                AccountMeta::new_readonly(system_program::id(), false), // System program
+            ],
+        );
+        let token = remove(
+            token_key,
+            vec![
+                AccountMeta::new(treasury_tokens, false),          // Treasury token account
+                AccountMeta::new(member_tokens, false),            // Member's token account
+                AccountMeta::new_readonly(token_authority, false), // Treasury authority PDA
+                AccountMeta::new_readonly(spl_token::id(), false), // SPL token program
+            ],
+        );
+        let dao_lamports = context.banks_client.get_balance(dao_key).await.unwrap();
+        let transaction = Transaction::new_signed_with_payer(
+            &[separate, token],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &member, &treasury_wallet],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        // The lamport refund leaves the treasury wallet rather than the DAO account
+        assert_eq!(context.banks_client.get_balance(treasury_wallet.pubkey()).await.unwrap(), 10_000_000 - refund);
+        assert_eq!(context.banks_client.get_balance(dao_key).await.unwrap(), dao_lamports);
+        let after = dao.reload(&mut context.banks_client, program_id, dao_key, &[]).await;
+        assert_eq!(after.treasury.balance, 10_000_000 - refund);
+        assert!(after.members.is_empty());
+
+        // The token refund moves from the treasury token account to the member's
+        let token_balance = |account: Account| spl_token::state::Account::unpack(&account.data).unwrap().amount;
+        assert_eq!(token_balance(context.banks_client.get_account(member_tokens).await.unwrap().unwrap()), refund);
+        assert_eq!(token_balance(context.banks_client.get_account(treasury_tokens).await.unwrap().unwrap()), 10_000_000 - refund);
+        let after = token_dao.reload(&mut context.banks_client, program_id, token_key, &[]).await;
+        assert_eq!(after.treasury.balance, 10_000_000 - refund);
+        assert!(after.members.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_member_with_pending_claim_not_removed() {
+        let member = Keypair::new();
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 1_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(member.pubkey(), 1_000, treasury);
+        dao.members[0].premiums_paid = tier_benefits(MemberTier::Gold).premium;
+
+        for status in [ClaimStatus::Pending, ClaimStatus::Verified, ClaimStatus::Appealed] {
+            dao.claims[0].status = status;
+            let (result, after, member_lamports) = remove_member(&dao, &member, member.pubkey(), 1_000, 10_000_000).await;
+            assert_eq!(
+                result.unwrap_err().unwrap(),
+                TransactionError::InstructionError(0, InstructionError::Custom(DaoError::PendingClaimsExist as u32)),
+            );
+            assert_eq!(after.members.len(), 1);
+            assert_eq!(member_lamports, 1_000_000);
+        }
+    }
+
+    #[test]
+    fn test_required_reserve_in_basis_points() {
+        let treasury = |reserve_ratio_bps| Treasury { balance: 12_345_679, reserve_ratio_bps, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        assert_eq!(required_reserve(&treasury(500)), 617_283);
+        assert_eq!(required_reserve(&treasury(2_500)), 3_086_419);
+        assert_eq!(required_reserve(&treasury(10_000)), 12_345_679);
+
+        // u128 intermediates keep the product exact for the largest balances
+        let full = Treasury { balance: u64::MAX, ..treasury(10_000) };
+        assert_eq!(required_reserve(&full), u64::MAX);
+    }
+
+    #[tokio::test]
+    async fn test_reserve_ratio_above_100_percent_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let admin = Keypair::new();
+
+        let treasury = Treasury { balance: 0, reserve_ratio_bps: 1_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(Pubkey::new_unique(), 1_000, treasury);
+        dao.admin = admin.pubkey();
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        program_test.add_account(dao_key, dao_account(program_id, &dao));
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                // DAO account
+                AccountMeta::new_readonly(admin.pubkey(), true), // Admin
+            ],
+            data: finance_data(FinanceInstruction::SetReserveRatio { reserve_ratio_bps: 10_001 }),
+        };
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer, &admin], recent_blockhash);
+        assert_eq!(
+            banks_client.process_transaction(transaction).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::InvalidArgument),
+        );
+
+        let account = banks_client.get_account(dao_key).await.unwrap().unwrap();
+        assert_eq!(HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap().treasury.reserve_ratio_bps, 1_000);
+    }
+
+    #[tokio::test]
+    async fn test_legacy_reserve_ratio_migrated() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let admin = Keypair::new();
+
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 2_500, max_single_claim_bps: 1_000, initialized: true, shared_pool: 7, token_mint: None };
+        let mut dao = dao_with_claim(Pubkey::new_unique(), 1_000, treasury);
+        dao.admin = admin.pubkey();
+        dao.alert_margin = 42;
+        let dao = dao.tallied_dao();
+
+        // Re-encode the treasury as the legacy program wrote it, with the ratio as an f32
+        let current = dao.try_to_vec().unwrap();
+        let treasury_start = (dao.admin, &dao.members).try_to_vec().unwrap().len();
+        let mut legacy = current[..treasury_start].to_vec();
+        legacy.extend((10_000_000u64, 0.25f32, 1_000u16, true, 7u64).try_to_vec().unwrap());
+        legacy.extend(&current[treasury_start + dao.treasury.try_to_vec().unwrap().len()..]);
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        program_test.add_account(
+            dao_key,
+            Account { lamports: Rent::default().minimum_balance(legacy.len()), data: legacy, owner: program_id, executable: false, rent_epoch: 0 },
+        );
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let migrate = |recent_blockhash| {
+            let instruction = Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(dao_key, false),                // DAO account
+                    AccountMeta::new_readonly(admin.pubkey(), true), // Admin
+                ],
+                data: finance_data(FinanceInstruction::MigrateReserveRatio),
+            };
+            Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer, &admin], recent_blockhash)
+        };
+        banks_client.process_transaction(migrate(recent_blockhash)).await.unwrap();
+
+        let account = banks_client.get_account(dao_key).await.unwrap().unwrap();
+        let after = HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap();
+        assert_eq!(after.treasury.reserve_ratio_bps, 2_500);
+        assert_eq!(after.treasury.max_single_claim_bps, 1_000);
+        assert_eq!(after.treasury.shared_pool, 7);
+        assert_eq!(after.alert_margin, 42);
+        assert_eq!(after.claims_count, 1);
+        assert_eq!(after.open_claim_exposure, 1_000);
+
+        // A migrated account is left alone
+        let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+        assert_eq!(
+            banks_client.process_transaction(migrate(recent_blockhash)).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::AccountAlreadyInitialized),
+        );
+    }
+
+    #[test]
+    fn test_band_reserve_above_balance_leaves_nothing_available() {
+        let band = RiskBand { min_risk_score: 0, max_risk_score: 99, reserve_ratio_bps: 15_000, balance: 1_000_000 };
+        assert_eq!(band_available(&band), 0);
+
+        let full = RiskBand { reserve_ratio_bps: u16::MAX, balance: u64::MAX, ..band };
+        assert_eq!(band_available(&full), 0);
+    }
+
+    #[tokio::test]
+    async fn test_members_sharing_a_risk_score_keep_separate_profiles() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let admin = Keypair::new();
+
+        // Two members whose keys compute the same risk score
+        let first = Keypair::new();
+        let second = std::iter::repeat_with(Keypair::new).find(|k| calculate_risk_score(&k.pubkey()) == calculate_risk_score(&first.pubkey())).unwrap();
+
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 0, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(second.pubkey(), 2_000_000, treasury);
+        dao.admin = admin.pubkey();
+        dao.members.push(member_record(first.pubkey(), MemberTier::Gold));
+
+        let (mut banks_client, payer, recent_blockhash) = treasury_test(program_id, dao_key, &dao, admin.pubkey(), second.pubkey()).start().await;
+
+        // Adding a small profile for the first member leaves the second member's profile alone
+        let update = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                  // DAO account
+                AccountMeta::new_readonly(first.pubkey(), false),  // Member whose profile changes
+                AccountMeta::new_readonly(admin.pubkey(), true),   // Admin
+            ],
+            data: finance_data(FinanceInstruction::UpdateRiskProfile { risk_score: calculate_risk_score(&first.pubkey()), coverage_limit: 1_000_000 }),
+        };
+        // The second member's 2M claim is paid from their own profile, not the first member's 1M limit
+        let payout = payout_instruction(program_id, dao_key, admin.pubkey(), second.pubkey(), 0, None);
+        let transaction = Transaction::new_signed_with_payer(&[update, payout], Some(&payer.pubkey()), &[&payer, &admin], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let after = dao.reload(&mut banks_client, program_id, dao_key, &[]).await;
+        assert_eq!(after.risk_profiles.len(), 2);
+        let profile = |member: Pubkey| after.risk_profiles.iter().find(|rp| rp.member == member).unwrap();
+        assert_eq!(profile(first.pubkey()).coverage_limit, 1_000_000);
+        assert_eq!(profile(first.pubkey()).claims_paid, 0);
+        assert_eq!(profile(second.pubkey()).coverage_limit, u64::MAX);
+        assert_eq!(profile(second.pubkey()).claims_paid, 2_000_000);
+        assert_eq!(after.claims[0].status, ClaimStatus::Paid);
+    }
+
+    // DAO pricing premiums at half the 100_000 lamport base below risk score 50 and double it from 50 up
+    fn dao_with_premium_schedule(member: Pubkey, risk_score: u8) -> Ledger {
+        let treasury = Treasury { balance: 0, reserve_ratio_bps: 1_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(member, 1_000, treasury);
+        dao.base_premium = 100_000;
+        dao.risk_multiplier_bps = vec![(0, 5_000), (50, 20_000)];
+        dao.risk_profiles[0].risk_score = risk_score;
+        dao
+    }
+
+    #[test]
+    fn test_premium_quote_follows_risk_score() {
+        let member = Pubkey::new_unique();
+        let low = dao_with_premium_schedule(member, 10);
+        let high = dao_with_premium_schedule(member, 80);
+        assert_eq!(premium_quote(&low, &low.members[0]), 50_000);
+        assert_eq!(premium_quote(&high, &high.members[0]), 200_000);
+
+        // Without a base premium the tier premium applies
+        let mut tiered = dao_with_premium_schedule(member, 80);
+        tiered.base_premium = 0;
+        assert_eq!(premium_quote(&tiered, &tiered.members[0]), tier_benefits(MemberTier::Gold).premium);
+    }
+
+    #[tokio::test]
+    async fn test_premium_checked_against_risk_quote() {
+        for (risk_score, quote) in [(10, 50_000), (80, 200_000)] {
+            let program_id = Pubkey::new_unique();
+            let dao_key = Pubkey::new_unique();
+            let member = Keypair::new();
+            let dao = dao_with_premium_schedule(member.pubkey(), risk_score);
+
+            let (mut banks_client, payer, recent_blockhash) = treasury_test(program_id, dao_key, &dao, dao.admin, member.pubkey()).start().await;
+            let lamports_before = banks_client.get_account(dao_key).await.unwrap().unwrap().lamports;
+
+            let pay = |amount| {
+                let instruction = premium_instruction(program_id, dao_key, member.pubkey(), amount);
+                Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer, &member], recent_blockhash)
+            };
+            assert_eq!(
+                banks_client.process_transaction(pay(quote - 1)).await.unwrap_err().unwrap(),
+                TransactionError::InstructionError(0, InstructionError::Custom(DaoError::PremiumUnderpaid as u32)),
+            );
+            banks_client.process_transaction(pay(quote)).await.unwrap();
+
+            // The premium moves into the treasury along with the recorded balance
+            let account = banks_client.get_account(dao_key).await.unwrap().unwrap();
+            assert_eq!(account.lamports, lamports_before + quote);
+            assert_eq!(HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap().treasury.balance, quote);
+        }
+    }
+
+    #[test]
+    fn test_claim_free_periods_accrue_and_reset() {
+        let period = 30 * 86_400;
+        let mut member = Member { last_premium_ts: period, ..member_record(Pubkey::new_unique(), MemberTier::Gold) };
+
+        let pay = |member: &mut Member, now: i64| {
+            accrue_claim_free_period(member, now, period);
+            member.last_premium_ts = now;
+        };
+        pay(&mut member, 2 * period);
+        pay(&mut member, 2 * period + 1); // A second payment within the period doesn't count twice
+        pay(&mut member, 3 * period + 1);
+        assert_eq!(member.periods_claim_free, 2);
+
+        // A paid claim resets the streak, and the period it was paid in doesn't count
+        member.end_claim_free_streak(3 * period + 10);
+        pay(&mut member, 4 * period + 1);
+        assert_eq!(member.periods_claim_free, 0);
+        pay(&mut member, 5 * period + 1);
+        assert_eq!(member.periods_claim_free, 1);
+    }
+
+    #[test]
+    fn test_no_claims_discount_capped() {
+        let member = Pubkey::new_unique();
+        let treasury = Treasury { balance: 0, reserve_ratio_bps: 1_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(member, 1_000, treasury);
+        dao.no_claims_discount_step_bps = 500;
+        dao.no_claims_discount_max_bps = 3_000;
+
+        // 5% per clean period on the 400_000 Gold premium, up to 30%
+        for (periods, quote) in [(0, 400_000), (1, 380_000), (6, 280_000), (20, 280_000)] {
+            dao.members[0].periods_claim_free = periods;
+            assert_eq!(premium_quote(&dao, &dao.members[0]), quote);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_payout_resets_no_claims_discount() {
+        let member = Keypair::new();
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 0, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(member.pubkey(), 1_000_000, treasury);
+        dao.no_claims_discount_step_bps = 500;
+        dao.no_claims_discount_max_bps = 3_000;
+        dao.members[0].periods_claim_free = 4;
+
+        let (result, dao) = pay_claim(&dao, &member, 500_000).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.members[0].periods_claim_free, 0);
+        assert_eq!(premium_quote(&dao, &dao.members[0]), tier_benefits(MemberTier::Gold).premium);
+    }
+
+    #[tokio::test]
+    async fn test_token_premium_and_payout() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let member = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let (authority, _) = Pubkey::find_program_address(&[TREASURY_AUTHORITY_SEED, dao_key.as_ref()], &program_id);
+        let member_tokens = Pubkey::new_unique();
+        let treasury_tokens = Pubkey::new_unique();
+
+        let treasury = Treasury { balance: 0, reserve_ratio_bps: 0, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: Some(mint) };
+        let admin = Keypair::new();
+        let mut dao = dao_with_claim(member.pubkey(), 300_000, treasury);
+        dao.admin = admin.pubkey();
+
+        // The SPL token program is loaded into the test validator by default
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, 0);
+        program_test.add_account(member_tokens, token_account(mint, member.pubkey(), 5_000_000));
+        program_test.add_account(treasury_tokens, token_account(mint, authority, 0));
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let premium = tier_benefits(MemberTier::Gold).premium;
+        let pay = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                  // DAO account
+                AccountMeta::new_readonly(member.pubkey(), true),  // Member paying the premium
+                AccountMeta::new(member_tokens, false),            // Member's token account
+                AccountMeta::new(treasury_tokens, false),          // Treasury token account
+                AccountMeta::new_readonly(spl_token::id(), false), // SPL token program
+            ],
+            data: finance_data(FinanceInstruction::PayPremium { amount: premium, nonce: 0 }),
+        };
+        let payout = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                  // DAO account
+                AccountMeta::new_readonly(admin.pubkey(), true),   // Admin releasing the payout
+                AccountMeta::new(claim_address(&program_id, &dao_key, 0).0, false), // Claim account
+                AccountMeta::new(treasury_tokens, false),          // Treasury token account
+                AccountMeta::new(member_tokens, false),            // Member's token account
+                AccountMeta::new_readonly(authority, false),       // Treasury authority PDA
+                AccountMeta::new_readonly(spl_token::id(), false), // SPL token program
+            ],
+            data: finance_data(FinanceInstruction::PayoutClaim { claim_id: 0, amount: None }),
+        };
+        let transaction = Transaction::new_signed_with_payer(&[pay, payout], Some(&payer.pubkey()), &[&payer, &member, &admin], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let token_balance = |account: Account| spl_token::state::Account::unpack(&account.data).unwrap().amount;
+        assert_eq!(token_balance(banks_client.get_account(member_tokens).await.unwrap().unwrap()), 5_000_000 - premium + 300_000);
+        assert_eq!(token_balance(banks_client.get_account(treasury_tokens).await.unwrap().unwrap()), premium - 300_000);
+        let after = dao.reload(&mut banks_client, program_id, dao_key, &[]).await;
+        assert_eq!(after.treasury.balance, premium - 300_000);
+        assert_eq!(after.claims[0].status, ClaimStatus::Paid);
+    }
+
+    #[tokio::test]
+    async fn test_token_payout_to_foreign_account_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let member = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let (authority, _) = Pubkey::find_program_address(&[TREASURY_AUTHORITY_SEED, dao_key.as_ref()], &program_id);
+        let other_tokens = Pubkey::new_unique();
+        let treasury_tokens = Pubkey::new_unique();
+
+        let treasury = Treasury { balance: 1_000_000, reserve_ratio_bps: 0, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: Some(mint) };
+        let admin = Keypair::new();
+        let mut dao = dao_with_claim(member.pubkey(), 300_000, treasury);
+        dao.admin = admin.pubkey();
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, 0);
+        program_test.add_account(other_tokens, token_account(mint, Pubkey::new_unique(), 0));
+        program_test.add_account(treasury_tokens, token_account(mint, authority, 1_000_000));
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        // The destination must belong to the claimant, not whoever assembled the transaction
+        let payout = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                  // DAO account
+                AccountMeta::new_readonly(admin.pubkey(), true),   // Admin releasing the payout
+                AccountMeta::new(claim_address(&program_id, &dao_key, 0).0, false), // Claim account
+                AccountMeta::new(treasury_tokens, false),          // Treasury token account
+                AccountMeta::new(other_tokens, false),             // Someone else's token account
+                AccountMeta::new_readonly(authority, false),       // Treasury authority PDA
+                AccountMeta::new_readonly(spl_token::id(), false), // SPL token program
+            ],
+            data: finance_data(FinanceInstruction::PayoutClaim { claim_id: 0, amount: None }),
+        };
+        let transaction = Transaction::new_signed_with_payer(&[payout], Some(&payer.pubkey()), &[&payer, &admin], recent_blockhash);
+        assert_eq!(
+            banks_client.process_transaction(transaction).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::InvalidAccountData),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_token_treasury_takes_split_premium_donation_and_deposit() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let member = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let (authority, _) = Pubkey::find_program_address(&[TREASURY_AUTHORITY_SEED, dao_key.as_ref()], &program_id);
+        let sources = [Keypair::new(), Keypair::new()];
+        let source_tokens = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let treasury_tokens = Pubkey::new_unique();
+
+        let treasury = Treasury { balance: 0, reserve_ratio_bps: 0, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: Some(mint) };
+        let dao = dao_with_claim(member, 1_000, treasury);
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, 0);
+        for (source, tokens) in sources.iter().zip(source_tokens) {
+            program_test.add_account(tokens, token_account(mint, source.pubkey(), 5_000_000));
+        }
+        program_test.add_account(treasury_tokens, token_account(mint, authority, 0));
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let premium = premium_quote(&dao, &dao.members[0]);
+        let split = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                      // DAO account
+                AccountMeta::new_readonly(member, false),              // Member the premium is for
+                AccountMeta::new(treasury_tokens, false),              // Treasury token account
+                AccountMeta::new_readonly(spl_token::id(), false),     // SPL token program
+                AccountMeta::new_readonly(sources[0].pubkey(), true),  // Personal wallet
+                AccountMeta::new(source_tokens[0], false),             // Its token account
+                AccountMeta::new_readonly(sources[1].pubkey(), true),  // Employer or HSA account
+                AccountMeta::new(source_tokens[1], false),             // Its token account
+            ],
+            data: finance_data(FinanceInstruction::PaySplitPremium { amounts: vec![premium - 100_000, 100_000], nonce: 0 }),
+        };
+        let contribute = |instruction: FinanceInstruction| Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                     // DAO account
+                AccountMeta::new_readonly(sources[0].pubkey(), true), // Donor or depositor
+                AccountMeta::new(source_tokens[0], false),            // Their token account
+                AccountMeta::new(treasury_tokens, false),             // Treasury token account
+                AccountMeta::new_readonly(spl_token::id(), false),    // SPL token program
+            ],
+            data: finance_data(instruction),
+        };
+        let instructions = [split, contribute(FinanceInstruction::Donate { amount: 300_000 }), contribute(FinanceInstruction::DepositTreasury { amount: 200_000 })];
+        let transaction = Transaction::new_signed_with_payer(&instructions, Some(&payer.pubkey()), &[&payer, &sources[0], &sources[1]], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        // Every contribution arrives as tokens in the treasury token account, and none as lamports
+        let token_balance = |account: Account| spl_token::state::Account::unpack(&account.data).unwrap().amount;
+        assert_eq!(token_balance(banks_client.get_account(treasury_tokens).await.unwrap().unwrap()), premium + 500_000);
+        assert_eq!(token_balance(banks_client.get_account(source_tokens[0]).await.unwrap().unwrap()), 5_000_000 - (premium - 100_000) - 500_000);
+        assert_eq!(token_balance(banks_client.get_account(source_tokens[1]).await.unwrap().unwrap()), 4_900_000);
+        let after = dao.reload(&mut banks_client, program_id, dao_key, &[]).await;
+        assert_eq!(after.treasury.balance, premium + 500_000);
+        assert_eq!(after.members[0].premiums_paid, premium);
+        assert_eq!(after.donations_received, 300_000);
+        assert_eq!(after.deposits_received, 200_000);
+    }
+
+    #[tokio::test]
+    async fn test_funded_treasury_keeps_its_mint() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let admin = Keypair::new();
+
+        let treasury = Treasury { balance: 1_000_000, reserve_ratio_bps: 0, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(Pubkey::new_unique(), 1_000, treasury);
+        dao.admin = admin.pubkey();
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, 1_000_000);
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let set_mint = |mint| Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                // DAO account
+                AccountMeta::new_readonly(admin.pubkey(), true), // Admin
+            ],
+            data: finance_data(FinanceInstruction::SetTokenMint { mint }),
+        };
+
+        // Switching currency would reinterpret the lamports on record as tokens
+        let transaction = Transaction::new_signed_with_payer(&[set_mint(Some(Pubkey::new_unique()))], Some(&payer.pubkey()), &[&payer, &admin], recent_blockhash);
+        assert_eq!(
+            banks_client.process_transaction(transaction).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::InvalidAccountData),
+        );
+
+        // Restating the current currency changes nothing and is allowed
+        let transaction = Transaction::new_signed_with_payer(&[set_mint(None)], Some(&payer.pubkey()), &[&payer, &admin], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+        let after = dao.reload(&mut banks_client, program_id, dao_key, &[]).await;
+        assert_eq!(after.treasury.token_mint, None);
+    }
+
+    #[tokio::test]
+    async fn test_non_member_deposits_into_treasury() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let depositor = Keypair::new();
+
+        let treasury = Treasury { balance: 1_000_000, reserve_ratio_bps: 2_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(Pubkey::new_unique(), 1_000, treasury);
+        dao.treasury_account = dao_key; // Treasury held in the DAO account
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, 0);
+        program_test.add_account(
+            depositor.pubkey(),
+            Account { lamports: 10_000_000, data: Vec::new(), owner: system_program::id(), executable: false, rent_epoch: 0 },
+        );
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let lamports_before = banks_client.get_balance(dao_key).await.unwrap();
+
+        let deposit = |treasury: Pubkey| Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                       // DAO account
+                AccountMeta::new(depositor.pubkey(), true),             // Depositor, not a member
+                AccountMeta::new(treasury, false),                      // Treasury
+                AccountMeta::new_readonly(system_program::id(), false), // System program
+            ],
+            data: finance_data(FinanceInstruction::DepositTreasury { amount: 4_000_000 }),
+        };
+
+        // Funds can't be steered into an account other than the DAO's treasury
+        let transaction = Transaction::new_signed_with_payer(&[deposit(Pubkey::new_unique())], Some(&payer.pubkey()), &[&payer, &depositor], recent_blockhash);
+        assert_eq!(
+            banks_client.process_transaction(transaction).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::InvalidArgument),
+        );
+
+        let transaction = Transaction::new_signed_with_payer(&[deposit(dao_key)], Some(&payer.pubkey()), &[&payer, &depositor], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let account = banks_client.get_account(dao_key).await.unwrap().unwrap();
+        assert_eq!(account.lamports, lamports_before + 4_000_000);
+        let after = HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap();
+        assert_eq!(after.treasury.balance, 5_000_000);
+        assert_eq!(after.deposits_received, 4_000_000);
+        assert_eq!(after.donations_received, 0);
+        assert!(!after.members.iter().any(|m| m.member_address == depositor.pubkey()));
+    }
+
+    // Propose `data` for this program under a two-of-three multi-sig, approve it with the first `approvals` signers,
+    // then try to execute it with the DAO account, the multi-sig authority PDA, and `accounts` as the inner
+    // instruction's accounts. The clock reads `now` when one is given. Returns the result and the test context.
+    async fn execute_through_multisig(
+        dao: &HealthInsuranceDAO,
+        program_id: Pubkey,
+        dao_key: Pubkey,
+        approvals: usize,
+        accounts: Vec<AccountMeta>,
+        data: Vec<u8>,
+        now: Option<i64>,
+    ) -> (Result<(), BanksClientError>, ProgramTestContext) {
+        let signers = [Keypair::new(), Keypair::new(), Keypair::new()];
+        let (authority, _) = security::multisig_authority(&program_id, &dao_key);
+        let mut dao = dao.clone();
+        dao.multi_sig_signers = signers.iter().map(|s| s.pubkey()).collect();
+        dao.multi_sig_threshold = 2;
+
+        // Leave headroom so the proposal fits in the account, which also holds the treasury's lamports
+        let mut account = dao_account(program_id, &dao);
+        account.data.resize(account.data.len() + 1_024, 0);
+        account.lamports = Rent::default().minimum_balance(account.data.len()) + dao.treasury.balance;
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        program_test.add_account(dao_key, account);
+        let mut context = program_test.start_with_context().await;
+        if let Some(now) = now {
+            context.set_sysvar(&Clock { unix_timestamp: now, ..Clock::default() });
+        }
+
+        let mut inner_accounts = vec![
+            AccountMeta::new(dao_key, false),           // DAO account
+            AccountMeta::new_readonly(authority, true), // Multi-sig authority PDA
+        ];
+        inner_accounts.extend(accounts);
+        let propose = SecurityInstruction::ProposeMultiSig {
+            program_id,
+            accounts: inner_accounts.iter().map(|a| ProposalAccount { pubkey: a.pubkey, is_signer: a.is_signer, is_writable: a.is_writable }).collect(),
+            data,
+        };
+        let mut steps = vec![(propose, &signers[0])];
+        steps.extend(signers.iter().take(approvals).map(|s| (SecurityInstruction::ApproveMultiSig { proposal_index: 0 }, s)));
+        for (step, signer) in steps {
+            let instruction = Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(dao_key, false),                 // DAO account
+                    AccountMeta::new_readonly(signer.pubkey(), true), // Multi-sig signer
+                ],
+                data: DaoInstruction::Security(step).try_to_vec().unwrap(),
+            };
+            let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&context.payer.pubkey()), &[&context.payer, signer], context.last_blockhash);
+            context.banks_client.process_transaction(transaction).await.unwrap();
+        }
+
+        let mut accounts = vec![
+            AccountMeta::new(dao_key, false),             // DAO account
+            AccountMeta::new(authority, false),           // Multi-sig authority PDA
+            AccountMeta::new_readonly(program_id, false), // Target program: this DAO
+        ];
+        accounts.extend(inner_accounts.into_iter().map(|a| AccountMeta { is_signer: false, ..a }));
+        let execute = Instruction {
+            program_id,
+            accounts,
+            data: DaoInstruction::Security(SecurityInstruction::ExecuteMultiSig { proposal_index: 0 }).try_to_vec().unwrap(),
+        };
+        let transaction = Transaction::new_signed_with_payer(&[execute], Some(&context.payer.pubkey()), &[&context.payer], context.last_blockhash);
+        (context.banks_client.process_transaction(transaction).await, context)
+    }
+
+    // Withdraw `amount` lamports from the treasury through the multi-sig with `approvals` of the two required approvals.
+    // Returns the result, what arrived, and the DAO state.
+    async fn multisig_withdrawal(approvals: usize, amount: u64) -> (Result<(), BanksClientError>, u64, HealthInsuranceDAO) {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let destination = Pubkey::new_unique();
+
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 2_000, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(Pubkey::new_unique(), 1_000, treasury);
+        dao.treasury_account = dao_key;
+
+        let withdrawal_accounts = vec![
+            AccountMeta::new(dao_key, false),                       // Treasury, held in the DAO account
+            AccountMeta::new(destination, false),                   // Destination
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+        ];
+        let data = finance_data(FinanceInstruction::WithdrawTreasury { amount, destination });
+        let (result, mut context) = execute_through_multisig(&dao, program_id, dao_key, approvals, withdrawal_accounts, data, None).await;
+
+        let received = context.banks_client.get_account(destination).await.unwrap().map_or(0, |a| a.lamports);
+        let account = context.banks_client.get_account(dao_key).await.unwrap().unwrap();
+        (result, received, HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_withdrawal_below_multisig_threshold_rejected() {
+        let (result, received, dao) = multisig_withdrawal(1, 1_000_000).await;
+        assert!(result.is_err());
+        assert_eq!(received, 0);
+        assert_eq!(dao.treasury.balance, 10_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_multisig_withdrawal_respects_reserve_floor() {
+        let (result, received, dao) = multisig_withdrawal(2, 8_000_000).await;
+        assert!(result.is_ok());
+        assert_eq!(received, 8_000_000);
+        assert_eq!(dao.treasury.balance, 2_000_000);
+
+        // One lamport more would leave the treasury below its 20% reserve
+        let (result, received, dao) = multisig_withdrawal(2, 8_000_001).await;
+        assert!(result.is_err());
+        assert_eq!(received, 0);
+        assert_eq!(dao.treasury.balance, 10_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_large_payout_triggers_emergency_pro_rata() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let admin = Keypair::new();
+        let member = Keypair::new();
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 0, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(member.pubkey(), 8_000_000, treasury);
+        for claim_id in 1..=2 {
+            dao.claims.push(Claim { claim_id, member: member.pubkey(), amount: 2_000_000, status: ClaimStatus::Verified, ..Default::default() });
+        }
+
+        let (mut banks_client, payer, recent_blockhash) = treasury_test(program_id, dao_key, &dao, admin.pubkey(), member.pubkey()).start().await;
+        let lamports_before = banks_client.get_account(member.pubkey()).await.unwrap().unwrap().lamports;
+
+        // Paying the large claim leaves 2M against 4M of approved claims
+        let payout = payout_instruction(program_id, dao_key, admin.pubkey(), member.pubkey(), 0, None);
+        let transaction = Transaction::new_signed_with_payer(&[payout], Some(&payer.pubkey()), &[&payer, &admin], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+        let after = dao.reload(&mut banks_client, program_id, dao_key, &[]).await;
+        assert_eq!(after.treasury.balance, 2_000_000);
+        assert!(after.emergency_mode);
+
+        // Each remaining claim is paid half, the share of obligations the balance covers, and only that half moves
+        let payout = payout_instruction(program_id, dao_key, admin.pubkey(), member.pubkey(), 1, None);
+        let transaction = Transaction::new_signed_with_payer(&[payout], Some(&payer.pubkey()), &[&payer, &admin], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+        let after = dao.reload(&mut banks_client, program_id, dao_key, &[]).await;
+        assert_eq!(after.treasury.balance, 1_000_000);
+        assert_eq!(after.claims[1].status, ClaimStatus::Paid);
+        assert!(after.emergency_mode);
+        let lamports_after = banks_client.get_account(member.pubkey()).await.unwrap().unwrap().lamports;
+        assert_eq!(lamports_after - lamports_before, 8_000_000 + 1_000_000);
+    }
+
+    #[test]
+    fn test_insolvency_and_pro_rata_share() {
+        let claim = Claim { amount: 4_000_000, status: ClaimStatus::Verified, ..Default::default() };
+        let treasury = Treasury { balance: 2_000_000, reserve_ratio_bps: 2_000, ..Default::default() };
+        let outstanding = Ledger::new(HealthInsuranceDAO::default(), vec![claim]).tallied_dao().outstanding_claims;
+        assert!(is_insolvent(&treasury, outstanding));
+        assert!(!is_insolvent(&Treasury { balance: 4_000_000, ..treasury.clone() }, outstanding));
+        assert_eq!(pro_rata_payout(1_000_000, 2_000_000, 4_000_000), 500_000);
+        assert_eq!(pro_rata_payout(1_000_000, 5_000_000, 4_000_000), 1_000_000);
+    }
+
+    #[test]
+    fn test_history_risk_score_ordering() {
+        let year = COVERAGE_PERIOD_SECS;
+        let clean_veteran = history_risk_score(0, 0, 1_000_000, 4 * year);
+        let clean_newcomer = history_risk_score(0, 0, 100_000, 0);
+        let occasional = history_risk_score(1, 200_000, 1_000_000, year);
+        let frequent = history_risk_score(4, 3_000_000, 1_000_000, year);
+        let heavy = history_risk_score(8, 20_000_000, 500_000, 0);
+
+        assert!(clean_veteran < clean_newcomer);
+        assert!(clean_newcomer < occasional);
+        assert!(occasional < frequent);
+        assert!(frequent < heavy);
+        assert!(heavy <= 99);
+
+        // Same claims, longer tenure: lower score
+        assert!(history_risk_score(2, 500_000, 1_000_000, 3 * year) < history_risk_score(2, 500_000, 1_000_000, 0));
+        // Same claim count, more of the premiums paid back out: higher score
+        assert!(history_risk_score(2, 2_000_000, 1_000_000, year) > history_risk_score(2, 200_000, 1_000_000, year));
+    }
+
+    #[tokio::test]
+    async fn test_risk_recalculated_from_claim_history() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let claimant = Pubkey::new_unique();
+        let now = 3 * COVERAGE_PERIOD_SECS;
+
+        let treasury = Treasury { balance: 10_000_000, reserve_ratio_bps: 0, max_single_claim_bps: 0, initialized: true, shared_pool: 0, token_mint: None };
+        let mut dao = dao_with_claim(claimant, 1_000_000, treasury);
+        dao.claims[0].paid_amount = 1_000_000;
+        dao.claims[0].status = ClaimStatus::Paid;
+        dao.members[0].premiums_paid = 500_000;
+        dao.members[0].joined_timestamp = now - COVERAGE_PERIOD_SECS;
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, 0);
+        let mut context = program_test.start_with_context().await;
+        context.set_sysvar(&Clock { unix_timestamp: now, ..Clock::default() });
+
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![AccountMeta::new(dao_key, false)], // DAO account
+            data: finance_data(FinanceInstruction::RecalculateRisk { member: claimant }),
+        };
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&context.payer.pubkey()), &[&context.payer], context.last_blockhash);
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        let account = context.banks_client.get_account(dao_key).await.unwrap().unwrap();
+        let after = HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap();
+        assert_eq!(after.risk_profiles[0].risk_score, history_risk_score(1, 1_000_000, 500_000, COVERAGE_PERIOD_SECS));
+    }
+}
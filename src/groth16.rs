@@ -0,0 +1,274 @@
+use solana_program::alt_bn128::prelude::{alt_bn128_addition, alt_bn128_multiplication, alt_bn128_pairing};
+
+// Groth16 verification of claim proofs over BN254, using the alt_bn128 syscalls. Points and scalars use the
+// big-endian EIP-197 encoding the syscalls expect; G2 coordinates list the imaginary part first.
+//
+// A packed proof is A (G1) || B (G2) || C (G1) followed by the public inputs as 32-byte scalars. A verifying key is
+// alpha (G1) || beta (G2) || gamma (G2) || delta (G2) followed by IC[0] and one G1 point per public input.
+
+const G1_LEN: usize = 64;
+const G2_LEN: usize = 128;
+const SCALAR_LEN: usize = 32;
+
+// Public inputs the claim circuit exposes
+pub const PUBLIC_INPUTS: usize = 1;
+
+// Length of a packed proof with its public inputs
+pub const PACKED_PROOF_LEN: usize = G1_LEN + G2_LEN + G1_LEN + PUBLIC_INPUTS * SCALAR_LEN;
+
+// Length of the claim circuit's verifying key
+pub const VERIFYING_KEY_LEN: usize = G1_LEN + 3 * G2_LEN + (PUBLIC_INPUTS + 1) * G1_LEN;
+
+// Base field modulus p; G1 points are negated as (x, p - y)
+const FIELD_MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x97, 0x81, 0x6a, 0x91, 0x68, 0x71, 0xca, 0x8d, 0x3c, 0x20, 0x8c, 0x16, 0xd8, 0x7c, 0xfd, 0x47,
+];
+
+// Order r of the BN254 groups; public inputs must be reduced below it
+const SCALAR_MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xf0, 0x00, 0x00, 0x01,
+];
+
+// Development verifying key for the claim circuit. Its setup secrets are known, so it only exists in test builds;
+// deployments store the key from the circuit's trusted setup on chain instead.
+#[cfg(test)]
+const ALPHA_G1: [u8; 64] = [
+    0x2a, 0x14, 0x70, 0x55, 0x37, 0xb0, 0x09, 0x18, 0x9d, 0xa8, 0x80, 0x86, 0x51, 0xee, 0xcd, 0xb8,
+    0x24, 0x82, 0x47, 0x7f, 0xe9, 0x2a, 0xc1, 0x2c, 0xa8, 0xb7, 0x1f, 0x80, 0xfc, 0x3d, 0x49, 0xef,
+    0x2d, 0xf7, 0xee, 0x7f, 0x24, 0x3e, 0xa8, 0xb3, 0x8e, 0x1d, 0xdf, 0x14, 0x02, 0x92, 0x58, 0x87,
+    0x7a, 0x61, 0x8c, 0x77, 0x9f, 0xd4, 0x71, 0x7d, 0xb6, 0x17, 0x7e, 0x19, 0xea, 0x67, 0xec, 0x38,
+];
+
+#[cfg(test)]
+const BETA_G2: [u8; 128] = [
+    0x00, 0x9e, 0xda, 0xf0, 0x69, 0x8a, 0x8c, 0x56, 0xf5, 0x11, 0x39, 0x58, 0x8a, 0xcc, 0x09, 0x4c,
+    0xee, 0x3c, 0x37, 0xd4, 0x27, 0xbb, 0x6d, 0x2e, 0xab, 0x83, 0x0a, 0xae, 0x52, 0x90, 0x97, 0xd1,
+    0x23, 0xad, 0x66, 0xf3, 0xa7, 0xcc, 0xa9, 0xdc, 0x75, 0x04, 0x96, 0x35, 0xfa, 0xeb, 0xd1, 0x24,
+    0x31, 0x62, 0x44, 0xb9, 0x1d, 0xe5, 0xfb, 0x27, 0x64, 0xcd, 0x15, 0x15, 0x72, 0xa9, 0x05, 0xf7,
+    0x27, 0x00, 0xe8, 0xa2, 0x9b, 0x7b, 0xb4, 0x5f, 0x30, 0x22, 0xa1, 0x8a, 0x07, 0xbd, 0xc6, 0x6d,
+    0x02, 0x54, 0x55, 0x9e, 0x17, 0xcc, 0xe6, 0x4e, 0x3b, 0x4a, 0xd2, 0x15, 0x78, 0xfc, 0xf4, 0x10,
+    0x1a, 0xd4, 0xf8, 0x7d, 0x3b, 0x43, 0x75, 0xa3, 0x99, 0x88, 0xac, 0x09, 0x9b, 0x04, 0x2b, 0x1e,
+    0x7c, 0x0c, 0x71, 0x56, 0x78, 0xe4, 0xc2, 0xbe, 0xa8, 0x90, 0x5f, 0x60, 0x7c, 0xf9, 0x50, 0xf8,
+];
+
+#[cfg(test)]
+const GAMMA_G2: [u8; 128] = [
+    0x22, 0x70, 0x71, 0xbb, 0xa5, 0xff, 0x3b, 0x47, 0xed, 0x8b, 0x50, 0x4b, 0xb5, 0xb2, 0x15, 0xbc,
+    0x70, 0x1d, 0x7a, 0x32, 0x59, 0xb9, 0x33, 0xbf, 0xf1, 0xa4, 0x16, 0x4e, 0xae, 0x49, 0x9c, 0x2c,
+    0x0c, 0x51, 0xa3, 0x67, 0xb6, 0x1d, 0x31, 0x19, 0x67, 0x7b, 0x29, 0x73, 0x9d, 0xdc, 0xcb, 0xb7,
+    0x80, 0x02, 0xb5, 0x55, 0x8d, 0x8f, 0x49, 0xff, 0x16, 0xe2, 0x99, 0xc1, 0xb4, 0x1f, 0x80, 0x98,
+    0x08, 0xbb, 0x18, 0x8b, 0x2a, 0x61, 0x87, 0xbb, 0x1e, 0x87, 0x83, 0x4c, 0x85, 0xa6, 0xa9, 0x17,
+    0x76, 0x3d, 0x65, 0xb9, 0x8f, 0xeb, 0xf2, 0xc4, 0x5e, 0xa3, 0x39, 0xdd, 0x77, 0xfa, 0xc4, 0x15,
+    0x18, 0xfd, 0x2f, 0xd1, 0x3b, 0xe8, 0x49, 0x4c, 0x39, 0xe8, 0xa9, 0x13, 0x25, 0xd1, 0xef, 0x3b,
+    0xa7, 0xd1, 0xa2, 0x05, 0xd1, 0x07, 0x88, 0xe3, 0x8b, 0xc9, 0xe0, 0x9d, 0x9b, 0xe8, 0x77, 0x69,
+];
+
+#[cfg(test)]
+const DELTA_G2: [u8; 128] = [
+    0x25, 0x40, 0x7b, 0xe3, 0x5f, 0x18, 0xc6, 0x59, 0x41, 0x74, 0x37, 0x48, 0x41, 0x31, 0x14, 0x66,
+    0xc0, 0xe6, 0x6f, 0xf0, 0x03, 0x76, 0x24, 0x48, 0xc0, 0x6b, 0xca, 0x4f, 0xa5, 0xe9, 0xc5, 0x4e,
+    0x15, 0xcb, 0xba, 0x9a, 0xb7, 0x3b, 0xc7, 0x3d, 0x0b, 0xa4, 0xad, 0x13, 0x2a, 0x15, 0xcb, 0x0c,
+    0x73, 0x10, 0x7a, 0x9c, 0x19, 0xb0, 0x40, 0xc4, 0xc7, 0x3d, 0x89, 0xf6, 0xbf, 0x75, 0x40, 0x4d,
+    0x1e, 0xde, 0xf8, 0x6c, 0x1a, 0x42, 0xfa, 0x85, 0xab, 0x6a, 0xe8, 0xd2, 0x68, 0xa7, 0xe9, 0xb4,
+    0x68, 0x90, 0xb2, 0x13, 0x0d, 0xd8, 0x3b, 0x91, 0xc8, 0x6c, 0x50, 0x4c, 0xf1, 0xf9, 0x3f, 0xbf,
+    0x2c, 0x75, 0x0c, 0x04, 0x51, 0x12, 0xe4, 0xab, 0x07, 0xf1, 0x8b, 0x12, 0x47, 0x53, 0x09, 0xce,
+    0xbd, 0xcb, 0x72, 0x6b, 0xda, 0x1c, 0xa9, 0x94, 0x8b, 0xac, 0xd4, 0x98, 0xa2, 0x8c, 0xf4, 0x11,
+];
+
+// IC[0] followed by one G1 point per public input
+#[cfg(test)]
+const IC: [u8; 128] = [
+    0x1e, 0x28, 0x26, 0x0f, 0x0e, 0xe9, 0x71, 0xde, 0xc1, 0xe8, 0x4c, 0xf8, 0x1f, 0xf2, 0x77, 0x6a,
+    0xd3, 0x14, 0xd2, 0xcf, 0xb9, 0xef, 0x81, 0xd4, 0xc9, 0x70, 0x62, 0x0c, 0x29, 0xb8, 0x11, 0xf1,
+    0x28, 0xfc, 0x8a, 0x72, 0xd4, 0xff, 0x12, 0x65, 0x4c, 0x3c, 0x39, 0xda, 0xb5, 0x4e, 0xae, 0xf9,
+    0x63, 0x8d, 0x28, 0xde, 0x73, 0x89, 0x59, 0x77, 0x9f, 0xcd, 0x3e, 0x7a, 0xc9, 0x18, 0xb3, 0x96,
+    0x16, 0x05, 0xff, 0xc1, 0xea, 0x2e, 0x1a, 0xef, 0x15, 0xd7, 0x74, 0xd3, 0x20, 0x71, 0x76, 0x42,
+    0x0c, 0x5c, 0xc4, 0x54, 0xb1, 0x9b, 0x55, 0x55, 0x85, 0x62, 0xb0, 0xc7, 0xdd, 0xf0, 0x0a, 0x7d,
+    0x0c, 0xf6, 0x05, 0x87, 0x3f, 0xaa, 0x80, 0x28, 0xdf, 0x38, 0xec, 0x2d, 0x08, 0x00, 0xd5, 0xdd,
+    0xc6, 0x7f, 0x17, 0x76, 0x33, 0x8d, 0x67, 0x54, 0x91, 0xfe, 0x87, 0xf6, 0xbb, 0x73, 0x54, 0xb3,
+];
+
+// Whether `packed` holds a Groth16 proof that verifies against `verifying_key`.
+// Malformed encodings, points off the curve, and unreduced inputs all fail verification.
+pub fn verify_proof(verifying_key: &[u8], packed: &[u8]) -> bool {
+    pairing_check(verifying_key, packed).unwrap_or(false)
+}
+
+// The public inputs packed after the proof points, or None when `packed` isn't a packed proof
+pub fn public_inputs(packed: &[u8]) -> Option<&[u8]> {
+    if packed.len() != PACKED_PROOF_LEN {
+        return None;
+    }
+    Some(&packed[PACKED_PROOF_LEN - PUBLIC_INPUTS * SCALAR_LEN..])
+}
+
+// Checks e(-A, B) * e(alpha, beta) * e(L, gamma) * e(C, delta) == 1, where L commits to the public inputs
+fn pairing_check(verifying_key: &[u8], packed: &[u8]) -> Option<bool> {
+    if packed.len() != PACKED_PROOF_LEN || verifying_key.len() != VERIFYING_KEY_LEN {
+        return None;
+    }
+    let (a, rest) = packed.split_at(G1_LEN);
+    let (b, rest) = rest.split_at(G2_LEN);
+    let (c, inputs) = rest.split_at(G1_LEN);
+    let (alpha, rest) = verifying_key.split_at(G1_LEN);
+    let (beta, rest) = rest.split_at(G2_LEN);
+    let (gamma, rest) = rest.split_at(G2_LEN);
+    let (delta, ic) = rest.split_at(G2_LEN);
+
+    let neg_a = negate_g1(a)?;
+    let commitment = public_input_commitment(ic, inputs)?;
+    let mut pairs = Vec::with_capacity(4 * (G1_LEN + G2_LEN));
+    for (g1, g2) in [(&neg_a[..], b), (alpha, beta), (&commitment[..], gamma), (c, delta)] {
+        pairs.extend_from_slice(g1);
+        pairs.extend_from_slice(g2);
+    }
+    let result = alt_bn128_pairing(&pairs).ok()?;
+    Some(result.last() == Some(&1))
+}
+
+// IC[0] + sum of input_i * IC[i + 1]
+fn public_input_commitment(ic: &[u8], inputs: &[u8]) -> Option<Vec<u8>> {
+    let mut commitment = ic[..G1_LEN].to_vec();
+    for (input, point) in inputs.chunks(SCALAR_LEN).zip(ic[G1_LEN..].chunks(G1_LEN)) {
+        if input >= &SCALAR_MODULUS[..] {
+            return None;
+        }
+        let term = alt_bn128_multiplication(&[point, input].concat()).ok()?;
+        commitment = alt_bn128_addition(&[&commitment[..], &term[..]].concat()).ok()?;
+    }
+    Some(commitment)
+}
+
+// Negates a G1 point by replacing y with p - y; the point at infinity (all zeros) is its own negation
+fn negate_g1(point: &[u8]) -> Option<[u8; G1_LEN]> {
+    let mut negated = [0u8; G1_LEN];
+    negated.copy_from_slice(point);
+    let y = &point[32..];
+    if y >= &FIELD_MODULUS[..] {
+        return None;
+    }
+    if y.iter().all(|&byte| byte == 0) {
+        return Some(negated);
+    }
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let digit = FIELD_MODULUS[i] as i16 - y[i] as i16 - borrow;
+        borrow = (digit < 0) as i16;
+        negated[32 + i] = digit.rem_euclid(256) as u8;
+    }
+    Some(negated)
+}
+
+// The development verifying key, laid out as stored on chain
+#[cfg(test)]
+pub(crate) fn dev_verifying_key() -> Vec<u8> {
+    [&ALPHA_G1[..], &BETA_G2[..], &GAMMA_G2[..], &DELTA_G2[..], &IC[..]].concat()
+}
+
+// Proves `input` using the development key's setup secrets (alpha 11, beta 13, gamma 17, delta 19 and IC scalars 23
+// and 29). With B the G2 generator and C the G1 generator, A = (alpha * beta + gamma * 23 + delta) * G + input * (gamma * 29) * G.
+#[cfg(test)]
+pub(crate) fn dev_proof(input: &[u8; SCALAR_LEN]) -> Vec<u8> {
+    const G1_GENERATOR: [u8; G1_LEN] = {
+        let mut point = [0u8; G1_LEN];
+        point[31] = 1;
+        point[63] = 2;
+        point
+    };
+    const G2_GENERATOR: [u8; G2_LEN] = [
+        0x19, 0x8e, 0x93, 0x93, 0x92, 0x0d, 0x48, 0x3a, 0x72, 0x60, 0xbf, 0xb7, 0x31, 0xfb, 0x5d, 0x25,
+        0xf1, 0xaa, 0x49, 0x33, 0x35, 0xa9, 0xe7, 0x12, 0x97, 0xe4, 0x85, 0xb7, 0xae, 0xf3, 0x12, 0xc2,
+        0x18, 0x00, 0xde, 0xef, 0x12, 0x1f, 0x1e, 0x76, 0x42, 0x6a, 0x00, 0x66, 0x5e, 0x5c, 0x44, 0x79,
+        0x67, 0x43, 0x22, 0xd4, 0xf7, 0x5e, 0xda, 0xdd, 0x46, 0xde, 0xbd, 0x5c, 0xd9, 0x92, 0xf6, 0xed,
+        0x09, 0x06, 0x89, 0xd0, 0x58, 0x5f, 0xf0, 0x75, 0xec, 0x9e, 0x99, 0xad, 0x69, 0x0c, 0x33, 0x95,
+        0xbc, 0x4b, 0x31, 0x33, 0x70, 0xb3, 0x8e, 0xf3, 0x55, 0xac, 0xda, 0xdc, 0xd1, 0x22, 0x97, 0x5b,
+        0x12, 0xc8, 0x5e, 0xa5, 0xdb, 0x8c, 0x6d, 0xeb, 0x4a, 0xab, 0x71, 0x80, 0x8d, 0xcb, 0x40, 0x8f,
+        0xe3, 0xd1, 0xe7, 0x69, 0x0c, 0x43, 0xd3, 0x7b, 0x4c, 0xe6, 0xcc, 0x01, 0x66, 0xfa, 0x7d, 0xaa,
+    ];
+    let scalar = |value: u64| {
+        let mut scalar = [0u8; SCALAR_LEN];
+        scalar[SCALAR_LEN - 8..].copy_from_slice(&value.to_be_bytes());
+        scalar
+    };
+    let multiple = |point: &[u8], scalar: &[u8]| alt_bn128_multiplication(&[point, scalar].concat()).unwrap();
+
+    let fixed = multiple(&G1_GENERATOR, &scalar(11 * 13 + 17 * 23 + 19));
+    let input_base = multiple(&G1_GENERATOR, &scalar(17 * 29));
+    let a = alt_bn128_addition(&[&fixed[..], &multiple(&input_base, input)[..]].concat()).unwrap();
+    [&a[..], &G2_GENERATOR[..], &G1_GENERATOR[..], &input[..]].concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Proof of the development circuit for public input 42
+    const VALID_PROOF: [u8; 288] = [
+        0x14, 0xb4, 0xfa, 0x25, 0x12, 0x77, 0xa6, 0xf4, 0xcb, 0xbf, 0xe3, 0x79, 0xa1, 0x52, 0xa9, 0x76,
+        0x64, 0x1f, 0x58, 0xa4, 0xa2, 0xbf, 0xfd, 0x3b, 0x67, 0x7e, 0xa0, 0x93, 0xbd, 0xad, 0x85, 0x3c,
+        0x28, 0xce, 0x09, 0x4a, 0x6d, 0x16, 0x28, 0x0a, 0xbc, 0xf8, 0xd8, 0x4e, 0xfa, 0x06, 0x2c, 0x85,
+        0x51, 0x18, 0x19, 0xdd, 0x87, 0xd8, 0xda, 0x25, 0x58, 0x85, 0xce, 0x05, 0x80, 0xeb, 0xee, 0x36,
+        0x04, 0x76, 0xbe, 0x09, 0x3a, 0x6d, 0x2b, 0x4b, 0xbf, 0x90, 0x71, 0x72, 0x04, 0x98, 0x74, 0xaf,
+        0x11, 0xe1, 0xb6, 0x26, 0x76, 0x06, 0xe0, 0x08, 0x04, 0xd3, 0xff, 0x00, 0x37, 0xec, 0x57, 0xfd,
+        0x30, 0x10, 0xc6, 0x8c, 0xb5, 0x01, 0x61, 0xb7, 0xd1, 0xd9, 0x6b, 0xb7, 0x1e, 0xdf, 0xec, 0x98,
+        0x80, 0x17, 0x19, 0x54, 0xe5, 0x68, 0x71, 0xab, 0xf3, 0xd9, 0x3c, 0xc9, 0x4d, 0x74, 0x5f, 0xa1,
+        0x14, 0xc0, 0x59, 0xd7, 0x4e, 0x5b, 0x6c, 0x4e, 0xc1, 0x4a, 0xe5, 0x86, 0x4e, 0xbe, 0x23, 0xa7,
+        0x17, 0x81, 0xd8, 0x6c, 0x29, 0xfb, 0x8f, 0xb6, 0xcc, 0xe9, 0x4f, 0x70, 0xd3, 0xde, 0x7a, 0x21,
+        0x01, 0xb3, 0x34, 0x61, 0xf3, 0x9d, 0x9e, 0x88, 0x7d, 0xbb, 0x10, 0x0f, 0x17, 0x0a, 0x23, 0x45,
+        0xdd, 0xe3, 0xc0, 0x7e, 0x25, 0x6d, 0x1d, 0xfa, 0x2b, 0x65, 0x7b, 0xa5, 0xcd, 0x03, 0x04, 0x27,
+        0x0a, 0x8f, 0x9c, 0x29, 0x40, 0xce, 0x2a, 0x6e, 0x26, 0x61, 0x17, 0xc4, 0xdc, 0xdd, 0x1e, 0x1d,
+        0x12, 0x37, 0xae, 0x88, 0xf2, 0xbe, 0x03, 0x5a, 0xfd, 0xcc, 0xe2, 0x2d, 0x32, 0x04, 0x3d, 0x0a,
+        0x27, 0x74, 0x1b, 0xbc, 0x9a, 0xa8, 0x18, 0xb9, 0xaa, 0x70, 0x60, 0x44, 0x4f, 0x89, 0xe6, 0xe0,
+        0x4f, 0x38, 0x63, 0x76, 0xb1, 0x51, 0x02, 0xad, 0x0a, 0xce, 0xcc, 0x53, 0xed, 0x05, 0x0d, 0x70,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x2a,
+    ];
+
+    #[test]
+    fn test_valid_proof_verifies() {
+        assert!(verify_proof(&dev_verifying_key(), &VALID_PROOF));
+    }
+
+    #[test]
+    fn test_tampered_proof_rejected() {
+        // A different public input no longer matches the proof
+        let mut tampered = VALID_PROOF;
+        tampered[PACKED_PROOF_LEN - 1] ^= 1;
+        assert!(!verify_proof(&dev_verifying_key(), &tampered));
+
+        // Corrupting C moves it off the curve
+        let mut tampered = VALID_PROOF;
+        tampered[G1_LEN + G2_LEN + 5] ^= 1;
+        assert!(!verify_proof(&dev_verifying_key(), &tampered));
+
+        assert!(!verify_proof(&dev_verifying_key(), &VALID_PROOF[..PACKED_PROOF_LEN - 1]));
+        assert!(!verify_proof(&dev_verifying_key(), &[]));
+
+        // Nor does a proof verify against a truncated key
+        let key = dev_verifying_key();
+        assert!(!verify_proof(&key[..VERIFYING_KEY_LEN - 1], &VALID_PROOF));
+    }
+
+    #[test]
+    fn test_dev_proof_verifies_for_its_input() {
+        let mut input = [0u8; SCALAR_LEN];
+        input[SCALAR_LEN - 1] = 42;
+        let proof = dev_proof(&input);
+        assert!(verify_proof(&dev_verifying_key(), &proof));
+        assert_eq!(public_inputs(&proof), Some(&input[..]));
+
+        input[SCALAR_LEN - 1] = 43;
+        let mut mismatched = proof;
+        mismatched[PACKED_PROOF_LEN - SCALAR_LEN..].copy_from_slice(&input);
+        assert!(!verify_proof(&dev_verifying_key(), &mismatched));
+    }
+
+    #[test]
+    fn test_unreduced_public_input_rejected() {
+        let mut unreduced = VALID_PROOF;
+        unreduced[PACKED_PROOF_LEN - SCALAR_LEN..].copy_from_slice(&SCALAR_MODULUS);
+        assert!(!verify_proof(&dev_verifying_key(), &unreduced));
+    }
+}
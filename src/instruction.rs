@@ -0,0 +1,153 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+use crate::migrate::LegacyLayout;
+use crate::{ClaimShare, MemberTier, ProposalAccount, Role, RoundingMode, ServiceType, TokenVault};
+
+// Every instruction the program accepts. The outer variant selects the module that handles it,
+// so the Borsh encoding is a module byte followed by that module's instruction tag and fields.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum DaoInstruction {
+    Claims(ClaimsInstruction),
+    Finance(FinanceInstruction),
+    Dispute(DisputeInstruction),
+    Security(SecurityInstruction),
+    MigrateLegacy { layout: Option<LegacyLayout> }, // Rewrites an account written by a standalone program into the unified layout; `layout`, if given, must match the one its bytes decode as
+}
+
+// Instructions handled by the claims module
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum ClaimsInstruction {
+    SubmitClaim {
+        amount: u64,                // Claim amount in lamports
+        service_date: i64,          // Date of the medical service or event
+        external_ref: [u8; 16],     // Integrator's reference (UUID) for the claim
+        preauth_id: Option<u64>,    // Pre-authorization the claim is submitted against
+        condition: Option<[u8; 32]>, // Hash commitment to a later event the payout is contingent on
+        supersedes: Option<u64>,    // Prior rejected or withdrawn claim this claim resubmits
+        service_type: ServiceType,  // Type of medical service or event
+        nonce: u64,                 // Member's current nonce, guarding against replayed submissions
+    },
+    VerifyClaim { claim_id: u64, approve: bool }, // `approve: false` records a review that declines to approve
+    SetAffiliations { affiliations: Vec<Pubkey> },
+    RequestPreAuth { max_amount: u64, service_type: ServiceType },
+    ApprovePreAuth { preauth_index: u64, valid_for_secs: i64 },
+    ConfigurePreAuth { amount_threshold: u64, service_types: Vec<ServiceType> },
+    SetTreasury,
+    DemoteInactiveVerifiers,
+    SetVerifierInactivity { inactivity_secs: i64 },
+    SetMaxVerifiers { max_verifiers: u8 },
+    AppealClaim { claim_id: u64 },
+    ConfigurePeriodCap { period_claim_cap: u32, rollover_enabled: bool },
+    RolloverPeriod,
+    ExportClaims { offset: u64 },
+    SetConditionOracle,
+    FulfillCondition { claim_id: u64, attestation: Vec<u8> },
+    DeclareEmergency { threshold: u8, window_start: i64, expires_at: i64 },
+    DepositStake { amount: u64 },
+    ConfirmFraud { claim_id: u64 },
+    SetStakeRequirement { requirement: u64 },
+    SetCoolingWindow { cooling_seconds: i64 },
+    SetProviderSignatureRequired { required: bool },
+    WithdrawClaim { claim_id: u64 },
+    SetCategoryThresholds { thresholds: Vec<(ServiceType, u8)> },
+    SetAnnualClaimCap { cap: u32 },
+    SetPairingLimit { limit: u32 },
+    ReleaseHoldback { claim_id: u64 },
+    ConfigureHoldback { holdback_bps: u16, timeout_secs: i64 },
+    ArchiveClaims,
+    SetClaimRetention { retention_seconds: i64 },
+    SetMinReviewers { min_reviewed: u8 },
+    ConfigureMicroClaims { threshold: u64, auto_pay: bool, min_paid_claims: u32 },
+    SetFraudThresholds { suspension_strikes: u8, removal_strikes: u8 },
+    AcknowledgePayout { claim_id: u64 },
+    ConfigureAcknowledgment { threshold: u64, timeout_secs: i64 },
+    RejectClaim { claim_id: u64, reason: Option<String> },
+    SetVerificationThreshold { threshold: u8 },
+    SetClaimWindow { claim_window_secs: i64 },
+    ExpireClaims,
+    SetPendingTtl { ttl_secs: i64 },
+    BatchVerify { claim_ids: Vec<u64> }, // Approves each listed claim, skipping any the verifier can't approve
+}
+
+// Instructions handled by the finance module
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum FinanceInstruction {
+    PayPremium { amount: u64, nonce: u64 }, // `nonce` must match the payer's current nonce
+    PayoutClaim { claim_id: u64, amount: Option<u64> }, // None pays the claim's remaining balance
+    UpdateRiskProfile { risk_score: u8, coverage_limit: u64 },
+    SetReserveRatio { reserve_ratio_bps: u16 },
+    SetSingleClaimCap { cap_bps: u16 },
+    InitializeTreasury { reserve_ratio_bps: Option<u16> }, // None uses the default reserve ratio
+    GetSolvency,
+    ChangeTier { tier: MemberTier },
+    SweepDormant, // Runs only through an approved multi-sig proposal
+    SetDormancyThreshold { threshold_secs: i64 },
+    SetRoundingMode { rounding_mode: RoundingMode },
+    SetAlertMargin { alert_margin: u64 },
+    SubmitSharedClaim { amount: u64, shares: Vec<ClaimShare>, nonce: u64 }, // `nonce` must match the submitter's current nonce
+    RescaleReserveRatio,
+    SetReserveRatioBounds { min_bps: u16, max_bps: u16 },
+    Donate { amount: u64 },
+    RecomputeUtilization,
+    OpenPaymentPlan { installments_total: u8, interval_secs: i64 },
+    PayInstallment { amount: u64, nonce: u64 }, // `nonce` must match the member's current nonce
+    EstimatePayout { amount: u64 },
+    RebalanceVaults,
+    SetMinLiquidity { min_liquidity: u64 },
+    PaySplitPremium { amounts: Vec<u64>, nonce: u64 }, // One amount per source account, in account order; `nonce` is the member's
+    Heartbeat,
+    RegisterTokenVault { vault: TokenVault },
+    SetMaxPriceAge { max_age_secs: i64 },
+    ValueTreasury,
+    DefineRiskBands { bands: Vec<(u8, u8, u16)> }, // (min risk score, max risk score, reserve ratio in basis points)
+    SetServiceCaps { caps: Vec<(ServiceType, u64)> }, // (service type, largest claim amount in lamports)
+    RemoveMember, // Refunds the unearned part of the current period's premium to the removed member
+    MigrateReserveRatio, // Rewrites an account saved while the reserve ratio was an f32 into the basis-point layout
+    SetPremiumSchedule { base_premium: u64, multipliers: Vec<(u8, u16)> }, // (lowest risk score, multiplier in basis points)
+    QuotePremium { member: Pubkey }, // Returns the member's premium per period via return data
+    SetPremiumPeriod { period_secs: i64, grace_secs: i64 },
+    SetNoClaimsDiscount { step_bps: u16, max_bps: u16 }, // Discount per claim-free period and its cap, in basis points
+    SetTokenMint { mint: Option<Pubkey> }, // None returns premiums and payouts to lamports
+    DepositTreasury { amount: u64 }, // Funding from any signer, recorded apart from premiums
+    WithdrawTreasury { amount: u64, destination: Pubkey }, // Only runs as an executed multi-sig proposal
+    ClearEmergency, // Leaves emergency mode once the treasury covers its obligations again
+    RecalculateRisk { member: Pubkey }, // Rescores the member's risk profile from their on-chain claim history
+}
+
+// Instructions handled by the dispute module
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum DisputeInstruction {
+    SubmitDispute { description: String },
+    VoteDispute { dispute_index: u64, vote: bool }, // `vote: true` sides with the initiator
+    RolloverParticipation,
+    ReclaimDeposit { dispute_index: u64 },
+    CloseStaleDispute { dispute_index: u64 },
+    SetVoteWeightCap { cap_pct: u8 },
+    SetMaxTenureWeight { max_weight: u32 }, // 0 gives every member one vote regardless of tenure
+    SetDisputeDeposit { deposit: u64, slash_margin_pct: u8 }, // A slash margin of 0 refunds every deposit
+    SetOpenDisputeLimit { max_open_disputes: u32 }, // 0 removes the limit
+    SetQuorumDecay { decay_secs: i64 }, // 0 disables quorum decay
+    SetEarlyClose { enabled: bool },
+    SetParticipationPolicy { min_participation_pct: u8, penalty: u32 },
+    SetJurySize { jury_size: u8 }, // 0 lets every member vote
+}
+
+// Instructions handled by the security module
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum SecurityInstruction {
+    JoinDao { encrypted_data_hash: [u8; 32] }, // Always joins as a plain member
+    SubmitClaim { amount: u64, provider: Pubkey, service_date: i64, zkp_proof: Vec<u8>, nonce: u64 }, // Packed Groth16 proof whose public input is security::claim_statement of the claim
+    ExecuteMultiSig { proposal_index: u64 }, // Runs the proposal's instruction once enough signers have approved
+    ForgetMember,
+    SetLegalHold { legal_hold: bool },
+    ConfigureMultiSig { threshold: u8, signers: Vec<Pubkey> },
+    ProposeMultiSig { program_id: Pubkey, accounts: Vec<ProposalAccount>, data: Vec<u8> },
+    ApproveMultiSig { proposal_index: u64 },
+    ProposeAdmin { nominee: Pubkey },
+    AcceptAdmin,
+    Pause,
+    Unpause,
+    SetMemberRole { member: Pubkey, role: Role },
+    SetVerifyingKey { verifying_key: Vec<u8> }, // Claim circuit's Groth16 verifying key from its trusted setup; can only be set once
+}
@@ -0,0 +1,699 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+pub mod claims;
+pub mod dispute;
+pub mod error;
+pub mod finance;
+pub mod groth16;
+pub mod instruction;
+pub mod migrate;
+pub mod parse;
+pub mod security;
+#[cfg(test)]
+mod testing;
+
+use error::DaoError;
+use instruction::DaoInstruction;
+
+// Number of most recent payouts considered when measuring claim volatility
+pub const VOLATILITY_WINDOW: usize = 16;
+
+// Define role for access control
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum Role {
+    Admin,
+    #[default]
+    Member,
+    Verifier,
+}
+
+// Define an enum for claim status
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Default)]
+pub enum ClaimStatus {
+    #[default]
+    Pending,
+    Verified,
+    Rejected,
+    Paid,
+    Withdrawn,
+    PayoutPendingAck, // Verified high-value claim waiting for the member to acknowledge the payout
+    Appealed,         // Rejected claim under appeal, waiting on its linked dispute
+    PartiallyPaid,    // Some but not all of the claim amount has been disbursed
+}
+
+// Category of medical service or event a claim or pre-authorization covers
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceType {
+    Emergency,
+    Surgery,
+    Consultation,
+    Pharmacy,
+    Dental,
+    Other(u16), // Plan-specific category code; 0 means the service wasn't classified
+}
+
+impl Default for ServiceType {
+    fn default() -> Self {
+        ServiceType::Other(0)
+    }
+}
+
+// Subscription tiers with differentiated premiums, coverage, and deductibles
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum MemberTier {
+    #[default]
+    Bronze, // Lowest premium, lowest coverage, highest deductible
+    Silver,
+    Gold,   // Highest premium, highest coverage, lowest deductible
+}
+
+// Direction to round fractional lamports in cost-sharing calculations
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum RoundingMode {
+    FavorMember, // Round payouts up so the member receives any fractional lamport
+    #[default]
+    FavorPool,   // Round payouts down so the pool keeps any fractional lamport
+    Nearest,     // Round to the nearest lamport, with halves rounding up
+}
+
+// Member record shared by every module
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default)]
+pub struct Member {
+    pub member_address: Pubkey,     // The public key of a member for identification
+    pub joined_timestamp: i64,      // Unix timestamp when the member joined the DAO
+    pub role: Role,                 // Assigned role for access control
+    pub encrypted_data_hash: [u8; 32], // Hash of off-chain encrypted data
+    pub legal_hold: bool,           // Set by the admin when records must be retained, blocking deletion requests
+    pub affiliations: Vec<Pubkey>,  // Providers the member is affiliated with (employment, ownership, etc.)
+    pub last_verification_at: i64,  // Unix timestamp of the member's most recent claim verification
+    pub stake: u64,                 // Lamports staked as a verifier, escrowed in the DAO account and slashable
+    pub fraud_strikes: u8,          // Claims by this member confirmed fraudulent
+    pub suspended: bool,            // Set once fraud strikes reach the suspension threshold; blocks new claims
+    pub tier: MemberTier,           // Subscription tier determining premiums and benefits
+    pub pending_tier: Option<MemberTier>, // Tier the member moves to once their current paid premium period ends
+    pub premiums_paid: u64,         // Premiums contributed by the member and still attributed to them, in lamports
+    pub last_activity_at: i64,      // Unix timestamp of the member's most recent premium payment or claim activity
+    pub dormant: bool,              // Set once the member's contributions have been swept into the shared pool
+    pub last_heartbeat_at: i64,     // Unix timestamp of the member's last wellness check-in that counted toward their streak
+    pub heartbeat_streak: u32,      // Consecutive check-ins made no more than two intervals apart
+    pub reputation: u32,            // Standing within the DAO, reduced for poor governance participation
+    pub votes_cast: u32,            // Dispute votes cast by the member in the current period
+    pub disputes_eligible: u32,     // Disputes the member was eligible to vote on in the current period
+    pub nonce: u64,                 // Nonce the member's next premium payment or claim submission must carry
+    pub last_premium_ts: i64,       // Unix timestamp of the member's most recent full premium payment
+    pub last_premium_amount: u64,   // Lamports that payment (or the payment plan it completed) brought in for the period
+    pub periods_claim_free: u32,    // Consecutive premium periods without a paid claim, earning the no-claims discount
+    pub last_claim_paid_at: i64,    // Unix timestamp a claim was last paid to the member
+    pub claim_history: ClaimHistory, // Running totals over the member's claim accounts
+}
+
+// Totals over a member's claims, kept on the member record so eligibility checks and risk scoring don't need to load
+// every claim account. Each claim's contribution is removed before it changes and added back afterwards; archived
+// claims keep theirs, so closing old claim accounts doesn't rewrite the member's record.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default, PartialEq)]
+pub struct ClaimHistory {
+    pub submitted: u32,             // Claims the member has submitted, in any status
+    pub amount_submitted: u64,      // Combined amount of those claims, in lamports
+    pub service_types: Vec<(ServiceType, u32)>, // Claims submitted per service type
+    pub recent_submissions: Vec<i64>, // Submission times of claims not withdrawn drawing on the member's coverage (shared claims included), pruned to the rolling claim year
+    pub open: u32,                  // Submitted claims not yet paid, rejected, or withdrawn
+    pub in_flight: u32,             // Claims drawing on the member's coverage (shared claims included) awaiting review, payout, or appeal
+    pub paid: u32,                  // Submitted claims paid in full
+    pub rejected: u32,              // Submitted claims currently rejected
+    pub paid_out: u32,              // Submitted claims with any amount disbursed
+    pub paid_out_total: u64,        // Amount disbursed on those claims, in lamports
+    pub paid_coverage: u64,         // Coverage drawn by fully paid claims, including shares of shared claims, in lamports
+}
+
+// Claims filed against a provider and how many of them stand rejected, used to score new claims from the provider
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct ProviderClaims {
+    pub provider: Pubkey, // The provider's public key
+    pub claims: u32,      // Claims naming the provider
+    pub rejected: u32,    // Those claims currently rejected
+}
+
+// Claim record shared by every module, stored in its own PDA derived from ["claim", dao_key, claim_id]
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default)]
+pub struct Claim {
+    pub claim_id: u64,           // Unique identifier for each claim
+    pub member: Pubkey,          // The member who submitted the claim
+    pub amount: u64,             // The amount of the claim in lamports
+    pub service_date: i64,       // Date of the medical service or event
+    pub service_type: ServiceType, // Type of medical service or event
+    pub provider: Pubkey,        // The provider's public key
+    pub status: ClaimStatus,     // Current status of the claim
+    pub verifiers: Vec<Pubkey>,  // List of oracles or verifiers who have checked this claim
+    pub preauth_id: Option<u64>, // Pre-authorization this claim was submitted against, if any
+    pub assigned_verifiers: Vec<Pubkey>, // Verifiers allowed to review the claim after an appeal (empty means any verifier)
+    pub condition: Option<[u8; 32]>, // Hash commitment to a later event the payout is contingent on
+    pub condition_met: bool,     // Set once the condition oracle attests to the committed event
+    pub external_ref: [u8; 16],  // Integrator's reference (UUID) for this claim in their off-chain adjudication system
+    pub submitted_at: i64,       // Unix timestamp when the claim was submitted
+    pub provider_attested: bool, // Set when the provider co-signed the submission, attesting the service occurred
+    pub supersedes: Option<u64>, // Prior rejected or withdrawn claim from the same member that this claim resubmits
+    pub holdback: u64,           // Portion of the payout withheld until the provider confirms the service, in lamports
+    pub holdback_release_at: i64, // Unix timestamp after which the holdback is released without provider confirmation
+    pub anomaly_score: u8,       // Heuristic 0-100 outlier score computed at submission to guide verifiers
+    pub rejections: Vec<Pubkey>, // Verifiers who reviewed the claim and declined to approve it
+    pub ack_requested_at: i64,   // Unix timestamp the payout acknowledgment was requested from the member
+    pub acknowledged: bool,      // Set once the member has acknowledged a high-value payout
+    pub shares: Vec<ClaimShare>, // Per-member shares for claims covering several members (empty for single-member claims)
+    pub zkp_proof: Vec<u8>,      // Packed Groth16 proof and public inputs (empty for claims submitted without one)
+    pub rejection_reason: Option<String>, // Reason recorded by the verifier who rejected the claim
+    pub appealed: bool,          // Set once the claimant has appealed the rejection; each claim may be appealed once
+    pub paid_amount: u64,        // Portion of the claim amount disbursed so far, in lamports
+    pub resubmitted: bool,       // Set once a later claim supersedes this one; each claim may be resubmitted once
+}
+
+// One covered member's portion of a shared claim
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct ClaimShare {
+    pub member: Pubkey, // Member whose coverage this share draws from
+    pub amount: u64, // Portion of the claim amount attributed to the member, in lamports
+}
+
+// Pre-authorization for a planned procedure, requested by a member before the service
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct PreAuth {
+    pub preauth_id: u64,         // Unique identifier for each pre-authorization
+    pub member: Pubkey,          // The member who requested the pre-authorization
+    pub provider: Pubkey,        // The provider expected to perform the service
+    pub service_type: ServiceType, // Type of medical service being pre-authorized
+    pub max_amount: u64,         // Maximum claim amount covered by this pre-authorization, in lamports
+    pub approved: bool,          // Set once the admin approves the request
+    pub expires_at: i64,         // Unix timestamp after which the pre-authorization can no longer be used
+    pub used: bool,              // Set once a claim has been submitted against it
+}
+
+// Number of times a verifier has verified claims for a provider, used to rotate verifiers away from familiar providers
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct VerifierPairing {
+    pub verifier: Pubkey,        // The verifier who reviewed the claims
+    pub provider: Pubkey,        // The provider whose claims were reviewed
+    pub count: u32,              // Verifications this verifier has recorded on this provider's claims
+}
+
+// Account an instruction wrapped in a multi-sig proposal reads or writes
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct ProposalAccount {
+    pub pubkey: Pubkey,    // The account's address
+    pub is_signer: bool,   // Whether the inner instruction needs the account's signature (only the multi-sig authority can sign)
+    pub is_writable: bool, // Whether the inner instruction may modify the account
+}
+
+// Instruction awaiting approval from the multi-sig signers before the program executes it
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct MultiSigProposal {
+    pub proposer: Pubkey,               // Multi-sig signer who proposed the instruction
+    pub program_id: Pubkey,             // Program the wrapped instruction is sent to
+    pub accounts: Vec<ProposalAccount>, // Accounts of the wrapped instruction, in order
+    pub data: Vec<u8>,                  // Instruction data of the wrapped instruction
+    pub threshold: u8,                  // Approvals needed, fixed when the proposal is made
+    pub approvals: Vec<Pubkey>,         // Distinct multi-sig signers who have approved
+    pub executed: bool,                 // Set once the wrapped instruction has run
+}
+
+// Declared emergency temporarily lowering the verification bar for affected claims
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct Emergency {
+    pub verification_threshold: u8, // Reduced number of verifications needed during the emergency
+    pub window_start: i64,       // Earliest service date covered by the emergency
+    pub expires_at: i64,         // End of the emergency; also the latest covered service date
+}
+
+// Installment schedule for a member paying their premium over time instead of up front
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct PaymentPlan {
+    pub member: Pubkey, // The member paying in installments
+    pub total_owed: u64, // Premium covered by the plan, in lamports
+    pub amount_paid: u64, // Installments received so far, in lamports
+    pub installment_amount: u64, // Amount due at each installment, in lamports
+    pub installments_total: u8, // Number of installments in the schedule
+    pub installments_paid: u8, // Installments received so far
+    pub start_at: i64, // Unix timestamp the first installment is due
+    pub interval_secs: i64, // Time between installment due dates
+}
+
+// Define structures for risk assessment
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default)]
+pub struct RiskProfile {
+    pub member: Pubkey, // The member the profile belongs to; profiles are looked up by this key, never by score
+    pub risk_score: u8, // Simplified risk score, could be based on health data, claim history, etc.
+    pub coverage_limit: u64, // Maximum claim amount based on risk, in lamports
+    pub deductible: u64, // Amount of each claim the member covers themselves, in lamports
+    pub coinsurance_bps: u16, // Member's share of each claim after the deductible, in basis points
+    pub claims_paid: u64, // Total of paid claims drawn against this coverage, in lamports
+    pub utilization_bps: u16, // Cached share of the coverage limit used by paid claims, in basis points (capped at 100%)
+    pub used_coverage: u64, // Claim amounts paid against the coverage limit in the current coverage year, in lamports
+    pub coverage_period_start: i64, // Unix timestamp the current coverage year began
+}
+
+// Define structures for financial management
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default)]
+pub struct Treasury {
+    pub balance: u64, // Current balance of the treasury in lamports
+    pub reserve_ratio_bps: u16, // Share of funds to keep in reserve for liquidity and solvency, in basis points (0..=10000)
+    pub max_single_claim_bps: u16, // Largest share of the balance a single payout may take, in basis points (0 disables the cap)
+    pub initialized: bool, // Set once the admin has initialized the treasury; payouts are blocked until then
+    pub shared_pool: u64, // Contributions reclaimed from dormant members, no longer attributed to anyone
+    pub token_mint: Option<Pubkey>, // SPL mint premiums and payouts are made in; None keeps the treasury in lamports
+}
+
+// Sub-pool for members whose risk score falls in the band, so very different risks don't share one pool
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct RiskBand {
+    pub min_risk_score: u8, // Lowest risk score assigned to the band
+    pub max_risk_score: u8, // Highest risk score assigned to the band
+    pub reserve_ratio_bps: u16, // Share of the band's balance held back from payouts, in basis points
+    pub balance: u64, // Premiums paid into the band and not yet paid out, in lamports
+}
+
+// A token vault held by the treasury, valued in the base unit through its price oracle
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct TokenVault {
+    pub mint: Pubkey, // Mint of the tokens the vault holds
+    pub vault: Pubkey, // SPL token account holding the treasury's tokens
+    pub oracle: Pubkey, // Price oracle quoting one whole token in the base unit
+    pub decimals: u8, // Decimals of the mint, to convert raw amounts into whole tokens
+}
+
+// Define structure for a dispute within the DAO
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default)]
+pub struct Dispute {
+    pub dispute_id: u64,                // Unique identifier for the dispute
+    pub claim_id: Option<u64>,          // Optional link to a specific claim this dispute relates to
+    pub initiator: Pubkey,              // Public key of the member initiating the dispute
+    pub respondent: Pubkey,             // Public key of the member or entity the dispute is against
+    pub description: String,            // Detailed explanation of the dispute
+    pub status: DisputeStatus,          // Current status of the dispute
+    pub votes: Vec<(Pubkey, bool)>,     // Collection of votes where each tuple contains the voter's key and their vote (true for supporting the initiator, false otherwise)
+    pub deposit: u64,                   // Filing deposit escrowed in the DAO account, in lamports
+    pub deposit_status: DepositStatus,  // What happens to the filing deposit once the dispute closes
+    pub jury_seed: [u8; 32],            // Slot-derived seed the jury was drawn from, kept so the draw can be re-checked
+    pub jurors: Vec<Pubkey>,            // Members selected to decide the dispute (empty means every member may vote)
+    pub opened_at: i64,                 // Unix timestamp the dispute was submitted, used to decay its quorum
+}
+
+// Enum to represent the status of a dispute
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Default)]
+pub enum DisputeStatus {
+    #[default]
+    Open,   // Dispute is still open and accepting votes
+    Closed, // Dispute has been resolved or voting has concluded
+}
+
+// Enum to track the filing deposit through the dispute lifecycle
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Default)]
+pub enum DepositStatus {
+    #[default]
+    Held,       // Dispute is open and the deposit is escrowed
+    Refundable, // Dispute closed without a lopsided loss; the initiator may reclaim the deposit
+    Refunded,   // Deposit has been returned to the initiator
+    Slashed,    // Dispute was lost by a wide margin; the deposit now belongs to the treasury
+}
+
+// Program state shared by every module. This is the only definition of the account layout,
+// so state written by one module's instructions is always readable by the others.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default)]
+pub struct HealthInsuranceDAO {
+    pub admin: Pubkey,           // The admin who manages the DAO
+    pub members: Vec<Member>,    // List of all members in the DAO
+    pub treasury: Treasury,      // Financial management component
+    pub risk_profiles: Vec<RiskProfile>, // Risk assessment for each member, one profile per member
+    pub disputes: Vec<Dispute>,  // Array to hold all disputes within the DAO
+    pub multi_sig_signers: Vec<Pubkey>, // Keys allowed to approve sensitive treasury and multi-sig operations
+    pub multi_sig_threshold: u8, // Approvals from registered signers needed to execute a multi-sig operation
+    pub multi_sig_proposals: Vec<MultiSigProposal>, // Instructions proposed for multi-sig execution
+    pub pending_admin: Option<Pubkey>, // Nominee who becomes admin once they accept the handoff
+    pub paused: bool,            // Circuit breaker; while set, only read-only instructions and unpausing are accepted
+
+    // Claims handling
+    pub treasury_account: Pubkey, // Address of the treasury account for claim payouts
+    pub preauths: Vec<PreAuth>,  // Pre-authorization requests for planned procedures
+    pub preauth_amount_threshold: u64,      // Claims at or above this amount may require pre-authorization (0 disables)
+    pub preauth_service_types: Vec<ServiceType>, // Service types that require pre-authorization above the threshold
+    pub verifier_inactivity_secs: i64,      // Idle time after which verifiers are demoted to members (0 disables)
+    pub max_verifiers_per_claim: u8,        // Maximum verifiers recorded on a single claim (0 uses the default)
+    pub period_claim_cap: u32,              // Claims accepted per period to bound verifier workload (0 disables)
+    pub period_claims_used: u32,            // Claims accepted so far in the current period
+    pub period_rollover_enabled: bool,      // Whether unused slots carry over into the next period
+    pub period_carryover: u32,              // Unused slots carried over from the previous period
+    pub condition_oracle: Pubkey,           // Oracle trusted to attest that claim conditions have been met
+    pub emergency: Option<Emergency>,       // Active or most recent declared emergency
+    pub verifier_stake_requirement: u64,    // Minimum stake a verifier must hold to verify claims
+    pub slashed_stakes: u64,                // Verifier stakes slashed for approving fraudulent claims, in lamports
+    pub verify_cooling_seconds: i64,        // Time after submission before a claim may be verified, for fraud checks to run
+    pub require_provider_signature: bool,   // Whether the provider must co-sign every claim submission
+    pub category_verification_thresholds: Vec<(ServiceType, u8)>, // Verifications needed per service type, overriding the default
+    pub max_claims_per_member_per_year: u32, // Claims a member may submit in any rolling 365-day window (0 disables)
+    pub max_verifier_provider_pairings: u32, // Verifications one verifier may make on one provider's claims (0 disables rotation)
+    pub verifier_pairings: Vec<VerifierPairing>, // Verifier-provider pairing history, tracked while rotation is enabled
+    pub holdback_bps: u16,                  // Share of each payout withheld pending provider confirmation, in basis points (0 disables)
+    pub holdback_timeout_secs: i64,         // Time after payout when an unconfirmed holdback is released anyway
+    pub claim_retention_seconds: i64,       // Age after which paid or rejected claims may be archived (0 disables archival)
+    pub archived_claims_root: [u8; 32],     // Commitment chaining the Merkle root of every archived batch of claims
+    pub archived_claim_count: u64,          // Claim accounts closed by archival
+    pub min_verifiers_reviewed: u8,         // Distinct verifiers who must approve or reject a claim before payout (0 disables)
+    pub micro_claim_threshold: u64,         // Claims below this amount from members in good standing skip verification (0 disables)
+    pub micro_claim_auto_pay: bool,         // Whether auto-verified micro-claims are also paid immediately
+    pub micro_claim_min_paid_claims: u32,   // Paid claims a member needs on record to be in good standing for micro-claims
+    pub fraud_suspension_strikes: u8,       // Fraud strikes at which a member is suspended (0 disables)
+    pub fraud_removal_strikes: u8,          // Fraud strikes at which a member is removed from the DAO (0 disables)
+    pub ack_required_above: u64,            // Claims at or above this amount need the member's acknowledgment before payout (0 disables)
+    pub ack_timeout_secs: i64,              // Time the member has to acknowledge before the request must be reissued
+    pub verification_threshold: u8,         // Verifications needed to move a claim to Verified (0 uses the default)
+    pub claim_window_secs: i64,             // How long after the service date a claim may still be submitted (0 disables)
+    pub pending_ttl_secs: i64,              // Age after submission at which a still-pending claim may be expired (0 disables)
+    pub claims_count: u64,                  // Claims ever submitted; the next claim takes this id, so ids are never reused
+    pub outstanding_claims: u64,            // Unpaid amounts on claims approved for payment, in lamports
+    pub open_claim_exposure: u64,           // Unpaid amounts on claims not yet paid, rejected, or withdrawn, in lamports
+    pub provider_claims: Vec<ProviderClaims>, // Claim totals per provider named on a claim
+
+    // Financial and risk management
+    pub dormancy_threshold_secs: i64, // Inactivity after which a member's contributions may be swept (0 disables)
+    pub rounding_mode: RoundingMode, // Rounding applied uniformly to every payout computation
+    pub alert_margin: u64, // Distance above the required reserve at which outflows raise a low-balance alert, in lamports
+    pub recent_payouts: [u64; VOLATILITY_WINDOW], // Ring buffer of the most recent payout amounts, fixed-size so payouts never grow the account
+    pub payouts_recorded: u64, // Payouts written to the ring buffer so far; the next one goes at this count modulo the window
+    pub reserve_ratio_min_bps: u16, // Reserve ratio used when recent payouts are perfectly steady, in basis points
+    pub reserve_ratio_max_bps: u16, // Reserve ratio reached when recent payouts are maximally volatile, in basis points
+    pub donations_received: u64, // Lamports donated to the treasury by external funders, kept apart from member premiums
+    pub payment_plans: Vec<PaymentPlan>, // Installment plans; coverage lapses while a member's plan is behind schedule
+    pub yield_balance: u64, // Treasury funds parked in the yield vault, in lamports; the treasury account holds `treasury.balance`
+    pub min_liquidity: u64, // Floor the treasury account is kept at during rebalancing regardless of the reserve ratio, in lamports
+    pub token_vaults: Vec<TokenVault>, // Accepted mints the treasury holds, each with its price oracle
+    pub max_price_age_secs: i64, // Oldest oracle price the valuation will accept
+    pub risk_bands: Vec<RiskBand>, // Risk-band sub-pools; when set, premiums fund and claims draw from the member's band only
+    pub service_coverage_caps: Vec<(ServiceType, u64)>, // Largest claim amount paid per service type, in lamports; uncapped types rely on the coverage limit
+    pub base_premium: u64, // Premium per period before the risk multiplier, in lamports (0 falls back to tier premiums)
+    pub risk_multiplier_bps: Vec<(u8, u16)>, // (lowest risk score, premium multiplier in basis points), ascending by score
+    pub premium_period_secs: i64, // Time one premium payment keeps a member covered (0 disables lapse tracking)
+    pub grace_period_secs: i64, // Time past the end of the premium period before coverage lapses
+    pub no_claims_discount_step_bps: u16, // Premium discount earned per claim-free period, in basis points (0 disables)
+    pub no_claims_discount_max_bps: u16, // Largest no-claims discount a member can earn, in basis points
+    pub deposits_received: u64, // Lamports deposited by grant makers and reinsurers, kept apart from premiums and donations
+    pub emergency_mode: bool, // Set when a payout leaves the treasury insolvent; payouts are pro-rated until the admin clears it
+
+    // Dispute resolution
+    pub min_participation_pct: u8,      // Participation rate below which members are penalized at rollover
+    pub participation_penalty: u32,     // Reputation deducted from members below the participation threshold
+    pub dispute_deposit: u64,           // Lamports an initiator must escrow to file a dispute
+    pub slash_margin_pct: u8,           // Share of votes against the initiator at or above which the deposit is slashed
+    pub jury_size: u8,                  // Jurors drawn for each new dispute (0 lets every member vote)
+    pub max_open_disputes: u32,         // Disputes that may be open at once across the DAO (0 means no limit)
+    pub quorum_decay_secs: i64,         // Time for a stale dispute's quorum to drop by one vote (0 disables decay)
+    pub next_dispute_id: u64,           // Id assigned to the next dispute; only ever increases, so ids are never reused
+    pub early_close_enabled: bool,      // Close a dispute as soon as remaining voters can no longer change the outcome
+    pub vote_weight_cap_pct: u8,        // Weight votes by reputation, each capped at this share of the total (0 counts one vote per member)
+    pub max_tenure_weight: u32,         // Most votes a member's tenure can earn them on a dispute (0 disables tenure weighting)
+}
+
+impl Member {
+    // A paid claim ends the member's run of claim-free periods
+    pub fn end_claim_free_streak(&mut self, now: i64) {
+        self.periods_claim_free = 0;
+        self.last_claim_paid_at = now;
+    }
+}
+
+impl Claim {
+    // Claim accounts are allocated with headroom for reviewers added after submission, so trailing bytes are ignored
+    pub fn load(account: &AccountInfo) -> Result<Self, ProgramError> {
+        Ok(Self::deserialize(&mut &account.data.borrow()[..])?)
+    }
+
+    pub fn save(&self, account: &AccountInfo) -> ProgramResult {
+        self.serialize(&mut &mut account.data.borrow_mut()[..])?;
+        Ok(())
+    }
+}
+
+impl HealthInsuranceDAO {
+    // The account may be allocated with headroom for growth, so bytes past the serialized state are ignored
+    pub fn load(account: &AccountInfo) -> Result<Self, ProgramError> {
+        Ok(Self::deserialize(&mut &account.data.borrow()[..])?)
+    }
+
+    // Save the updated DAO state back into the account's data
+    pub fn save(&self, account: &AccountInfo) -> ProgramResult {
+        self.serialize(&mut &mut account.data.borrow_mut()[..])?;
+        Ok(())
+    }
+
+    // Replay protection for sensitive member instructions: the supplied nonce must match the member's stored nonce,
+    // which then advances. Accounts without a member record have no nonce to check.
+    pub fn consume_nonce(&mut self, member: &Pubkey, nonce: u64) -> ProgramResult {
+        if let Some(record) = self.members.iter_mut().find(|m| m.member_address == *member) {
+            if record.nonce != nonce {
+                msg!("Nonce {} doesn't match the expected nonce {} for {}", nonce, record.nonce, member);
+                return Err(DaoError::BadNonce.into());
+            }
+            record.nonce = record.nonce.checked_add(1).ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+        Ok(())
+    }
+
+    // Whether the member is current on premiums: paid within the last period plus the grace period, or paying for the
+    // period through an installment plan with every installment due so far paid
+    pub fn coverage_active(&self, member: &Member, now: i64) -> bool {
+        self.premium_period_secs == 0
+            || now <= member.last_premium_ts.saturating_add(self.premium_period_secs).saturating_add(self.grace_period_secs)
+            || self.payment_plans.iter().any(|plan| {
+                plan.member == member.member_address && plan.amount_paid < plan.total_owed && finance::plan_is_current(plan, now)
+            })
+    }
+
+    // Fails with ProgramPaused while the circuit breaker is engaged
+    pub fn ensure_active(&self) -> ProgramResult {
+        if self.paused {
+            msg!("The DAO is paused");
+            return Err(DaoError::ProgramPaused.into());
+        }
+        Ok(())
+    }
+}
+
+// Whether `key` holds admin rights: the DAO's admin key, or a member granted the admin role
+pub fn is_admin(dao: &HealthInsuranceDAO, key: &Pubkey) -> bool {
+    *key == dao.admin || dao.members.iter().any(|m| m.member_address == *key && m.role == Role::Admin)
+}
+
+// Whether `key` holds `role`. Every member record counts as a member, whatever its role.
+pub fn has_role(dao: &HealthInsuranceDAO, key: &Pubkey, role: Role) -> bool {
+    match role {
+        Role::Admin => is_admin(dao, key),
+        Role::Verifier => dao.members.iter().any(|m| m.member_address == *key && m.role == Role::Verifier),
+        Role::Member => dao.members.iter().any(|m| m.member_address == *key),
+    }
+}
+
+// Fails with Unauthorized unless `key` holds `role`
+pub fn require_role(dao: &HealthInsuranceDAO, key: &Pubkey, role: Role) -> Result<(), ProgramError> {
+    if !has_role(dao, key, role) {
+        msg!("{} does not hold the {:?} role", key, role);
+        return Err(DaoError::Unauthorized.into());
+    }
+    Ok(())
+}
+
+// Fails with MissingRequiredSignature unless `account` signed, then with Unauthorized unless it holds `role`
+pub fn require_signed_role(dao: &HealthInsuranceDAO, account: &AccountInfo, role: Role) -> ProgramResult {
+    if !account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    require_role(dao, account.key, role)
+}
+
+// Entrypoint for the program, handling every module's instructions
+entrypoint!(process_instruction);
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Malformed or truncated instruction data is rejected here, before any handler runs
+    let instruction = DaoInstruction::try_from_slice(instruction_data).map_err(|_| ProgramError::InvalidInstructionData)?;
+    match instruction {
+        DaoInstruction::Claims(instruction) => claims::process(program_id, accounts, instruction),
+        DaoInstruction::Finance(instruction) => finance::process(program_id, accounts, instruction),
+        DaoInstruction::Dispute(instruction) => dispute::process(program_id, accounts, instruction),
+        DaoInstruction::Security(instruction) => security::process(program_id, accounts, instruction),
+        DaoInstruction::MigrateLegacy { layout } => migrate::process(program_id, accounts, layout),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction::{ClaimsInstruction, DisputeInstruction, FinanceInstruction, SecurityInstruction};
+    use solana_program::{instruction::{AccountMeta, Instruction}, rent::Rent, system_program};
+    use solana_program_test::*;
+    use solana_sdk::{
+        account::Account,
+        instruction::InstructionError,
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    };
+
+    #[tokio::test]
+    async fn test_modules_share_one_account_layout() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let member = Keypair::new();
+
+        // Leave headroom so the new member fits in the account, which also holds the treasury
+        let mut data = HealthInsuranceDAO { treasury_account: dao_key, ..Default::default() }.try_to_vec().unwrap();
+        data.resize(data.len() + 1_024, 0);
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        program_test.add_account(
+            dao_key,
+            Account { lamports: Rent::default().minimum_balance(data.len()), data, owner: program_id, executable: false, rent_epoch: 0 },
+        );
+        program_test.add_account(
+            member.pubkey(),
+            Account { lamports: 1_000_000_000, data: Vec::new(), owner: system_program::id(), executable: false, rent_epoch: 0 },
+        );
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        // Join through the security module, then pay a premium through the finance module
+        let join = DaoInstruction::Security(SecurityInstruction::JoinDao { encrypted_data_hash: [0u8; 32] });
+        let premium = DaoInstruction::Finance(FinanceInstruction::PayPremium { amount: 100_000, nonce: 0 });
+        let accounts = vec![
+            AccountMeta::new(dao_key, false),        // DAO account
+            AccountMeta::new(member.pubkey(), true), // Member
+        ];
+        let mut premium_accounts = accounts.clone();
+        premium_accounts.extend([
+            AccountMeta::new(dao_key, false),                       // Treasury
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+        ]);
+        let instructions = [
+            Instruction { program_id, accounts, data: join.try_to_vec().unwrap() },
+            Instruction { program_id, accounts: premium_accounts, data: premium.try_to_vec().unwrap() },
+        ];
+        let transaction = Transaction::new_signed_with_payer(&instructions, Some(&payer.pubkey()), &[&payer, &member], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let account = banks_client.get_account(dao_key).await.unwrap().unwrap();
+        let dao = HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap();
+        assert_eq!(dao.members.len(), 1);
+        assert_eq!(dao.members[0].role, Role::Member);
+        assert_eq!(dao.members[0].premiums_paid, 100_000);
+        assert_eq!(dao.treasury.balance, 100_000);
+    }
+
+    #[test]
+    fn test_instruction_round_trip() {
+        let instruction = DaoInstruction::Finance(FinanceInstruction::PayPremium { amount: 100_000, nonce: 0 });
+        let data = instruction.try_to_vec().unwrap();
+        assert_eq!(data[..2], [1, 0]); // Finance module, first finance instruction
+        assert_eq!(DaoInstruction::try_from_slice(&data).unwrap(), instruction);
+    }
+
+    #[test]
+    fn test_service_type_round_trip() {
+        let variants = [
+            (ServiceType::Emergency, vec![0]),
+            (ServiceType::Surgery, vec![1]),
+            (ServiceType::Consultation, vec![2]),
+            (ServiceType::Pharmacy, vec![3]),
+            (ServiceType::Dental, vec![4]),
+            (ServiceType::Other(513), vec![5, 1, 2]), // Category code follows the tag, little-endian
+        ];
+        for (service_type, encoded) in variants {
+            let data = service_type.try_to_vec().unwrap();
+            assert_eq!(data, encoded);
+            assert_eq!(ServiceType::try_from_slice(&data).unwrap(), service_type);
+        }
+        assert!(ServiceType::try_from_slice(&[5, 1]).is_err()); // Truncated category code
+    }
+
+    #[test]
+    fn test_roles_checked_against_member_records() {
+        let admin = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        let verifier = Pubkey::new_unique();
+        let member = Pubkey::new_unique();
+        let record = |member_address, role| Member { member_address, role, ..Default::default() };
+        let dao = HealthInsuranceDAO {
+            admin,
+            members: vec![record(delegate, Role::Admin), record(verifier, Role::Verifier), record(member, Role::Member)],
+            ..Default::default()
+        };
+
+        assert!(is_admin(&dao, &admin) && is_admin(&dao, &delegate));
+        assert!(!is_admin(&dao, &verifier));
+        assert!(has_role(&dao, &verifier, Role::Verifier));
+        assert!(!has_role(&dao, &member, Role::Verifier));
+        assert!(!has_role(&dao, &member, Role::Admin));
+
+        // Every member record counts as a member, but the admin key alone does not
+        assert!(has_role(&dao, &verifier, Role::Member));
+        assert!(has_role(&dao, &member, Role::Member));
+        assert!(!has_role(&dao, &admin, Role::Member));
+    }
+
+    #[tokio::test]
+    async fn test_malformed_instruction_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let data = HealthInsuranceDAO::default().try_to_vec().unwrap();
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        program_test.add_account(
+            dao_key,
+            Account { lamports: Rent::default().minimum_balance(data.len()), data, owner: program_id, executable: false, rent_epoch: 0 },
+        );
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        // An unknown module, and a premium payment cut off partway through its amount
+        for data in [vec![9, 0], vec![1, 0, 0xa0, 0x86]] {
+            let instruction = Instruction { program_id, accounts: vec![AccountMeta::new(dao_key, false)], data };
+            let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer], recent_blockhash);
+            let error = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+            assert_eq!(error, TransactionError::InstructionError(0, InstructionError::InvalidInstructionData));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_truncated_instruction_data_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let data = HealthInsuranceDAO::default().try_to_vec().unwrap();
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        program_test.add_account(
+            dao_key,
+            Account { lamports: Rent::default().minimum_balance(data.len()), data, owner: program_id, executable: false, rent_epoch: 0 },
+        );
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let instructions = [
+            DaoInstruction::Claims(ClaimsInstruction::VerifyClaim { claim_id: 0, approve: true }),
+            DaoInstruction::Finance(FinanceInstruction::SetReserveRatioBounds { min_bps: 1_000, max_bps: 3_000 }),
+            DaoInstruction::Dispute(DisputeInstruction::VoteDispute { dispute_index: 0, vote: true }),
+            DaoInstruction::Security(SecurityInstruction::JoinDao { encrypted_data_hash: [7u8; 32] }),
+        ];
+        for instruction in instructions {
+            let data = instruction.try_to_vec().unwrap();
+            // Every strict prefix fails to decode rather than reading past the end
+            for len in 0..data.len() {
+                assert!(DaoInstruction::try_from_slice(&data[..len]).is_err());
+            }
+
+            // And the program surfaces a clean error for a payload missing its last byte
+            let truncated = Instruction { program_id, accounts: vec![AccountMeta::new(dao_key, false)], data: data[..data.len() - 1].to_vec() };
+            let transaction = Transaction::new_signed_with_payer(&[truncated], Some(&payer.pubkey()), &[&payer], recent_blockhash);
+            let error = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+            assert_eq!(error, TransactionError::InstructionError(0, InstructionError::InvalidInstructionData));
+        }
+    }
+}
@@ -0,0 +1,606 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program::invoke,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_instruction, system_program,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use crate::claims;
+use crate::dispute;
+use crate::error::DaoError;
+use crate::finance;
+use crate::{is_admin, Claim, ClaimStatus, Dispute, DisputeStatus, HealthInsuranceDAO, Member, RiskProfile, Role, ServiceType, Treasury};
+
+// Spare bytes left in a migrated DAO account so members can still join and claims be tracked afterwards
+const MIGRATED_HEADROOM: usize = 1_024;
+
+// Standalone program that wrote a legacy account. Those programs stored no version, so the migration probes the
+// account's bytes against every layout and only proceeds when exactly one of them fits.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq)]
+pub enum LegacyLayout {
+    Claims,   // Claims_Handling.rs
+    Finance,  // Financial_Risk_Management.rs
+    Dispute,  // Dispute_Resolution.rs
+    Security, // Security_Privacy.rs
+}
+
+const LEGACY_LAYOUTS: [LegacyLayout; 4] = [LegacyLayout::Claims, LegacyLayout::Finance, LegacyLayout::Dispute, LegacyLayout::Security];
+
+// Unified state decoded from a legacy account, with the claims still to be moved into their own accounts
+type Decoded = (HealthInsuranceDAO, Vec<Claim>);
+
+// Member record the claims and finance programs wrote
+#[derive(BorshSerialize, BorshDeserialize)]
+struct LegacyMember {
+    member_address: Pubkey,
+    joined_timestamp: i64,
+}
+
+// Claim written by the claims program, with a free-form service label
+#[derive(BorshSerialize, BorshDeserialize)]
+struct ClaimsClaim {
+    claim_id: u64,
+    member: Pubkey,
+    amount: u64,
+    service_date: i64,
+    service_type: String,
+    provider: Pubkey,
+    status: ClaimStatus,
+    verifiers: Vec<Pubkey>,
+}
+
+// Account written by the claims program
+#[derive(BorshSerialize, BorshDeserialize)]
+struct ClaimsLayout {
+    admin: Pubkey,
+    members: Vec<LegacyMember>,
+    claims: Vec<ClaimsClaim>,
+    treasury: Pubkey,
+}
+
+// Claim written by the finance program, which tracked only verification
+#[derive(BorshSerialize, BorshDeserialize)]
+struct FinanceClaim {
+    claim_id: u64,
+    member: Pubkey,
+    amount: u64,
+    verified: bool,
+}
+
+// Treasury written by the finance program, with the reserve ratio as an f32
+#[derive(BorshSerialize, BorshDeserialize)]
+struct FinanceTreasury {
+    balance: u64,
+    reserve_ratio: f32,
+}
+
+// Risk profile written by the finance program, shared by every member whose key-derived score matched
+#[derive(BorshSerialize, BorshDeserialize)]
+struct FinanceRiskProfile {
+    risk_score: u8,
+    coverage_limit: u64,
+}
+
+// Account written by the finance program
+#[derive(BorshSerialize, BorshDeserialize)]
+struct FinanceLayout {
+    admin: Pubkey,
+    members: Vec<LegacyMember>,
+    claims: Vec<FinanceClaim>,
+    treasury: FinanceTreasury,
+    risk_profiles: Vec<FinanceRiskProfile>,
+}
+
+// Dispute written by the dispute program, before deposits and juries
+#[derive(BorshSerialize, BorshDeserialize)]
+struct DisputeRecord {
+    dispute_id: u64,
+    claim_id: Option<u64>,
+    initiator: Pubkey,
+    respondent: Pubkey,
+    description: String,
+    status: DisputeStatus,
+    votes: Vec<(Pubkey, bool)>,
+}
+
+// Account written by the dispute program, which kept no admin or members
+#[derive(BorshSerialize, BorshDeserialize)]
+struct DisputeLayout {
+    disputes: Vec<DisputeRecord>,
+}
+
+// Member record written by the security program
+#[derive(BorshSerialize, BorshDeserialize)]
+struct SecurityMember {
+    member_address: Pubkey,
+    joined_timestamp: i64,
+    role: Role,
+    encrypted_data_hash: [u8; 32],
+}
+
+// Claim written by the security program
+#[derive(BorshSerialize, BorshDeserialize)]
+struct SecurityClaim {
+    claim_id: u64,
+    member: Pubkey,
+    amount: u64,
+    zkp_proof: Vec<u8>,
+}
+
+// Account written by the security program
+#[derive(BorshSerialize, BorshDeserialize)]
+struct SecurityLayout {
+    admin: Pubkey,
+    members: Vec<SecurityMember>,
+    claims: Vec<SecurityClaim>,
+    multi_sig_signers: Vec<Pubkey>,
+}
+
+impl From<LegacyMember> for Member {
+    fn from(member: LegacyMember) -> Self {
+        Member { member_address: member.member_address, joined_timestamp: member.joined_timestamp, reputation: dispute::STARTING_REPUTATION, ..Default::default() }
+    }
+}
+
+// Map a free-form service label onto its typed service category; unrecognized labels are left unclassified
+fn service_type(label: &str) -> ServiceType {
+    match label.trim().to_ascii_lowercase().as_str() {
+        "emergency" => ServiceType::Emergency,
+        "surgery" => ServiceType::Surgery,
+        "consultation" => ServiceType::Consultation,
+        "pharmacy" | "prescription" => ServiceType::Pharmacy,
+        "dental" => ServiceType::Dental,
+        _ => ServiceType::default(),
+    }
+}
+
+// Decode `data` in the named layout and carry its state into the unified layout, returning the claims to move into
+// their own accounts. The standalone programs read their accounts with `try_from_slice`, so a legacy account holds
+// exactly its state. Fields the legacy program didn't have keep their defaults, and the claim totals are left for the
+// caller to tally as each claim account is created.
+fn decode(layout: LegacyLayout, data: &[u8]) -> Result<Decoded, ProgramError> {
+    match layout {
+        LegacyLayout::Claims => {
+            let legacy = ClaimsLayout::try_from_slice(data)?;
+            let claims = legacy.claims.into_iter().map(|c| Claim {
+                claim_id: c.claim_id,
+                member: c.member,
+                amount: c.amount,
+                service_date: c.service_date,
+                service_type: service_type(&c.service_type),
+                provider: c.provider,
+                status: c.status,
+                verifiers: c.verifiers,
+                ..Default::default()
+            });
+            let dao = HealthInsuranceDAO {
+                admin: legacy.admin,
+                members: legacy.members.into_iter().map(Member::from).collect(),
+                treasury_account: legacy.treasury,
+                ..Default::default()
+            };
+            Ok((dao, claims.collect()))
+        }
+        LegacyLayout::Finance => {
+            let legacy = FinanceLayout::try_from_slice(data)?;
+            if !(0.0..=1.0).contains(&legacy.treasury.reserve_ratio) {
+                return Err(ProgramError::InvalidAccountData); // Not a reserve ratio the legacy program could have stored
+            }
+            let members: Vec<Member> = legacy.members.into_iter().map(Member::from).collect();
+            // Profiles are now looked up by member, so each member gets a copy of the profile their score matched
+            let risk_profiles = members
+                .iter()
+                .filter_map(|m| {
+                    let score = finance::calculate_risk_score(&m.member_address);
+                    legacy.risk_profiles.iter().find(|rp| rp.risk_score == score).map(|rp| RiskProfile {
+                        member: m.member_address,
+                        risk_score: rp.risk_score,
+                        coverage_limit: rp.coverage_limit,
+                        ..Default::default()
+                    })
+                })
+                .collect();
+            let claims = legacy.claims.into_iter().map(|c| Claim {
+                claim_id: c.claim_id,
+                member: c.member,
+                amount: c.amount,
+                status: if c.verified { ClaimStatus::Verified } else { ClaimStatus::Pending },
+                ..Default::default()
+            });
+            // The legacy program paid claims against its reserve ratio without a separate initialization step
+            let treasury = Treasury {
+                balance: legacy.treasury.balance,
+                reserve_ratio_bps: (legacy.treasury.reserve_ratio * 10_000.0).round() as u16,
+                initialized: true,
+                ..Default::default()
+            };
+            let dao = HealthInsuranceDAO { admin: legacy.admin, members, treasury, risk_profiles, ..Default::default() };
+            Ok((dao, claims.collect()))
+        }
+        LegacyLayout::Dispute => {
+            let legacy = DisputeLayout::try_from_slice(data)?;
+            let disputes = legacy.disputes.into_iter().map(|d| Dispute {
+                dispute_id: d.dispute_id,
+                claim_id: d.claim_id,
+                initiator: d.initiator,
+                respondent: d.respondent,
+                description: d.description,
+                status: d.status,
+                votes: d.votes,
+                ..Default::default()
+            });
+            let disputes: Vec<Dispute> = disputes.collect();
+            // Dispute ids were assigned by position, so numbering resumes after the highest one
+            let next_dispute_id = disputes.iter().map(|d| d.dispute_id.saturating_add(1)).max().unwrap_or(0);
+            Ok((HealthInsuranceDAO { disputes, next_dispute_id, ..Default::default() }, Vec::new()))
+        }
+        LegacyLayout::Security => {
+            let legacy = SecurityLayout::try_from_slice(data)?;
+            let members = legacy.members.into_iter().map(|m| Member {
+                member_address: m.member_address,
+                joined_timestamp: m.joined_timestamp,
+                role: m.role,
+                encrypted_data_hash: m.encrypted_data_hash,
+                reputation: dispute::STARTING_REPUTATION,
+                ..Default::default()
+            });
+            let claims = legacy.claims.into_iter().map(|c| Claim {
+                claim_id: c.claim_id,
+                member: c.member,
+                amount: c.amount,
+                zkp_proof: c.zkp_proof,
+                ..Default::default()
+            });
+            let dao = HealthInsuranceDAO {
+                admin: legacy.admin,
+                members: members.collect(),
+                multi_sig_signers: legacy.multi_sig_signers,
+                ..Default::default()
+            };
+            Ok((dao, claims.collect()))
+        }
+    }
+}
+
+// Every legacy layout `data` decodes as, with the state decoded from it
+fn probe(data: &[u8]) -> Vec<(LegacyLayout, Decoded)> {
+    LEGACY_LAYOUTS.iter().filter_map(|&layout| decode(layout, data).ok().map(|decoded| (layout, decoded))).collect()
+}
+
+// Rewrite a DAO account written by one of the standalone programs into the unified layout. The account has to decode
+// as exactly one legacy layout; `expected`, when given, must be that layout. Accounts: the DAO account, the admin
+// (signer, paying for the claim accounts and any growth of the DAO account), the system program, then the PDA of each
+// legacy claim in the order the legacy account stored them.
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], expected: Option<LegacyLayout>) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let account = next_account_info(accounts_iter)?;
+    let admin = next_account_info(accounts_iter)?;
+    let system_program_account = next_account_info(accounts_iter)?;
+
+    // Verify that this program owns the account we're about to modify
+    if account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if *system_program_account.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let mut matches = probe(&account.data.borrow());
+    let (layout, (mut dao_data, legacy_claims)) = match matches.len() {
+        0 if HealthInsuranceDAO::load(account).is_ok() => {
+            msg!("DAO account already uses the unified layout");
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        0 => {
+            msg!("DAO account doesn't decode as any legacy layout");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        1 => matches.remove(0),
+        _ => {
+            // Migrating under the wrong layout would misread every field, so an ambiguous account is left alone
+            let layouts: Vec<LegacyLayout> = matches.iter().map(|(layout, _)| *layout).collect();
+            msg!("DAO account decodes as more than one legacy layout: {:?}", layouts);
+            return Err(ProgramError::InvalidAccountData);
+        }
+    };
+    if expected.is_some_and(|expected| expected != layout) {
+        msg!("DAO account was written by the {:?} program, not the {:?} program", layout, expected);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if layout == LegacyLayout::Dispute {
+        // The dispute program recorded no admin, so the account's own key must approve whoever becomes admin
+        if !account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        dao_data.admin = *admin.key;
+    } else if !is_admin(&dao_data, admin.key) {
+        return Err(DaoError::Unauthorized.into()); // Only the admin the legacy program recorded can migrate its account
+    }
+
+    // Each claim moves into its own PDA, keeping its id; the DAO keeps only the totals over them
+    for claim in &legacy_claims {
+        let claim_account = next_account_info(accounts_iter)?;
+        claims::create_claim(program_id, account.key, admin, claim_account, system_program_account, claim)?;
+        claims::track_claim(&mut dao_data, claim);
+        dao_data.claims_count = dao_data.claims_count.max(claim.claim_id.saturating_add(1));
+    }
+
+    // The unified state is larger than any single program's, so the account grows with room to spare
+    let encoded = dao_data.try_to_vec()?;
+    let space = encoded.len() + MIGRATED_HEADROOM;
+    if account.data_len() < space {
+        let rent = Rent::get()?.minimum_balance(space);
+        if account.lamports() < rent {
+            invoke(
+                &system_instruction::transfer(admin.key, account.key, rent - account.lamports()),
+                &[admin.clone(), account.clone(), system_program_account.clone()],
+            )?;
+        }
+        account.realloc(space, true)?;
+    }
+    let mut data = account.data.borrow_mut();
+    data.fill(0);
+    data[..encoded.len()].copy_from_slice(&encoded);
+
+    msg!(
+        "Migrated the {:?} layout: {} members, {} claims, {} disputes",
+        layout,
+        dao_data.members.len(),
+        legacy_claims.len(),
+        dao_data.disputes.len()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claims::claim_address;
+    use crate::instruction::DaoInstruction;
+    use crate::process_instruction;
+    use solana_program::instruction::{AccountMeta, Instruction};
+    use solana_program_test::*;
+    use solana_sdk::{
+        account::Account,
+        instruction::InstructionError,
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    };
+
+    // Migrate `legacy`, expected to be written by the `layout` program when one is named, with the claim PDAs for
+    // `claim_ids`. The DAO account signs too when `dao_signs` is set. Returns the unified state and the migrated claims,
+    // or the error the migration failed with.
+    async fn migrate(
+        legacy: Vec<u8>,
+        layout: Option<LegacyLayout>,
+        admin: &Keypair,
+        dao_account: &Keypair,
+        dao_signs: bool,
+        claim_ids: &[u64],
+    ) -> Result<(HealthInsuranceDAO, Vec<Claim>), TransactionError> {
+        let program_id = Pubkey::new_unique();
+        let dao_key = dao_account.pubkey();
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        program_test.add_account(
+            dao_key,
+            Account { lamports: Rent::default().minimum_balance(legacy.len()), data: legacy, owner: program_id, executable: false, rent_epoch: 0 },
+        );
+        program_test.add_account(
+            admin.pubkey(),
+            Account { lamports: 1_000_000_000, data: Vec::new(), owner: system_program::id(), executable: false, rent_epoch: 0 },
+        );
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let mut accounts = vec![
+            AccountMeta::new(dao_key, dao_signs),                   // DAO account
+            AccountMeta::new(admin.pubkey(), true),                 // Admin, paying for the claim accounts
+            AccountMeta::new_readonly(system_program::id(), false), // System program
+        ];
+        accounts.extend(claim_ids.iter().map(|id| AccountMeta::new(claim_address(&program_id, &dao_key, *id).0, false)));
+        let data = DaoInstruction::MigrateLegacy { layout }.try_to_vec().unwrap();
+        let instruction = Instruction { program_id, accounts, data };
+        let mut signers = vec![&payer, admin];
+        if dao_signs {
+            signers.push(dao_account);
+        }
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &signers, recent_blockhash);
+        banks_client.process_transaction(transaction).await.map_err(|e| e.unwrap())?;
+
+        let account = banks_client.get_account(dao_key).await.unwrap().unwrap();
+        let dao = HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap();
+        // The account grew to fit the unified state, with room left for new members
+        assert!(account.data.len() >= dao.try_to_vec().unwrap().len() + MIGRATED_HEADROOM);
+        let mut claims = Vec::new();
+        for id in claim_ids {
+            let account = banks_client.get_account(claim_address(&program_id, &dao_key, *id).0).await.unwrap().unwrap();
+            claims.push(Claim::deserialize(&mut &account.data[..]).unwrap());
+        }
+        Ok((dao, claims))
+    }
+
+    #[tokio::test]
+    async fn test_claims_layout_migrated() {
+        let admin = Keypair::new();
+        let member = Pubkey::new_unique();
+        let provider = Pubkey::new_unique();
+        let claim = |claim_id, amount, status| ClaimsClaim {
+            claim_id,
+            member,
+            amount,
+            service_date: 1_000,
+            service_type: "Surgery".to_string(),
+            provider,
+            status,
+            verifiers: vec![admin.pubkey()],
+        };
+        let legacy = ClaimsLayout {
+            admin: admin.pubkey(),
+            members: vec![LegacyMember { member_address: member, joined_timestamp: 100 }],
+            claims: vec![claim(0, 40_000, ClaimStatus::Verified), claim(1, 10_000, ClaimStatus::Rejected)],
+            treasury: Pubkey::new_unique(),
+        };
+
+        let (dao, claims) = migrate(legacy.try_to_vec().unwrap(), Some(LegacyLayout::Claims), &admin, &Keypair::new(), false, &[0, 1]).await.unwrap();
+        assert_eq!(dao.admin, admin.pubkey());
+        assert_eq!(dao.treasury_account, legacy.treasury);
+        assert_eq!(dao.members.len(), 1);
+        assert_eq!(dao.members[0].joined_timestamp, 100);
+        assert_eq!(dao.members[0].reputation, dispute::STARTING_REPUTATION); // Migrated members can sit on juries
+
+        // Claims keep their ids in their own accounts, and numbering resumes after them
+        assert_eq!(claims[0].service_type, ServiceType::Surgery);
+        assert_eq!(claims[0].provider, provider);
+        assert_eq!(claims[0].status, ClaimStatus::Verified);
+        assert_eq!(claims[0].verifiers, vec![admin.pubkey()]);
+        assert_eq!(claims[1].status, ClaimStatus::Rejected);
+        assert_eq!(dao.claims_count, 2);
+        assert_eq!(dao.outstanding_claims, 40_000);
+        assert_eq!(dao.members[0].claim_history.submitted, 2);
+    }
+
+    #[tokio::test]
+    async fn test_finance_layout_migrated() {
+        let admin = Keypair::new();
+        let member = Pubkey::new_unique();
+        let legacy = FinanceLayout {
+            admin: admin.pubkey(),
+            members: vec![LegacyMember { member_address: member, joined_timestamp: 100 }],
+            claims: vec![
+                FinanceClaim { claim_id: 0, member, amount: 20_000, verified: true },
+                FinanceClaim { claim_id: 1, member, amount: 30_000, verified: false },
+            ],
+            treasury: FinanceTreasury { balance: 10_000_000, reserve_ratio: 0.25 },
+            risk_profiles: vec![FinanceRiskProfile { risk_score: finance::calculate_risk_score(&member), coverage_limit: 1_000_000 }],
+        };
+
+        let (dao, claims) = migrate(legacy.try_to_vec().unwrap(), Some(LegacyLayout::Finance), &admin, &Keypair::new(), false, &[0, 1]).await.unwrap();
+        assert_eq!(dao.treasury.balance, 10_000_000);
+        assert_eq!(dao.treasury.reserve_ratio_bps, 2_500);
+        assert!(dao.treasury.initialized);
+
+        // The shared profile is now keyed by the member it applied to
+        assert_eq!(dao.risk_profiles.len(), 1);
+        assert_eq!(dao.risk_profiles[0].member, member);
+        assert_eq!(dao.risk_profiles[0].coverage_limit, 1_000_000);
+
+        assert_eq!(claims[0].status, ClaimStatus::Verified);
+        assert_eq!(claims[1].status, ClaimStatus::Pending);
+        assert_eq!(dao.open_claim_exposure, 50_000);
+    }
+
+    #[tokio::test]
+    async fn test_dispute_layout_migrated() {
+        let admin = Keypair::new();
+        let dao_account = Keypair::new();
+        let initiator = Pubkey::new_unique();
+        let legacy = DisputeLayout {
+            disputes: vec![DisputeRecord {
+                dispute_id: 0,
+                claim_id: Some(3),
+                initiator,
+                respondent: Pubkey::new_unique(),
+                description: "Denied without review".to_string(),
+                status: DisputeStatus::Open,
+                votes: vec![(initiator, true)],
+            }],
+        };
+
+        // The dispute program kept no admin, so the DAO account itself must approve the migration
+        let result = migrate(legacy.try_to_vec().unwrap(), Some(LegacyLayout::Dispute), &admin, &dao_account, false, &[]).await;
+        assert_eq!(result.unwrap_err(), TransactionError::InstructionError(0, InstructionError::MissingRequiredSignature));
+
+        let (dao, _) = migrate(legacy.try_to_vec().unwrap(), Some(LegacyLayout::Dispute), &admin, &dao_account, true, &[]).await.unwrap();
+        assert_eq!(dao.admin, admin.pubkey());
+        assert_eq!(dao.disputes.len(), 1);
+        assert_eq!(dao.disputes[0].claim_id, Some(3));
+        assert_eq!(dao.disputes[0].description, "Denied without review");
+        assert_eq!(dao.disputes[0].votes, vec![(initiator, true)]);
+        assert_eq!(dao.next_dispute_id, 1);
+    }
+
+    #[tokio::test]
+    async fn test_security_layout_migrated() {
+        let admin = Keypair::new();
+        let member = Pubkey::new_unique();
+        let legacy = SecurityLayout {
+            admin: admin.pubkey(),
+            members: vec![SecurityMember { member_address: member, joined_timestamp: 100, role: Role::Verifier, encrypted_data_hash: [7u8; 32] }],
+            claims: vec![SecurityClaim { claim_id: 0, member, amount: 1_000_000, zkp_proof: vec![1, 2, 3] }],
+            multi_sig_signers: vec![admin.pubkey()],
+        };
+
+        // Without a layout named, the account's bytes identify the program that wrote it
+        let (dao, claims) = migrate(legacy.try_to_vec().unwrap(), None, &admin, &Keypair::new(), false, &[0]).await.unwrap();
+        assert_eq!(dao.members[0].role, Role::Verifier);
+        assert_eq!(dao.members[0].encrypted_data_hash, [7u8; 32]);
+        assert_eq!(dao.members[0].reputation, dispute::STARTING_REPUTATION);
+        assert_eq!(dao.multi_sig_signers, vec![admin.pubkey()]);
+        assert_eq!(claims[0].zkp_proof, vec![1, 2, 3]);
+        assert_eq!(claims[0].status, ClaimStatus::Pending);
+        assert_eq!(dao.open_claim_exposure, 1_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_migration_checks_named_layout_and_admin() {
+        let admin = Keypair::new();
+        let legacy = SecurityLayout { admin: admin.pubkey(), members: Vec::new(), claims: Vec::new(), multi_sig_signers: Vec::new() };
+        let data = legacy.try_to_vec().unwrap();
+
+        // Named as the wrong program, the account isn't migrated
+        let result = migrate(data.clone(), Some(LegacyLayout::Finance), &admin, &Keypair::new(), false, &[]).await;
+        assert_eq!(result.unwrap_err(), TransactionError::InstructionError(0, InstructionError::InvalidArgument));
+
+        // Bytes that fit no legacy layout aren't either
+        let result = migrate(vec![1, 2, 3], None, &admin, &Keypair::new(), false, &[]).await;
+        assert_eq!(result.unwrap_err(), TransactionError::InstructionError(0, InstructionError::InvalidAccountData));
+
+        let result = migrate(data, Some(LegacyLayout::Security), &Keypair::new(), &Keypair::new(), false, &[]).await;
+        assert_eq!(result.unwrap_err(), TransactionError::InstructionError(0, InstructionError::Custom(DaoError::Unauthorized as u32)));
+
+        // An account already in the unified layout is left alone
+        let mut migrated = HealthInsuranceDAO { admin: admin.pubkey(), ..Default::default() }.try_to_vec().unwrap();
+        migrated.resize(migrated.len() + MIGRATED_HEADROOM, 0);
+        let result = migrate(migrated, Some(LegacyLayout::Security), &admin, &Keypair::new(), false, &[]).await;
+        assert_eq!(result.unwrap_err(), TransactionError::InstructionError(0, InstructionError::AccountAlreadyInitialized));
+    }
+
+    #[tokio::test]
+    async fn test_ambiguous_legacy_account_not_migrated() {
+        let admin = Keypair::new();
+        // A claims-program account whose claim bytes also read as a finance-program claim, treasury and eight risk
+        // profiles: the service date's low byte is the finance claim's verified flag, and the label supplies a 0.5
+        // reserve ratio and the profile count
+        let legacy = ClaimsLayout {
+            admin: admin.pubkey(),
+            members: Vec::new(),
+            claims: vec![ClaimsClaim {
+                claim_id: 0,
+                member: Pubkey::new_unique(),
+                amount: 10_000,
+                service_date: 0,
+                service_type: "?\u{8}\0\0\0abc".to_string(),
+                provider: Pubkey::new_unique(),
+                status: ClaimStatus::Pending,
+                verifiers: Vec::new(),
+            }],
+            treasury: Pubkey::new_unique(),
+        };
+        let data = legacy.try_to_vec().unwrap();
+        assert_eq!(probe(&data).iter().map(|(layout, _)| *layout).collect::<Vec<_>>(), vec![LegacyLayout::Claims, LegacyLayout::Finance]);
+
+        // Naming either program doesn't settle it; the account can't be told apart, so it isn't migrated
+        for layout in [None, Some(LegacyLayout::Claims)] {
+            let result = migrate(data.clone(), layout, &admin, &Keypair::new(), false, &[0]).await;
+            assert_eq!(result.unwrap_err(), TransactionError::InstructionError(0, InstructionError::InvalidAccountData));
+        }
+    }
+}
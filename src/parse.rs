@@ -0,0 +1,41 @@
+use solana_program::program_error::ProgramError;
+
+// Bounds-checked little-endian readers for raw byte layouts. Each returns `InvalidInstructionData`
+// instead of panicking when `data` is too short to hold the value at `offset`.
+
+pub fn read_u64(data: &[u8], offset: usize) -> Result<u64, ProgramError> {
+    Ok(u64::from_le_bytes(read_array(data, offset)?))
+}
+
+pub fn read_i64(data: &[u8], offset: usize) -> Result<i64, ProgramError> {
+    Ok(i64::from_le_bytes(read_array(data, offset)?))
+}
+
+fn read_array<const N: usize>(data: &[u8], offset: usize) -> Result<[u8; N], ProgramError> {
+    let end = offset.checked_add(N).ok_or(ProgramError::InvalidInstructionData)?;
+    let bytes = data.get(offset..end).ok_or(ProgramError::InvalidInstructionData)?;
+    Ok(bytes.try_into().unwrap()) // The slice is exactly N bytes long
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_readers_decode_in_bounds_values() {
+        let mut data = vec![7u8];
+        data.extend_from_slice(&42u64.to_le_bytes());
+        data.extend_from_slice(&(-5i64).to_le_bytes());
+
+        assert_eq!(read_u64(&data, 1), Ok(42));
+        assert_eq!(read_i64(&data, 9), Ok(-5));
+    }
+
+    #[test]
+    fn test_readers_reject_truncated_data() {
+        let data = [1u8; 8];
+        assert_eq!(read_u64(&data, 1), Err(ProgramError::InvalidInstructionData));
+        assert_eq!(read_i64(&data[..7], 0), Err(ProgramError::InvalidInstructionData));
+        assert_eq!(read_u64(&data, usize::MAX), Err(ProgramError::InvalidInstructionData));
+    }
+}
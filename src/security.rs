@@ -0,0 +1,1231 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    hash::hashv,
+    instruction::{AccountMeta, Instruction},
+    msg,
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::{clock::Clock, Sysvar},
+};
+
+use crate::{require_signed_role, Claim, ClaimHistory, ClaimStatus, HealthInsuranceDAO, Member, MemberTier, MultiSigProposal, Role, ServiceType};
+use crate::claims;
+use crate::dispute;
+use crate::error::DaoError;
+use crate::groth16;
+use crate::instruction::SecurityInstruction;
+
+// Seed for the PDA that signs instructions executed through the multi-sig, derived as ["multisig", dao_key]
+const MULTISIG_AUTHORITY_SEED: &[u8] = b"multisig";
+
+// Seed for the PDA holding the claim circuit's verifying key, derived as ["verifying_key", dao_key]
+const VERIFYING_KEY_SEED: &[u8] = b"verifying_key";
+
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction: SecurityInstruction,
+) -> ProgramResult {
+    // Fail early with a clear message when the client supplies too few accounts for the instruction
+    let expected = expected_account_count(&instruction);
+    if accounts.len() < expected {
+        msg!("Instruction {:?} expects at least {} accounts but received {}", instruction, expected, accounts.len());
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let accounts_iter = &mut accounts.iter();
+    let account = next_account_info(accounts_iter)?;
+
+    if account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let mut dao_data = HealthInsuranceDAO::load(account)?;
+
+    // Only unpausing and the multi-sig flow that can trigger it run while the DAO is paused
+    if !allowed_while_paused(&instruction) {
+        dao_data.ensure_active()?;
+    }
+
+    match instruction {
+        SecurityInstruction::JoinDao { encrypted_data_hash } => {
+            // Join DAO - Enhanced for security 
+            let new_member = next_account_info(accounts_iter)?;
+            if !new_member.is_signer {
+                return Err(ProgramError::MissingRequiredSignature); // Nobody can be enrolled without their consent
+            }
+
+            // Check if the member is not already in the DAO
+            if dao_data.members.iter().any(|m| m.member_address == *new_member.key) {
+                return Err(DaoError::AlreadyMember.into());
+            }
+
+            dao_data.members.push(Member {
+                member_address: *new_member.key,
+                joined_timestamp: Clock::get()?.unix_timestamp,
+                role: Role::Member, // Elevated roles are granted by the admin, not claimed on joining
+                encrypted_data_hash,
+                legal_hold: false,
+                affiliations: Vec::new(),
+                last_verification_at: 0,
+                stake: 0,
+                fraud_strikes: 0,
+                suspended: false,
+                tier: MemberTier::Bronze,
+                pending_tier: None,
+                premiums_paid: 0,
+                last_activity_at: 0,
+                dormant: false,
+                last_heartbeat_at: 0,
+                heartbeat_streak: 0,
+                reputation: dispute::STARTING_REPUTATION,
+                votes_cast: 0,
+                disputes_eligible: 0,
+                nonce: 0,
+                last_premium_ts: 0,
+                last_premium_amount: 0,
+                periods_claim_free: 0,
+                last_claim_paid_at: 0,
+                claim_history: ClaimHistory::default(),
+            });
+
+            msg!("New member {} joined the DAO", new_member.key);
+        }
+        SecurityInstruction::SubmitClaim { amount, provider, service_date, zkp_proof, nonce } => {
+            // Submit Claim - Enhanced with a Groth16 proof for privacy
+            let member = next_account_info(accounts_iter)?;
+            let claim_account = next_account_info(accounts_iter)?; // PDA for the new claim, funded by the member
+            let system_program_account = next_account_info(accounts_iter)?; // System program for the account creation
+            let key_account = next_account_info(accounts_iter)?; // PDA holding the claim circuit's verifying key
+            if !member.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            claims::check_claimant(&dao_data, member.key, Clock::get()?.unix_timestamp)?;
+            dao_data.consume_nonce(member.key, nonce)?;
+
+            if *key_account.key != verifying_key_address(program_id, account.key).0 {
+                return Err(ProgramError::InvalidSeeds); // Proofs are only checked against this DAO's stored key
+            }
+            if key_account.owner != program_id {
+                msg!("No verifying key has been set for claim proofs");
+                return Err(DaoError::VerifyingKeyNotSet.into());
+            }
+
+            // The proof must be for this claim, and it and its public input must verify against the claim circuit's key
+            let statement = claim_statement(member.key, amount, &provider, service_date);
+            if groth16::public_inputs(&zkp_proof) != Some(&statement[..]) || !groth16::verify_proof(&key_account.data.borrow(), &zkp_proof) {
+                msg!("Claim proof failed verification");
+                return Err(DaoError::InvalidProof.into());
+            }
+
+            let now = Clock::get()?.unix_timestamp;
+            claims::admit_claim(&mut dao_data, &[*member.key], service_date, now)?;
+            let claim = Claim {
+                claim_id: claims::assign_claim_id(&mut dao_data.claims_count)?,
+                member: *member.key,
+                amount,
+                service_date,
+                service_type: ServiceType::default(),
+                provider,
+                status: ClaimStatus::Pending,
+                verifiers: Vec::new(),
+                preauth_id: None,
+                assigned_verifiers: Vec::new(),
+                condition: None,
+                condition_met: false,
+                external_ref: [0u8; 16],
+                submitted_at: now,
+                provider_attested: false,
+                supersedes: None,
+                holdback: 0,
+                holdback_release_at: 0,
+                anomaly_score: 0,
+                rejections: Vec::new(),
+                ack_requested_at: 0,
+                acknowledged: false,
+                shares: Vec::new(),
+                zkp_proof,
+                rejection_reason: None,
+                appealed: false,
+                paid_amount: 0,
+                resubmitted: false,
+            };
+            claims::create_claim(program_id, account.key, member, claim_account, system_program_account, &claim)?;
+            claims::track_claim(&mut dao_data, &claim);
+            msg!("Claim {} submitted for {} lamports with ZKP", claim.claim_id, amount);
+        }
+        SecurityInstruction::ExecuteMultiSig { proposal_index } => {
+            // Execute Multi-Sig - Runs an approved proposal's instruction, signed by the multi-sig authority PDA
+            let authority = next_account_info(accounts_iter)?;
+            let (authority_key, bump) = multisig_authority(program_id, account.key);
+            if *authority.key != authority_key {
+                return Err(ProgramError::InvalidSeeds); // The authority must be this DAO's PDA
+            }
+
+            let proposal = dao_data.multi_sig_proposals.get_mut(proposal_index as usize).ok_or(DaoError::ProposalNotFound)?;
+            if proposal.executed {
+                msg!("Multi-sig proposal {} has already been executed", proposal_index);
+                return Err(DaoError::ProposalAlreadyExecuted.into());
+            }
+            if dao_data.paused && proposal.program_id != *program_id {
+                // Calls into other programs bypass the DAO's own pause check, so they wait until it's unpaused
+                return Err(DaoError::ProgramPaused.into());
+            }
+
+            // Only approvals from distinct keys still registered as signers count toward the threshold
+            let approvals = counted_approvals(&proposal.approvals, &dao_data.multi_sig_signers);
+            if approvals < proposal.threshold as usize {
+                msg!("Multi-sig proposal {} has {} of {} required approvals", proposal_index, approvals, proposal.threshold);
+                return Err(DaoError::InsufficientApprovals.into());
+            }
+            proposal.executed = true;
+            let inner = Instruction {
+                program_id: proposal.program_id,
+                accounts: proposal.accounts.iter().map(|a| AccountMeta { pubkey: a.pubkey, is_signer: a.is_signer, is_writable: a.is_writable }).collect(),
+                data: proposal.data.clone(),
+            };
+
+            // Persist the executed flag before the call, and reload afterwards in case the inner instruction changed the DAO
+            dao_data.save(account)?;
+            invoke_signed(&inner, accounts, &[&[MULTISIG_AUTHORITY_SEED, account.key.as_ref(), &[bump]]])?;
+            dao_data = HealthInsuranceDAO::load(account)?;
+            msg!("Multi-sig proposal {} executed with {} approvals", proposal_index, approvals);
+        }
+        SecurityInstruction::ForgetMember => {
+            // Forget Member - Member-initiated deletion of personal data, keeping anonymized claim records
+            let member = next_account_info(accounts_iter)?;
+            if !member.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+
+            let record = dao_data.members.iter_mut().find(|m| m.member_address == *member.key).ok_or(DaoError::NotAMember)?;
+            if record.legal_hold {
+                msg!("Member {} is under a legal hold and cannot be forgotten", member.key);
+                return Err(DaoError::LegalHold.into());
+            }
+
+            // Claims keep their amounts so financial totals are unchanged; only the member reference is replaced
+            let anonymized_id = anonymized_member_id(member.key, Clock::get()?.unix_timestamp);
+            record.member_address = anonymized_id;
+            record.encrypted_data_hash = [0u8; 32];
+            anonymize_dao_references(&mut dao_data, member.key, anonymized_id);
+            // The claim accounts naming the member follow; their running totals move with the record, so only the claims change
+            for claim_account in accounts_iter {
+                let mut claim = claims::load_listed_claim(program_id, account.key, claim_account)?;
+                if anonymize_claim_references(&mut claim, member.key, anonymized_id) {
+                    claims::save_claim(claim_account, &claim)?;
+                }
+            }
+            msg!("Member data deleted; records now reference {}", anonymized_id);
+        }
+        SecurityInstruction::SetLegalHold { legal_hold } => {
+            // Set Legal Hold - Admin only, places or lifts a retention hold on a member's records
+            let admin = next_account_info(accounts_iter)?;
+            let member = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+
+            let record = dao_data.members.iter_mut().find(|m| m.member_address == *member.key).ok_or(DaoError::NotAMember)?;
+            record.legal_hold = legal_hold;
+            msg!("Legal hold for member {} set to {}", member.key, legal_hold);
+        }
+        SecurityInstruction::ConfigureMultiSig { threshold, signers } => {
+            // Configure Multi-Sig - Admin only, replaces the signer set and approval threshold
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+
+            validate_multisig_threshold(threshold, &signers)?;
+            dao_data.multi_sig_signers = signers;
+            dao_data.multi_sig_threshold = threshold;
+            msg!("Multi-sig set to {} of {} signers", threshold, dao_data.multi_sig_signers.len());
+        }
+        SecurityInstruction::ProposeMultiSig { program_id: target_program, accounts: proposal_accounts, data } => {
+            // Propose Multi-Sig - A registered signer wraps an instruction for the signers to approve
+            let proposer = next_account_info(accounts_iter)?;
+            if !proposer.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if !dao_data.multi_sig_signers.contains(proposer.key) {
+                return Err(DaoError::Unauthorized.into()); // Only registered signers can propose
+            }
+
+            // Never accept proposals against an unconfigured or invalid threshold
+            validate_multisig_threshold(dao_data.multi_sig_threshold, &dao_data.multi_sig_signers)?;
+            dao_data.multi_sig_proposals.push(MultiSigProposal {
+                proposer: *proposer.key,
+                program_id: target_program,
+                accounts: proposal_accounts,
+                data,
+                threshold: dao_data.multi_sig_threshold,
+                approvals: Vec::new(),
+                executed: false,
+            });
+            msg!("Multi-sig proposal {} created by {}", dao_data.multi_sig_proposals.len() - 1, proposer.key);
+        }
+        SecurityInstruction::ApproveMultiSig { proposal_index } => {
+            // Approve Multi-Sig - Records a registered signer's approval of a pending proposal
+            let approver = next_account_info(accounts_iter)?;
+            if !approver.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if !dao_data.multi_sig_signers.contains(approver.key) {
+                return Err(DaoError::Unauthorized.into()); // Only registered signers can approve
+            }
+
+            let proposal = dao_data.multi_sig_proposals.get_mut(proposal_index as usize).ok_or(DaoError::ProposalNotFound)?;
+            if proposal.executed {
+                return Err(DaoError::ProposalAlreadyExecuted.into()); // Executed proposals can't collect further approvals
+            }
+            if proposal.approvals.contains(approver.key) {
+                return Err(DaoError::DuplicateVote.into()); // Each signer approves a proposal once
+            }
+            proposal.approvals.push(*approver.key);
+            msg!("Multi-sig proposal {} approved by {} ({} of {})", proposal_index, approver.key, proposal.approvals.len(), proposal.threshold);
+        }
+        SecurityInstruction::ProposeAdmin { nominee } => {
+            // Propose Admin - Admin only, nominates a successor who must accept before taking over
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+
+            dao_data.pending_admin = Some(nominee);
+            msg!("{} nominated as the next admin", nominee);
+        }
+        SecurityInstruction::AcceptAdmin => {
+            // Accept Admin - The pending nominee takes over as admin
+            let nominee = next_account_info(accounts_iter)?;
+            if !nominee.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if dao_data.pending_admin != Some(*nominee.key) {
+                msg!("{} is not the pending admin", nominee.key);
+                return Err(DaoError::Unauthorized.into());
+            }
+
+            dao_data.admin = *nominee.key;
+            dao_data.pending_admin = None;
+            msg!("Admin handed off to {}", nominee.key);
+        }
+        SecurityInstruction::Pause => {
+            // Pause - Admin or multi-sig, halts every state-changing instruction until unpaused
+            let authority = next_account_info(accounts_iter)?;
+            require_admin_or_multisig(program_id, account.key, authority, &dao_data)?;
+
+            dao_data.paused = true;
+            msg!("DAO paused by {}", authority.key);
+        }
+        SecurityInstruction::Unpause => {
+            // Unpause - Admin or multi-sig, resumes normal operation
+            let authority = next_account_info(accounts_iter)?;
+            require_admin_or_multisig(program_id, account.key, authority, &dao_data)?;
+
+            dao_data.paused = false;
+            msg!("DAO unpaused by {}", authority.key);
+        }
+        SecurityInstruction::SetMemberRole { member, role } => {
+            // Set Member Role - Admin only, promotes or demotes an existing member
+            let admin = next_account_info(accounts_iter)?;
+            require_signed_role(&dao_data, admin, Role::Admin)?; // Only admins can grant roles, including Admin itself
+
+            let index = dao_data.members.iter().position(|m| m.member_address == member).ok_or(DaoError::NotAMember)?;
+            if dao_data.members[index].role == Role::Admin && role != Role::Admin && remaining_admins(&dao_data, &member) == 0 {
+                msg!("{} is the last admin and cannot be demoted", member);
+                return Err(DaoError::LastAdmin.into());
+            }
+            dao_data.members[index].role = role;
+            msg!("Member {} now holds the {:?} role", member, role);
+        }
+        SecurityInstruction::SetVerifyingKey { verifying_key } => {
+            // Set Verifying Key - Admin only, stores the claim circuit's key in its own PDA, which can't be changed after
+            let admin = next_account_info(accounts_iter)?; // Admin, funding the key account
+            let key_account = next_account_info(accounts_iter)?; // PDA for the verifying key
+            let system_program_account = next_account_info(accounts_iter)?; // System program for the account creation
+            require_signed_role(&dao_data, admin, Role::Admin)?;
+
+            let (key_address, bump) = verifying_key_address(program_id, account.key);
+            if *key_account.key != key_address {
+                return Err(ProgramError::InvalidSeeds); // Key account must be this DAO's PDA
+            }
+            if key_account.owner == program_id {
+                msg!("The claim verifying key is already set and can't be replaced");
+                return Err(ProgramError::AccountAlreadyInitialized);
+            }
+            if verifying_key.len() != groth16::VERIFYING_KEY_LEN {
+                return Err(ProgramError::InvalidInstructionData); // Not a key for the claim circuit
+            }
+            claims::create_pda(program_id, admin, key_account, system_program_account, verifying_key.len(), &[VERIFYING_KEY_SEED, account.key.as_ref(), &[bump]])?;
+            key_account.data.borrow_mut().copy_from_slice(&verifying_key);
+            msg!("Claim verifying key set by {}", admin.key);
+        }
+    }
+
+    dao_data.save(account)
+}
+
+// Minimum number of accounts each instruction needs, including the DAO account
+fn expected_account_count(instruction: &SecurityInstruction) -> usize {
+    match instruction {
+        SecurityInstruction::JoinDao { .. } => 2, // DAO, new member
+        SecurityInstruction::SubmitClaim { .. } => 5, // DAO, member, claim account, system program, verifying key
+        SecurityInstruction::ExecuteMultiSig { .. } => 3, // DAO, multi-sig authority, target program, followed by the inner instruction's accounts
+        SecurityInstruction::ForgetMember => 2, // DAO, member, followed by the member's claim accounts
+        SecurityInstruction::SetLegalHold { .. } => 3, // DAO, admin, member
+        SecurityInstruction::ConfigureMultiSig { .. } => 2, // DAO, admin
+        SecurityInstruction::ProposeMultiSig { .. } => 2, // DAO, proposer
+        SecurityInstruction::ApproveMultiSig { .. } => 2, // DAO, approver
+        SecurityInstruction::ProposeAdmin { .. } => 2, // DAO, admin
+        SecurityInstruction::AcceptAdmin => 2, // DAO, nominee
+        SecurityInstruction::Pause => 2, // DAO, admin or multi-sig authority
+        SecurityInstruction::Unpause => 2, // DAO, admin or multi-sig authority
+        SecurityInstruction::SetMemberRole { .. } => 2, // DAO, admin
+        SecurityInstruction::SetVerifyingKey { .. } => 4, // DAO, admin, verifying key account, system program
+    }
+}
+
+// Unpausing, directly or through a multi-sig proposal, must stay possible while the DAO is paused
+fn allowed_while_paused(instruction: &SecurityInstruction) -> bool {
+    matches!(
+        instruction,
+        SecurityInstruction::Unpause
+            | SecurityInstruction::ProposeMultiSig { .. }
+            | SecurityInstruction::ApproveMultiSig { .. }
+            | SecurityInstruction::ExecuteMultiSig { .. }
+    )
+}
+
+// Admin-equivalent keys other than `excluded`: the admin key plus members granted the admin role
+fn remaining_admins(dao: &HealthInsuranceDAO, excluded: &Pubkey) -> usize {
+    let admin_key = usize::from(dao.admin != Pubkey::default() && dao.admin != *excluded);
+    admin_key + dao.members.iter().filter(|m| m.role == Role::Admin && m.member_address != *excluded && m.member_address != dao.admin).count()
+}
+
+// Address and bump of the PDA that signs instructions executed through the DAO's multi-sig
+pub(crate) fn multisig_authority(program_id: &Pubkey, dao_key: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MULTISIG_AUTHORITY_SEED, dao_key.as_ref()], program_id)
+}
+
+// Address and bump of the PDA holding the claim circuit's verifying key
+pub fn verifying_key_address(program_id: &Pubkey, dao_key: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VERIFYING_KEY_SEED, dao_key.as_ref()], program_id)
+}
+
+// The admin, or the multi-sig authority when a proposal is executed, may pause and unpause the DAO
+fn require_admin_or_multisig(program_id: &Pubkey, dao_key: &Pubkey, signer: &AccountInfo, dao: &HealthInsuranceDAO) -> ProgramResult {
+    if signer.is_signer && *signer.key == multisig_authority(program_id, dao_key).0 {
+        return Ok(());
+    }
+    require_signed_role(dao, signer, Role::Admin)
+}
+
+// The public input a claim proof must carry, binding it to the member, amount, provider and service date it's
+// submitted with. The top bits of the hash are cleared so it's always a reduced BN254 scalar.
+pub fn claim_statement(member: &Pubkey, amount: u64, provider: &Pubkey, service_date: i64) -> [u8; 32] {
+    let mut statement = hashv(&[member.as_ref(), &amount.to_le_bytes(), provider.as_ref(), &service_date.to_le_bytes()]).to_bytes();
+    statement[0] &= 0x1f;
+    statement
+}
+
+// Replacement id for a forgotten member, derived from their key and the deletion time so it can't be recomputed from state alone
+fn anonymized_member_id(member: &Pubkey, forgotten_at: i64) -> Pubkey {
+    Pubkey::new_from_array(hashv(&[b"forgotten", member.as_ref(), &forgotten_at.to_le_bytes()]).to_bytes())
+}
+
+// Replaces every reference to a forgotten member held in the DAO account with their anonymized id
+fn anonymize_dao_references(dao: &mut HealthInsuranceDAO, member: &Pubkey, anonymized_id: Pubkey) {
+    let replace = |key: &mut Pubkey| {
+        if key == member {
+            *key = anonymized_id;
+        }
+    };
+    dao.risk_profiles.iter_mut().for_each(|profile| replace(&mut profile.member));
+    dao.payment_plans.iter_mut().for_each(|plan| replace(&mut plan.member));
+    dao.preauths.iter_mut().for_each(|preauth| replace(&mut preauth.member));
+    dao.verifier_pairings.iter_mut().for_each(|pairing| replace(&mut pairing.verifier));
+    for dispute in &mut dao.disputes {
+        replace(&mut dispute.initiator);
+        replace(&mut dispute.respondent);
+        dispute.votes.iter_mut().for_each(|(voter, _)| replace(voter));
+        dispute.jurors.iter_mut().for_each(replace);
+    }
+}
+
+// Replaces a forgotten member's references in `claim`, as claimant, share participant or reviewer; returns whether any changed
+fn anonymize_claim_references(claim: &mut Claim, member: &Pubkey, anonymized_id: Pubkey) -> bool {
+    let mut changed = false;
+    let mut replace = |key: &mut Pubkey| {
+        if key == member {
+            *key = anonymized_id;
+            changed = true;
+        }
+    };
+    replace(&mut claim.member);
+    claim.shares.iter_mut().for_each(|share| replace(&mut share.member));
+    claim.verifiers.iter_mut().chain(&mut claim.assigned_verifiers).chain(&mut claim.rejections).for_each(&mut replace);
+    changed
+}
+
+// A multi-sig threshold must require at least one approval and no more than there are signers, each listed once
+fn multisig_threshold_in_range(threshold: u8, signers: &[Pubkey]) -> bool {
+    let distinct = signers.iter().enumerate().all(|(i, signer)| !signers[..i].contains(signer));
+    distinct && threshold != 0 && threshold as usize <= signers.len()
+}
+
+fn validate_multisig_threshold(threshold: u8, signers: &[Pubkey]) -> Result<(), ProgramError> {
+    if !multisig_threshold_in_range(threshold, signers) {
+        msg!("Multi-sig threshold {} must be between 1 and the count of {} distinct signers", threshold, signers.len());
+        return Err(DaoError::InvalidMultiSigThreshold.into());
+    }
+    Ok(())
+}
+
+// Approvals counted toward a proposal's threshold: one per registered signer, however often their key appears
+fn counted_approvals(approvals: &[Pubkey], signers: &[Pubkey]) -> usize {
+    approvals.iter().enumerate().filter(|&(i, approver)| signers.contains(approver) && !approvals[..i].contains(approver)).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claims::claim_address;
+    use crate::instruction::DaoInstruction;
+    use crate::testing::Ledger;
+    use crate::{process_instruction, ClaimShare, Dispute, PaymentPlan, PreAuth, ProposalAccount, RiskProfile, VerifierPairing};
+    use borsh::{BorshDeserialize, BorshSerialize};
+    use solana_program::{rent::Rent, system_instruction, system_program};
+    use solana_program_test::*;
+    use solana_sdk::{
+        account::Account,
+        instruction::InstructionError,
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    };
+
+    #[tokio::test]
+    async fn test_join_dao() {
+        let program_id = Pubkey::new_unique();
+        let member = Keypair::new();
+        let dao_account = Keypair::new();
+        let rent = Rent::default();
+        // Leave headroom so the new member fits in the account
+        let mut data = HealthInsuranceDAO::default().try_to_vec().unwrap();
+        data.resize(data.len() + 1_024, 0);
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        program_test.add_account(
+            dao_account.pubkey(),
+            Account {
+                lamports: rent.minimum_balance(data.len()),
+                data,
+                owner: program_id,
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let data = DaoInstruction::Security(SecurityInstruction::JoinDao { encrypted_data_hash: [0u8; 32] })
+            .try_to_vec()
+            .unwrap();
+
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_account.pubkey(), false),
+                AccountMeta::new(member.pubkey(), true),
+            ],
+            data,
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[&payer, &member],
+            recent_blockhash,
+        );
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        // Joining always grants the plain member role
+        let account = banks_client.get_account(dao_account.pubkey()).await.unwrap().unwrap();
+        let dao = HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap();
+        let joined = dao.members.iter().find(|m| m.member_address == member.pubkey()).unwrap();
+        assert_eq!(joined.role, Role::Member);
+    }
+
+    #[tokio::test]
+    async fn test_unsigned_join_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let dao = HealthInsuranceDAO { admin: Pubkey::new_unique(), ..Default::default() };
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        program_test.add_account(dao_key, dao_account(program_id, &dao));
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        // Enrolling someone else's key would start charging them premiums they never agreed to
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                       // DAO account
+                AccountMeta::new_readonly(Pubkey::new_unique(), false), // New member, not signing
+            ],
+            data: DaoInstruction::Security(SecurityInstruction::JoinDao { encrypted_data_hash: [0u8; 32] })
+                .try_to_vec()
+                .unwrap(),
+        };
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer], recent_blockhash);
+        assert_eq!(
+            banks_client.process_transaction(transaction).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::MissingRequiredSignature),
+        );
+    }
+
+    // Build a program-owned DAO account holding the given state
+    fn dao_account(program_id: Pubkey, dao: &HealthInsuranceDAO) -> Account {
+        let data = dao.try_to_vec().unwrap();
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: program_id,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    fn member_record(member_address: Pubkey, legal_hold: bool) -> Member {
+        Member {
+            member_address,
+            role: Role::Member,
+            encrypted_data_hash: [7u8; 32],
+            legal_hold,
+            ..Default::default()
+        }
+    }
+
+    // DAO where `member` has two claims and another member has one
+    fn dao_with_claims(member: Pubkey, legal_hold: bool) -> Ledger {
+        let other = Pubkey::new_unique();
+        let dao = HealthInsuranceDAO {
+            admin: Pubkey::new_unique(),
+            members: vec![member_record(member, legal_hold), member_record(other, false)],
+            ..Default::default()
+        };
+        Ledger::new(
+            dao,
+            vec![
+                Claim { claim_id: 0, member, amount: 1_000_000, zkp_proof: vec![1], ..Default::default() },
+                Claim { claim_id: 1, member: other, amount: 2_000_000, zkp_proof: vec![1], ..Default::default() },
+                Claim { claim_id: 2, member, amount: 3_000_000, zkp_proof: vec![1], ..Default::default() },
+            ],
+        )
+    }
+
+    // Submit a forget request signed by `member` over every claim account, returning the result and final DAO state
+    async fn forget_member(dao: &Ledger, member: &Keypair) -> (Result<(), BanksClientError>, Ledger) {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, 0);
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let mut accounts = vec![
+            AccountMeta::new(dao_key, false),                 // DAO account
+            AccountMeta::new_readonly(member.pubkey(), true), // Member requesting deletion
+        ];
+        for claim in &dao.claims {
+            accounts.push(AccountMeta::new(claim_address(&program_id, &dao_key, claim.claim_id).0, false)); // Claim account, changed only if it names the member
+        }
+        let instruction = Instruction {
+            program_id,
+            accounts,
+            data: DaoInstruction::Security(SecurityInstruction::ForgetMember).try_to_vec().unwrap(),
+        };
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[&payer, member],
+            recent_blockhash,
+        );
+        let result = banks_client.process_transaction(transaction).await;
+        (result, dao.reload(&mut banks_client, program_id, dao_key, &[]).await)
+    }
+
+    #[tokio::test]
+    async fn test_forget_member_clears_pii() {
+        let member = Keypair::new();
+        let dao = dao_with_claims(member.pubkey(), false);
+        let total_before: u64 = dao.claims.iter().map(|c| c.amount).sum();
+
+        let (result, dao) = forget_member(&dao, &member).await;
+        assert!(result.is_ok());
+
+        // The member's key and data hash are gone from state
+        assert!(!dao.members.iter().any(|m| m.member_address == member.pubkey()));
+        assert!(!dao.claims.iter().any(|c| c.member == member.pubkey()));
+        let anonymized = dao.members[0].member_address;
+        assert_eq!(dao.members[0].encrypted_data_hash, [0u8; 32]);
+        assert_eq!(dao.claims[0].member, anonymized);
+        assert_eq!(dao.claims[2].member, anonymized);
+
+        // Claim records and totals are untouched
+        assert_eq!(dao.claims.len(), 3);
+        assert_eq!(dao.claims.iter().map(|c| c.amount).sum::<u64>(), total_before);
+        assert_eq!(dao.members[1].encrypted_data_hash, [7u8; 32]);
+    }
+
+    #[tokio::test]
+    async fn test_forget_member_scrubs_every_reference() {
+        let member = Keypair::new();
+        let key = member.pubkey();
+        let provider = Pubkey::new_unique();
+        let mut dao = dao_with_claims(key, false);
+        let other = dao.members[1].member_address;
+        dao.risk_profiles.push(RiskProfile { member: key, coverage_limit: 5_000_000, ..Default::default() });
+        dao.payment_plans.push(PaymentPlan { member: key, total_owed: 400_000, amount_paid: 0, installment_amount: 100_000, installments_total: 4, installments_paid: 0, start_at: 0, interval_secs: 86_400 });
+        dao.preauths.push(PreAuth { preauth_id: 0, member: key, provider, service_type: ServiceType::default(), max_amount: 1_000_000, approved: true, expires_at: 0, used: false });
+        dao.verifier_pairings.push(VerifierPairing { verifier: key, provider, count: 2 });
+        dao.disputes.push(Dispute { initiator: key, respondent: other, votes: vec![(key, true), (other, false)], jurors: vec![key, other], ..Default::default() });
+        // The other member's claim was shared with and reviewed by the forgotten member
+        dao.claims[1].shares = vec![ClaimShare { member: other, amount: 1_500_000 }, ClaimShare { member: key, amount: 500_000 }];
+        dao.claims[1].verifiers = vec![key];
+
+        let (result, dao) = forget_member(&dao, &member).await;
+        result.unwrap();
+
+        let anonymized = dao.members[0].member_address;
+        assert_eq!(dao.risk_profiles[0].member, anonymized);
+        assert_eq!(dao.payment_plans[0].member, anonymized);
+        assert_eq!(dao.preauths[0].member, anonymized);
+        assert_eq!(dao.verifier_pairings[0].verifier, anonymized);
+        let dispute = &dao.disputes[0];
+        assert_eq!((dispute.initiator, dispute.respondent), (anonymized, other));
+        assert_eq!(dispute.votes, vec![(anonymized, true), (other, false)]);
+        assert_eq!(dispute.jurors, vec![anonymized, other]);
+        assert_eq!(dao.claims[1].member, other);
+        assert_eq!(dao.claims[1].shares[1].member, anonymized);
+        assert_eq!(dao.claims[1].verifiers, vec![anonymized]);
+    }
+
+    #[tokio::test]
+    async fn test_forget_member_blocked_by_legal_hold() {
+        let member = Keypair::new();
+        let dao = dao_with_claims(member.pubkey(), true);
+
+        let (result, dao) = forget_member(&dao, &member).await;
+        assert_eq!(result.unwrap_err().unwrap(), TransactionError::InstructionError(0, InstructionError::Custom(DaoError::LegalHold as u32)));
+        assert_eq!(dao.members[0].member_address, member.pubkey());
+        assert_eq!(dao.members[0].encrypted_data_hash, [7u8; 32]);
+    }
+
+    // Build a program-owned account holding the development verifying key, as SetVerifyingKey would leave it
+    fn dev_key_account(program_id: Pubkey) -> Account {
+        let data = groth16::dev_verifying_key();
+        Account { lamports: Rent::default().minimum_balance(data.len()), data, owner: program_id, executable: false, rent_epoch: 0 }
+    }
+
+    // Submit a ZKP claim from `member` carrying `zkp_proof` and `nonce`, returning the result and final DAO state. The
+    // member has already spent nonce 0, and the DAO checks proofs against the development key.
+    async fn submit_zkp_claim(member: &Keypair, amount: u64, provider: Pubkey, service_date: i64, zkp_proof: Vec<u8>, nonce: u64) -> (Result<(), BanksClientError>, Ledger) {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let record = Member { nonce: 1, ..member_record(member.pubkey(), false) };
+        let dao = Ledger::new(HealthInsuranceDAO { members: vec![record], ..Default::default() }, Vec::new());
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, 0);
+        program_test.add_account(verifying_key_address(&program_id, &dao_key).0, dev_key_account(program_id));
+        program_test.add_account(
+            member.pubkey(),
+            Account { lamports: 1_000_000_000, data: Vec::new(), owner: system_program::id(), executable: false, rent_epoch: 0 },
+        );
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                                   // DAO account
+                AccountMeta::new(member.pubkey(), true),                            // Member, funding the claim account
+                AccountMeta::new(claim_address(&program_id, &dao_key, 0).0, false), // Claim account
+                AccountMeta::new_readonly(system_program::id(), false),             // System program
+                AccountMeta::new_readonly(verifying_key_address(&program_id, &dao_key).0, false), // Verifying key
+            ],
+            data: DaoInstruction::Security(SecurityInstruction::SubmitClaim { amount, provider, service_date, zkp_proof, nonce }).try_to_vec().unwrap(),
+        };
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer, member], recent_blockhash);
+        let result = banks_client.process_transaction(transaction).await;
+        (result, dao.reload(&mut banks_client, program_id, dao_key, &[]).await)
+    }
+
+    #[tokio::test]
+    async fn test_zkp_claim_takes_its_proven_details() {
+        let member = Keypair::new();
+        let provider = Pubkey::new_unique();
+        let proof = groth16::dev_proof(&claim_statement(&member.pubkey(), 2_500_000, &provider, 1_000));
+
+        let (result, after) = submit_zkp_claim(&member, 2_500_000, provider, 1_000, proof.clone(), 1).await;
+        result.unwrap();
+        let claim = &after.claims[0];
+        assert_eq!((claim.member, claim.amount, claim.provider, claim.service_date), (member.pubkey(), 2_500_000, provider, 1_000));
+        assert_eq!(claim.zkp_proof, proof);
+        assert_eq!(after.members[0].nonce, 2);
+    }
+
+    #[tokio::test]
+    async fn test_zkp_claim_with_stale_nonce_rejected() {
+        let member = Keypair::new();
+        let provider = Pubkey::new_unique();
+        let proof = groth16::dev_proof(&claim_statement(&member.pubkey(), 2_500_000, &provider, 1_000));
+
+        // Replaying an earlier submission carries the already-spent nonce
+        let (result, after) = submit_zkp_claim(&member, 2_500_000, provider, 1_000, proof, 0).await;
+        assert_eq!(result.unwrap_err().unwrap(), TransactionError::InstructionError(0, InstructionError::Custom(DaoError::BadNonce as u32)));
+        assert!(after.claims.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_zkp_claim_rejects_proof_for_other_details() {
+        let member = Keypair::new();
+        let provider = Pubkey::new_unique();
+        // A valid proof for a smaller amount can't be replayed to claim a larger one
+        let proof = groth16::dev_proof(&claim_statement(&member.pubkey(), 1_000_000, &provider, 1_000));
+
+        let (result, after) = submit_zkp_claim(&member, 9_000_000, provider, 1_000, proof, 1).await;
+        assert_eq!(result.unwrap_err().unwrap(), TransactionError::InstructionError(0, InstructionError::Custom(DaoError::InvalidProof as u32)));
+        assert!(after.claims.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_zkp_claim_for_future_service_rejected() {
+        let member = Keypair::new();
+        let provider = Pubkey::new_unique();
+        // A proof doesn't exempt the claim from the claim window, which opens once the service has happened
+        let service_date = i64::MAX;
+        let proof = groth16::dev_proof(&claim_statement(&member.pubkey(), 2_500_000, &provider, service_date));
+
+        let (result, after) = submit_zkp_claim(&member, 2_500_000, provider, service_date, proof, 1).await;
+        assert_eq!(result.unwrap_err().unwrap(), TransactionError::InstructionError(0, InstructionError::Custom(DaoError::ClaimWindowExpired as u32)));
+        assert!(after.claims.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_verifying_key_set_once_by_admin() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let admin = Keypair::new();
+        let member = Keypair::new();
+        let provider = Pubkey::new_unique();
+        let (key_address, _) = verifying_key_address(&program_id, &dao_key);
+
+        let dao = Ledger::new(HealthInsuranceDAO { admin: admin.pubkey(), members: vec![member_record(member.pubkey(), false)], ..Default::default() }, Vec::new());
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        dao.add_accounts(&mut program_test, program_id, dao_key, 0);
+        for wallet in [admin.pubkey(), member.pubkey()] {
+            program_test.add_account(wallet, Account { lamports: 1_000_000_000, data: Vec::new(), owner: system_program::id(), executable: false, rent_epoch: 0 });
+        }
+        let (mut banks_client, payer, mut recent_blockhash) = program_test.start().await;
+
+        let set_key = |signer: &Keypair, verifying_key: Vec<u8>| Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                       // DAO account
+                AccountMeta::new(signer.pubkey(), true),                // Admin, funding the key account
+                AccountMeta::new(key_address, false),                   // Verifying key account
+                AccountMeta::new_readonly(system_program::id(), false), // System program
+            ],
+            data: DaoInstruction::Security(SecurityInstruction::SetVerifyingKey { verifying_key }).try_to_vec().unwrap(),
+        };
+        let submit = |nonce: u64| {
+            let zkp_proof = groth16::dev_proof(&claim_statement(&member.pubkey(), 2_500_000, &provider, 1_000));
+            Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(dao_key, false),                                       // DAO account
+                    AccountMeta::new(member.pubkey(), true),                                // Member, funding the claim account
+                    AccountMeta::new(claim_address(&program_id, &dao_key, nonce).0, false), // Claim account
+                    AccountMeta::new_readonly(system_program::id(), false),                 // System program
+                    AccountMeta::new_readonly(key_address, false),                          // Verifying key
+                ],
+                data: DaoInstruction::Security(SecurityInstruction::SubmitClaim { amount: 2_500_000, provider, service_date: 1_000, zkp_proof, nonce })
+                    .try_to_vec()
+                    .unwrap(),
+            }
+        };
+
+        // Until the admin stores a key, no proof can be accepted
+        let transaction = Transaction::new_signed_with_payer(&[submit(0)], Some(&payer.pubkey()), &[&payer, &member], recent_blockhash);
+        assert_eq!(
+            banks_client.process_transaction(transaction).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::VerifyingKeyNotSet as u32)),
+        );
+
+        // Only the admin can store it
+        let transaction = Transaction::new_signed_with_payer(&[set_key(&member, groth16::dev_verifying_key())], Some(&payer.pubkey()), &[&payer, &member], recent_blockhash);
+        assert_eq!(
+            banks_client.process_transaction(transaction).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::Unauthorized as u32)),
+        );
+        let transaction = Transaction::new_signed_with_payer(&[set_key(&admin, groth16::dev_verifying_key()), submit(0)], Some(&payer.pubkey()), &[&payer, &admin, &member], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+        assert_eq!(banks_client.get_account(key_address).await.unwrap().unwrap().data, groth16::dev_verifying_key());
+
+        // Once set, the key can't be swapped for one whose setup secrets someone else holds
+        let mut other_key = groth16::dev_verifying_key();
+        other_key.reverse();
+        recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+        let transaction = Transaction::new_signed_with_payer(&[set_key(&admin, other_key)], Some(&payer.pubkey()), &[&payer, &admin], recent_blockhash);
+        assert_eq!(
+            banks_client.process_transaction(transaction).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::AccountAlreadyInitialized),
+        );
+        let after = dao.reload(&mut banks_client, program_id, dao_key, &[]).await;
+        assert_eq!(after.claims.len(), 1);
+        assert_eq!(banks_client.get_account(key_address).await.unwrap().unwrap().data, groth16::dev_verifying_key());
+    }
+
+    // Attempt to configure a multi-sig of `signers` with the given threshold
+    async fn configure_multisig(threshold: u8, signers: Vec<Pubkey>) -> Result<(), BanksClientError> {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let admin = Keypair::new();
+
+        let mut dao = dao_with_claims(Pubkey::new_unique(), false);
+        dao.admin = admin.pubkey();
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        program_test.add_account(dao_key, dao_account(program_id, &dao));
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let data = DaoInstruction::Security(SecurityInstruction::ConfigureMultiSig { threshold, signers }).try_to_vec().unwrap();
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                // DAO account
+                AccountMeta::new_readonly(admin.pubkey(), true), // Admin
+            ],
+            data,
+        };
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[&payer, &admin],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(transaction).await
+    }
+
+    #[tokio::test]
+    async fn test_zero_multisig_threshold_rejected() {
+        let signers = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+        assert_eq!(
+            configure_multisig(0, signers).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::InvalidMultiSigThreshold as u32)),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_multisig_threshold_above_signer_count_rejected() {
+        let signers = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+        assert_eq!(
+            configure_multisig(3, signers).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::InvalidMultiSigThreshold as u32)),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_multisig_with_repeated_signer_rejected() {
+        // Listing one key twice would let a single signer meet a two-of-two threshold
+        let signer = Pubkey::new_unique();
+        assert_eq!(
+            configure_multisig(2, vec![signer, signer]).await.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::InvalidMultiSigThreshold as u32)),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_approval_of_executed_or_missing_proposal_rejected() {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let signer = Keypair::new();
+        let outsider = Keypair::new();
+
+        let mut dao = dao_with_claims(Pubkey::new_unique(), false);
+        dao.multi_sig_signers = vec![signer.pubkey(), Pubkey::new_unique()];
+        dao.multi_sig_threshold = 1;
+        dao.multi_sig_proposals.push(MultiSigProposal {
+            proposer: signer.pubkey(),
+            program_id: system_program::id(),
+            accounts: Vec::new(),
+            data: Vec::new(),
+            threshold: 1,
+            approvals: vec![signer.pubkey()],
+            executed: true,
+        });
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        program_test.add_account(dao_key, dao_account(program_id, &dao));
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let cases = [
+            (&signer, 0, DaoError::ProposalAlreadyExecuted), // A registered signer can't keep approving once it has run
+            (&signer, 1, DaoError::ProposalNotFound),
+            (&outsider, 0, DaoError::Unauthorized), // Signing isn't enough without being a registered signer
+        ];
+        for (approver, proposal_index, expected) in cases {
+            let instruction = Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(dao_key, false),                   // DAO account
+                    AccountMeta::new_readonly(approver.pubkey(), true), // Approver
+                ],
+                data: DaoInstruction::Security(SecurityInstruction::ApproveMultiSig { proposal_index }).try_to_vec().unwrap(),
+            };
+            let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer, approver], recent_blockhash);
+            assert_eq!(
+                banks_client.process_transaction(transaction).await.unwrap_err().unwrap(),
+                TransactionError::InstructionError(0, InstructionError::Custom(expected as u32)),
+            );
+        }
+    }
+
+    // Propose a transfer of `amount` lamports out of the multi-sig authority PDA, approve it with the first `approvals`
+    // of three signers, then try to execute it. Returns the execution result, the recipient's balance, and the DAO state.
+    async fn multisig_transfer(threshold: u8, approvals: usize, amount: u64) -> (Result<(), BanksClientError>, u64, HealthInsuranceDAO) {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let signers = [Keypair::new(), Keypair::new(), Keypair::new()];
+        let (authority, _) = multisig_authority(&program_id, &dao_key);
+
+        let mut dao = dao_with_claims(Pubkey::new_unique(), false);
+        dao.multi_sig_signers = signers.iter().map(|s| s.pubkey()).collect();
+        dao.multi_sig_threshold = threshold;
+
+        // Leave headroom so the proposal fits in the account
+        let mut account = dao_account(program_id, &dao);
+        account.data.resize(account.data.len() + 1_024, 0);
+        account.lamports = Rent::default().minimum_balance(account.data.len());
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        program_test.add_account(dao_key, account);
+        program_test.add_account(
+            authority,
+            Account { lamports: 10_000_000, data: Vec::new(), owner: system_program::id(), executable: false, rent_epoch: 0 },
+        );
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let transfer = system_instruction::transfer(&authority, &recipient, amount);
+        let propose = SecurityInstruction::ProposeMultiSig {
+            program_id: transfer.program_id,
+            accounts: transfer.accounts.iter().map(|a| ProposalAccount { pubkey: a.pubkey, is_signer: a.is_signer, is_writable: a.is_writable }).collect(),
+            data: transfer.data.clone(),
+        };
+        let mut steps = vec![(propose, &signers[0])];
+        steps.extend(signers.iter().take(approvals).map(|s| (SecurityInstruction::ApproveMultiSig { proposal_index: 0 }, s)));
+        for (step, signer) in steps {
+            let instruction = Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(dao_key, false),                 // DAO account
+                    AccountMeta::new_readonly(signer.pubkey(), true), // Multi-sig signer
+                ],
+                data: DaoInstruction::Security(step).try_to_vec().unwrap(),
+            };
+            let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer, signer], recent_blockhash);
+            banks_client.process_transaction(transaction).await.unwrap();
+        }
+
+        let execute = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                       // DAO account
+                AccountMeta::new(authority, false),                     // Multi-sig authority PDA
+                AccountMeta::new_readonly(system_program::id(), false), // Target program
+                AccountMeta::new(recipient, false),                     // Transfer recipient
+            ],
+            data: DaoInstruction::Security(SecurityInstruction::ExecuteMultiSig { proposal_index: 0 }).try_to_vec().unwrap(),
+        };
+        let transaction = Transaction::new_signed_with_payer(&[execute], Some(&payer.pubkey()), &[&payer], recent_blockhash);
+        let result = banks_client.process_transaction(transaction).await;
+
+        let received = banks_client.get_account(recipient).await.unwrap().map_or(0, |a| a.lamports);
+        let account = banks_client.get_account(dao_key).await.unwrap().unwrap();
+        (result, received, HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_multisig_executes_with_two_of_three_approvals() {
+        let (result, received, dao) = multisig_transfer(2, 2, 1_000_000).await;
+        assert!(result.is_ok());
+        assert_eq!(received, 1_000_000);
+        assert!(dao.multi_sig_proposals[0].executed);
+    }
+
+    #[tokio::test]
+    async fn test_multisig_rejected_with_one_of_three_approvals() {
+        let (result, received, dao) = multisig_transfer(2, 1, 1_000_000).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::InsufficientApprovals as u32)),
+        );
+        assert_eq!(received, 0);
+        assert!(!dao.multi_sig_proposals[0].executed);
+    }
+
+    // Have the admin nominate `nominee`, then have `acceptor` try to accept. Returns the acceptance result and final DAO state.
+    async fn admin_handoff(admin: &Keypair, nominee: Pubkey, acceptor: &Keypair) -> (Result<(), BanksClientError>, HealthInsuranceDAO) {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+
+        let mut dao = dao_with_claims(Pubkey::new_unique(), false);
+        dao.admin = admin.pubkey();
+
+        // Leave headroom for the nominee
+        let mut account = dao_account(program_id, &dao);
+        account.data.resize(account.data.len() + 32, 0);
+        account.lamports = Rent::default().minimum_balance(account.data.len());
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        program_test.add_account(dao_key, account);
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let mut results = Vec::new();
+        for (step, signer) in [(SecurityInstruction::ProposeAdmin { nominee }, admin), (SecurityInstruction::AcceptAdmin, acceptor)] {
+            let instruction = Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(dao_key, false),                 // DAO account
+                    AccountMeta::new_readonly(signer.pubkey(), true), // Admin or nominee
+                ],
+                data: DaoInstruction::Security(step).try_to_vec().unwrap(),
+            };
+            let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer, signer], recent_blockhash);
+            results.push(banks_client.process_transaction(transaction).await);
+        }
+        assert!(results[0].is_ok());
+
+        let account = banks_client.get_account(dao_key).await.unwrap().unwrap();
+        (results.pop().unwrap(), HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_nominee_accepts_admin_handoff() {
+        let admin = Keypair::new();
+        let nominee = Keypair::new();
+
+        let (result, dao) = admin_handoff(&admin, nominee.pubkey(), &nominee).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.admin, nominee.pubkey());
+        assert_eq!(dao.pending_admin, None);
+    }
+
+    #[tokio::test]
+    async fn test_admin_handoff_rejects_other_acceptor() {
+        let admin = Keypair::new();
+        let nominee = Pubkey::new_unique();
+        let intruder = Keypair::new();
+
+        let (result, dao) = admin_handoff(&admin, nominee, &intruder).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::Unauthorized as u32)),
+        );
+        assert_eq!(dao.admin, admin.pubkey());
+        assert_eq!(dao.pending_admin, Some(nominee));
+    }
+
+    // Have `signer` set `member`'s role, returning the result and final DAO state
+    async fn set_member_role(dao: &HealthInsuranceDAO, signer: &Keypair, member: Pubkey, role: Role) -> (Result<(), BanksClientError>, HealthInsuranceDAO) {
+        let program_id = Pubkey::new_unique();
+        let dao_key = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::new("health_insurance_dao", program_id, processor!(process_instruction));
+        program_test.add_account(dao_key, dao_account(program_id, dao));
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let instruction = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(dao_key, false),                 // DAO account
+                AccountMeta::new_readonly(signer.pubkey(), true), // Admin
+            ],
+            data: DaoInstruction::Security(SecurityInstruction::SetMemberRole { member, role }).try_to_vec().unwrap(),
+        };
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer, signer], recent_blockhash);
+        let result = banks_client.process_transaction(transaction).await;
+
+        let account = banks_client.get_account(dao_key).await.unwrap().unwrap();
+        (result, HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_admin_promotes_member_to_verifier() {
+        let admin = Keypair::new();
+        let member = Pubkey::new_unique();
+        let mut dao = dao_with_claims(member, false);
+        dao.admin = admin.pubkey();
+
+        let (result, dao) = set_member_role(&dao, &admin, member, Role::Verifier).await;
+        assert!(result.is_ok());
+        assert_eq!(dao.members[0].role, Role::Verifier);
+
+        let (result, _) = set_member_role(&dao, &admin, Pubkey::new_unique(), Role::Verifier).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(DaoError::NotAMember as u32)),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_non_admin_cannot_set_roles() {
+        let member = Keypair::new();
+        let dao = dao_with_claims(member.pubkey(), false);
+
+        // A member can neither promote themselves to verifier nor grant themselves admin
+        for role in [Role::Verifier, Role::Admin] {
+            let (result, after) = set_member_role(&dao, &member, member.pubkey(), role).await;
+            assert_eq!(
+                result.unwrap_err().unwrap(),
+                TransactionError::InstructionError(0, InstructionError::Custom(DaoError::Unauthorized as u32)),
+            );
+            assert_eq!(after.members[0].role, Role::Member);
+        }
+    }
+
+    #[test]
+    fn test_last_admin_cannot_be_demoted() {
+        let delegate = Pubkey::new_unique();
+        let mut dao = HealthInsuranceDAO {
+            members: vec![Member { member_address: delegate, role: Role::Admin, ..Default::default() }],
+            ..Default::default()
+        };
+        assert_eq!(remaining_admins(&dao, &delegate), 0);
+
+        dao.admin = Pubkey::new_unique();
+        assert_eq!(remaining_admins(&dao, &delegate), 1);
+    }
+
+    #[test]
+    fn test_multisig_threshold_bounds() {
+        let signers = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        assert!(!multisig_threshold_in_range(0, &[]));
+        assert!(multisig_threshold_in_range(1, &signers));
+        assert!(multisig_threshold_in_range(3, &signers));
+        assert!(!multisig_threshold_in_range(4, &signers));
+    }
+
+    #[test]
+    fn test_duplicate_multisig_signers_rejected() {
+        let signer = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        assert!(!multisig_threshold_in_range(2, &[signer, signer]));
+        assert!(!multisig_threshold_in_range(1, &[signer, other, signer]));
+        assert!(multisig_threshold_in_range(2, &[signer, other]));
+    }
+
+    #[test]
+    fn test_repeated_approvals_count_once() {
+        let signers = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        assert_eq!(counted_approvals(&[signers[0], signers[0]], &signers), 1);
+        assert_eq!(counted_approvals(&[signers[0], signers[1], signers[0]], &signers), 2);
+        assert_eq!(counted_approvals(&[signers[0], Pubkey::new_unique()], &signers), 1); // Unregistered keys don't count
+    }
+}
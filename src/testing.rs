@@ -0,0 +1,130 @@
+// Fixtures shared by the module tests: DAO state together with the claim accounts it keeps totals over
+
+use std::ops::{Deref, DerefMut};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{instruction::Instruction, pubkey::Pubkey, rent::Rent};
+use solana_program_test::{BanksClient, ProgramTest};
+use solana_sdk::account::Account;
+
+use crate::claims::{self, claim_address, ref_marker_address, service_key, service_marker_address};
+use crate::{Claim, ClaimHistory, HealthInsuranceDAO};
+
+// Spare bytes allocated with the test DAO account, which still grows with members, provider totals, and claim history
+const DAO_HEADROOM: usize = 1_024;
+
+// DAO state and its live claims, which the program keeps in one PDA per claim. Tests build and inspect claims
+// through `claims`, indexed by claim id until any are archived; everything else derefs to the DAO.
+#[derive(Clone)]
+pub(crate) struct Ledger {
+    pub dao: HealthInsuranceDAO,
+    pub claims: Vec<Claim>,
+    pub markers: Vec<(Pubkey, Account)>, // Duplicate-check marker accounts written by earlier submissions
+}
+
+impl Ledger {
+    pub fn new(dao: HealthInsuranceDAO, claims: Vec<Claim>) -> Self {
+        Self { dao, claims, markers: Vec::new() }
+    }
+
+    // The DAO as the program would hold it: ids allocated past every claim, and running totals kept over the claims
+    pub fn tallied_dao(&self) -> HealthInsuranceDAO {
+        let mut dao = HealthInsuranceDAO::try_from_slice(&self.dao.try_to_vec().unwrap()).unwrap();
+        let next_id = self.claims.iter().map(|c| c.claim_id + 1).max().unwrap_or(0);
+        dao.claims_count = dao.claims_count.max(next_id);
+        dao.outstanding_claims = 0;
+        dao.open_claim_exposure = 0;
+        dao.provider_claims.clear();
+        for member in dao.members.iter_mut() {
+            member.claim_history = ClaimHistory::default();
+        }
+        for claim in &self.claims {
+            claims::track_claim(&mut dao, claim);
+        }
+        dao
+    }
+
+    // Seed the DAO account, holding `lamports` above rent, along with every claim and marker account. Claims built
+    // by the test get the markers a submission would have written, unless a marker was read back from an earlier run.
+    pub fn add_accounts(&self, program_test: &mut ProgramTest, program_id: Pubkey, dao_key: Pubkey, lamports: u64) {
+        let mut data = self.tallied_dao().try_to_vec().unwrap();
+        data.resize(data.len() + DAO_HEADROOM, 0);
+        program_test.add_account(dao_key, program_account(program_id, data, lamports));
+
+        // Markers point at the latest claim written for them, so later claims take over a shared service marker
+        let mut markers: Vec<(Pubkey, u64)> = Vec::new();
+        for claim in &self.claims {
+            let mut data = claim.try_to_vec().unwrap();
+            data.resize(data.len() + claims::CLAIM_ACCOUNT_HEADROOM, 0);
+            program_test.add_account(claim_address(&program_id, &dao_key, claim.claim_id).0, program_account(program_id, data, 0));
+
+            let service = service_key(&claim.member, &claim.provider, claim.service_date, claim.amount);
+            for key in [ref_marker_address(&program_id, &dao_key, &claim.external_ref).0, service_marker_address(&program_id, &dao_key, &service).0] {
+                markers.retain(|(marked, _)| *marked != key);
+                markers.push((key, claim.claim_id));
+            }
+        }
+        for (key, claim_id) in markers {
+            if self.markers.iter().all(|(read, _)| *read != key) {
+                program_test.add_account(key, program_account(program_id, claim_id.to_le_bytes().to_vec(), 0));
+            }
+        }
+        for (key, account) in &self.markers {
+            program_test.add_account(*key, account.clone());
+        }
+    }
+
+    // Read back the DAO, its live claims, and the marker accounts seeded here or written by `instructions`
+    pub async fn reload(&self, banks_client: &mut BanksClient, program_id: Pubkey, dao_key: Pubkey, instructions: &[Instruction]) -> Ledger {
+        let account = banks_client.get_account(dao_key).await.unwrap().unwrap();
+        let dao = HealthInsuranceDAO::deserialize(&mut &account.data[..]).unwrap();
+
+        let mut claims = Vec::new();
+        let mut claim_keys = Vec::new();
+        for claim_id in 0..dao.claims_count {
+            let key = claim_address(&program_id, &dao_key, claim_id).0;
+            claim_keys.push(key);
+            if let Some(account) = banks_client.get_account(key).await.unwrap() {
+                if account.owner == program_id && !account.data.is_empty() {
+                    claims.push(Claim::deserialize(&mut &account.data[..]).unwrap());
+                }
+            }
+        }
+
+        let mut marker_keys: Vec<Pubkey> = self.markers.iter().map(|(key, _)| *key).collect();
+        for meta in instructions.iter().flat_map(|i| i.accounts.iter()) {
+            if meta.pubkey != dao_key && !claim_keys.contains(&meta.pubkey) && !marker_keys.contains(&meta.pubkey) {
+                marker_keys.push(meta.pubkey);
+            }
+        }
+        let mut markers = Vec::new();
+        for key in marker_keys {
+            if let Some(account) = banks_client.get_account(key).await.unwrap() {
+                if account.owner == program_id {
+                    markers.push((key, account));
+                }
+            }
+        }
+
+        Ledger { dao, claims, markers }
+    }
+}
+
+impl Deref for Ledger {
+    type Target = HealthInsuranceDAO;
+
+    fn deref(&self) -> &HealthInsuranceDAO {
+        &self.dao
+    }
+}
+
+impl DerefMut for Ledger {
+    fn deref_mut(&mut self) -> &mut HealthInsuranceDAO {
+        &mut self.dao
+    }
+}
+
+// Program-owned account holding `data`, with `lamports` above rent
+fn program_account(program_id: Pubkey, data: Vec<u8>, lamports: u64) -> Account {
+    Account { lamports: Rent::default().minimum_balance(data.len()) + lamports, data, owner: program_id, executable: false, rent_epoch: 0 }
+}